@@ -0,0 +1,71 @@
+use crate::util::{escape_rust_keyword, field_not_ignored, trim_starting_raw_identifier};
+use heck::CamelCase;
+use proc_macro2::{Ident, TokenStream};
+use quote::{format_ident, quote, quote_spanned};
+use syn::{punctuated::Punctuated, token::Comma, Data, DataStruct, Field, Fields, Lit, Meta, Type};
+
+/// Method to derive an [ActiveModelFactory](sea_orm::ActiveModelFactory) for the ActiveModel
+pub fn expand_derive_factory(ident: Ident, data: Data) -> syn::Result<TokenStream> {
+    let fields = match data {
+        Data::Struct(DataStruct {
+            fields: Fields::Named(named),
+            ..
+        }) => named.named,
+        _ => {
+            return Ok(quote_spanned! {
+                ident.span() => compile_error!("you can only derive DeriveFactory on structs");
+            })
+        }
+    }
+    .into_iter()
+    .filter(field_not_ignored);
+
+    let name: Vec<Ident> = fields
+        .clone()
+        .map(|field| {
+            let ident = field.ident.as_ref().unwrap().to_string();
+            let ident = trim_starting_raw_identifier(ident).to_camel_case();
+            let ident = escape_rust_keyword(ident);
+            let mut ident = format_ident!("{}", &ident);
+            for attr in field.attrs.iter() {
+                if let Some(ident) = attr.path.get_ident() {
+                    if ident != "sea_orm" {
+                        continue;
+                    }
+                } else {
+                    continue;
+                }
+                if let Ok(list) = attr.parse_args_with(Punctuated::<Meta, Comma>::parse_terminated)
+                {
+                    for meta in list.iter() {
+                        if let Meta::NameValue(nv) = meta {
+                            if let Some(name) = nv.path.get_ident() {
+                                if name == "enum_name" {
+                                    if let Lit::Str(litstr) = &nv.lit {
+                                        ident = syn::parse_str(&litstr.value()).unwrap();
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+            ident
+        })
+        .collect();
+
+    let ty: Vec<Type> = fields.map(|Field { ty, .. }| ty).collect();
+
+    Ok(quote!(
+        #[automatically_derived]
+        impl sea_orm::ActiveModelFactory for ActiveModel {
+            fn fake_value(c: <Self::Entity as EntityTrait>::Column, seq: u64) -> sea_orm::Value {
+                let column = sea_orm::IdenStatic::as_str(&c);
+                match c {
+                    #(<Self::Entity as EntityTrait>::Column::#name => <#ty as sea_orm::FakeValue>::fake_value(column, seq).into(),)*
+                    _ => panic!("This ActiveModel does not have this field"),
+                }
+            }
+        }
+    ))
+}
@@ -4,9 +4,11 @@ mod active_model_behavior;
 mod column;
 mod entity;
 mod entity_model;
+mod factory;
 mod from_query_result;
 mod into_active_model;
 mod model;
+mod partial_model;
 mod primary_key;
 mod relation;
 
@@ -16,8 +18,10 @@ pub use active_model_behavior::*;
 pub use column::*;
 pub use entity::*;
 pub use entity_model::*;
+pub use factory::*;
 pub use from_query_result::*;
 pub use into_active_model::*;
 pub use model::*;
+pub use partial_model::*;
 pub use primary_key::*;
 pub use relation::*;
@@ -0,0 +1,217 @@
+use heck::CamelCase;
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote, quote_spanned};
+use syn::{ext::IdentExt, punctuated::Punctuated, token::Comma, Lit, Meta};
+
+enum Error {
+    InputNotStruct,
+    EntityNotSpecified(Span),
+    Syn(syn::Error),
+}
+
+enum ColumnAs {
+    /// A column of the Entity, selected with an alias named after the field
+    Col(syn::Ident),
+    /// An expression, selected with an alias named after the field
+    Expr(syn::Expr),
+    /// A nested partial model, its columns are selected with the field name as prefix
+    Nested(syn::Type),
+}
+
+struct DerivePartialModel {
+    entity: Option<syn::Type>,
+    ident: syn::Ident,
+    fields: Vec<(syn::Ident, ColumnAs)>,
+}
+
+impl DerivePartialModel {
+    fn new(input: syn::DeriveInput) -> Result<Self, Error> {
+        let fields = match input.data {
+            syn::Data::Struct(syn::DataStruct {
+                fields: syn::Fields::Named(syn::FieldsNamed { named, .. }),
+                ..
+            }) => named,
+            _ => return Err(Error::InputNotStruct),
+        };
+
+        let mut entity = None;
+        for meta in sea_orm_metas(&input.attrs).map_err(Error::Syn)? {
+            if let Meta::NameValue(nv) = meta {
+                if nv.path.is_ident("entity") {
+                    entity = Some(parse_lit_str::<syn::Type>(&nv.lit).map_err(Error::Syn)?);
+                }
+            }
+        }
+
+        let mut column_as_list = Vec::new();
+        for field in fields {
+            let field_ident = field.ident.unwrap();
+            let mut from_col = None;
+            let mut from_expr = None;
+            let mut nested = false;
+
+            for meta in sea_orm_metas(&field.attrs).map_err(Error::Syn)? {
+                match meta {
+                    Meta::Path(path) if path.is_ident("nested") => nested = true,
+                    Meta::NameValue(nv) if nv.path.is_ident("from_col") => {
+                        from_col = Some(parse_lit_str::<syn::Ident>(&nv.lit).map_err(Error::Syn)?);
+                    }
+                    Meta::NameValue(nv) if nv.path.is_ident("from_expr") => {
+                        from_expr = Some(parse_lit_str::<syn::Expr>(&nv.lit).map_err(Error::Syn)?);
+                    }
+                    _ => {}
+                }
+            }
+
+            let column_as = match (nested, from_col, from_expr) {
+                (true, None, None) => ColumnAs::Nested(field.ty),
+                (false, None, Some(expr)) => ColumnAs::Expr(expr),
+                (false, col, None) => {
+                    if entity.is_none() {
+                        return Err(Error::EntityNotSpecified(field_ident.span()));
+                    }
+                    let col = col.unwrap_or_else(|| field_ident.clone());
+                    let col = format_ident!("{}", col.unraw().to_string().to_camel_case());
+                    ColumnAs::Col(col)
+                }
+                _ => {
+                    return Err(Error::Syn(syn::Error::new_spanned(
+                        field_ident,
+                        "only one of `nested`, `from_col` and `from_expr` can be specified",
+                    )))
+                }
+            };
+            column_as_list.push((field_ident, column_as));
+        }
+
+        Ok(Self {
+            entity,
+            ident: input.ident,
+            fields: column_as_list,
+        })
+    }
+
+    fn expand(&self) -> syn::Result<TokenStream> {
+        let impl_partial_model_trait = self.impl_partial_model_trait();
+        let impl_from_query_result = self.impl_from_query_result();
+
+        Ok(quote!(
+            #impl_partial_model_trait
+
+            #impl_from_query_result
+        ))
+    }
+
+    fn impl_partial_model_trait(&self) -> TokenStream {
+        let Self {
+            entity,
+            ident,
+            fields,
+        } = self;
+
+        let select_cols = fields.iter().map(|(field_ident, column_as)| {
+            let name = field_ident.unraw().to_string();
+            match column_as {
+                ColumnAs::Col(col) => quote!(
+                    let select = sea_orm::QuerySelect::column_as(
+                        select,
+                        <#entity as sea_orm::EntityTrait>::Column::#col,
+                        format!("{}{}", pre, #name),
+                    );
+                ),
+                ColumnAs::Expr(expr) => quote!(
+                    let select = sea_orm::QuerySelect::column_as(
+                        select,
+                        std::convert::Into::<sea_orm::sea_query::SimpleExpr>::into(#expr),
+                        format!("{}{}", pre, #name),
+                    );
+                ),
+                ColumnAs::Nested(ty) => quote!(
+                    let select = <#ty as sea_orm::PartialModelTrait>::select_cols_nested(
+                        select,
+                        &format!("{}{}_", pre, #name),
+                    );
+                ),
+            }
+        });
+
+        quote!(
+            #[automatically_derived]
+            impl sea_orm::PartialModelTrait for #ident {
+                fn select_cols_nested<S: sea_orm::QuerySelect>(select: S, pre: &str) -> S {
+                    #(#select_cols)*
+                    select
+                }
+            }
+        )
+    }
+
+    fn impl_from_query_result(&self) -> TokenStream {
+        let Self { ident, fields, .. } = self;
+
+        let field_values = fields.iter().map(|(field_ident, column_as)| {
+            let name = field_ident.unraw().to_string();
+            match column_as {
+                ColumnAs::Col(_) | ColumnAs::Expr(_) => quote!(
+                    #field_ident: row.try_get(pre, #name)?
+                ),
+                ColumnAs::Nested(ty) => quote!(
+                    #field_ident: <#ty as sea_orm::FromQueryResult>::from_query_result(
+                        row,
+                        &format!("{}{}_", pre, #name),
+                    )?
+                ),
+            }
+        });
+
+        quote!(
+            #[automatically_derived]
+            impl sea_orm::FromQueryResult for #ident {
+                fn from_query_result(row: &sea_orm::QueryResult, pre: &str) -> std::result::Result<Self, sea_orm::DbErr> {
+                    Ok(Self {
+                        #(#field_values),*
+                    })
+                }
+            }
+        )
+    }
+}
+
+fn sea_orm_metas(attrs: &[syn::Attribute]) -> syn::Result<Vec<Meta>> {
+    let mut metas = Vec::new();
+    for attr in attrs {
+        if !attr.path.is_ident("sea_orm") {
+            continue;
+        }
+        let list = attr.parse_args_with(Punctuated::<Meta, Comma>::parse_terminated)?;
+        metas.extend(list);
+    }
+    Ok(metas)
+}
+
+fn parse_lit_str<T: syn::parse::Parse>(lit: &Lit) -> syn::Result<T> {
+    match lit {
+        Lit::Str(litstr) => litstr.parse(),
+        _ => Err(syn::Error::new_spanned(
+            lit,
+            "attribute value must be a string",
+        )),
+    }
+}
+
+/// Method to derive a [PartialModelTrait](sea_orm::PartialModelTrait) and a
+/// [FromQueryResult](sea_orm::FromQueryResult) for a struct
+pub fn expand_derive_partial_model(input: syn::DeriveInput) -> syn::Result<TokenStream> {
+    let ident_span = input.ident.span();
+
+    match DerivePartialModel::new(input) {
+        Ok(partial_model) => partial_model.expand(),
+        Err(Error::InputNotStruct) => Ok(quote_spanned! {
+            ident_span => compile_error!("you can only derive DerivePartialModel on structs");
+        }),
+        Err(Error::EntityNotSpecified(span)) => Ok(quote_spanned! {
+            span => compile_error!("`#[sea_orm(entity = \"..\")]` is required to select a column of the Entity");
+        }),
+        Err(Error::Syn(err)) => Err(err),
+    }
+}
@@ -419,6 +419,40 @@ pub fn derive_into_active_model(input: TokenStream) -> TokenStream {
         .into()
 }
 
+/// The DeriveFactory derive macro will implement ActiveModelFactory for ActiveModel,
+/// producing a fake value for each field with [FakeValue](sea_orm::FakeValue)
+///
+/// ### Usage
+///
+/// ```
+/// use sea_orm::entity::prelude::*;
+///
+/// #[derive(Clone, Debug, PartialEq, DeriveEntityModel, DeriveFactory)]
+/// #[sea_orm(table_name = "cake")]
+/// pub struct Model {
+///     #[sea_orm(primary_key)]
+///     pub id: i32,
+///     pub name: String,
+/// }
+///
+/// #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+/// pub enum Relation {}
+///
+/// impl ActiveModelBehavior for ActiveModel {}
+///
+/// let cake = ActiveModel::factory().build();
+/// assert_eq!(cake.name, sea_orm::ActiveValue::set("name 1".to_owned()));
+/// ```
+#[proc_macro_derive(DeriveFactory, attributes(sea_orm))]
+pub fn derive_factory(input: TokenStream) -> TokenStream {
+    let DeriveInput { ident, data, .. } = parse_macro_input!(input);
+
+    match derives::expand_derive_factory(ident, data) {
+        Ok(ts) => ts.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
 /// Models that a user can override
 ///
 /// ### Usage
@@ -550,6 +584,54 @@ pub fn derive_from_query_result(input: TokenStream) -> TokenStream {
     }
 }
 
+/// The DerivePartialModel derive macro will implement `sea_orm::PartialModelTrait` and
+/// `sea_orm::FromQueryResult` for a struct holding a subset of the columns of an Entity.
+///
+/// ### Usage
+///
+/// ```
+/// use sea_orm::{entity::prelude::*, sea_query::Expr, DerivePartialModel};
+///
+/// #[derive(Debug, DerivePartialModel)]
+/// #[sea_orm(entity = "sea_orm::tests_cfg::fruit::Entity")]
+/// struct FruitName {
+///     name: String,
+/// }
+///
+/// #[derive(Debug, DerivePartialModel)]
+/// #[sea_orm(entity = "sea_orm::tests_cfg::cake::Entity")]
+/// struct CakeSummary {
+///     #[sea_orm(from_col = "id")]
+///     cake_id: i32,
+///     name: String,
+///     #[sea_orm(from_expr = "Expr::cust(\"1\")")]
+///     one: i32,
+///     #[sea_orm(nested)]
+///     fruit: FruitName,
+/// }
+/// ```
+///
+/// # Macro Attributes
+///
+/// All macro attributes listed below have to be annotated in the form of `#[sea_orm(attr = value)]`.
+///
+/// - For struct
+///     - `entity`: The Entity the columns are selected from, i.e. `entity = "cake::Entity"`
+///         - It can be omitted when every field is annotated with `from_expr` or `nested`
+///
+/// - For field
+///     - `from_col`: Select the column of another name, i.e. `from_col = "id"`
+///     - `from_expr`: Select an expression instead of a column, i.e. `from_expr = "Expr::cust(\"1\")"`
+///     - `nested`: The field is another partial model, whose columns are selected and read
+///       with the field name followed by `_` as prefix
+#[proc_macro_derive(DerivePartialModel, attributes(sea_orm))]
+pub fn derive_partial_model(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    derives::expand_derive_partial_model(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
 /// The DeriveRelation derive macro will implement RelationTrait for Relation.
 ///
 /// ### Usage
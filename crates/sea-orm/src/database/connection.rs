@@ -1,8 +1,9 @@
 use crate::{
-    DatabaseTransaction, DbBackend, DbErr, ExecResult, QueryResult, Statement, TransactionError,
+    AccessMode, DatabaseTransaction, DbBackend, DbErr, ExecResult, IsolationLevel, QueryResult,
+    RetryPolicy, Statement, TransactionError,
 };
 use futures::Stream;
-use std::{future::Future, pin::Pin};
+use std::{future::Future, pin::Pin, time::Duration};
 
 /// Creates constraints for any structure that can create a database connection
 /// and execute SQL statements
@@ -21,6 +22,37 @@ pub trait ConnectionTrait: Sync {
     /// Execute a [Statement] and return a collection Vec<[QueryResult]> on success
     async fn query_all(&self, stmt: Statement) -> Result<Vec<QueryResult>, DbErr>;
 
+    /// Execute a [Statement] with a timeout overriding the statement timeout of the connection,
+    /// see [ConnectOptions::statement_timeout](crate::ConnectOptions::statement_timeout).
+    /// The timeout is ignored by default, for connections without statement timeouts such as the Mock database
+    async fn execute_with_timeout(
+        &self,
+        stmt: Statement,
+        _timeout: Option<Duration>,
+    ) -> Result<ExecResult, DbErr> {
+        self.execute(stmt).await
+    }
+
+    /// Same as [ConnectionTrait::query_one], with a timeout overriding the statement timeout of the connection.
+    /// The timeout is ignored by default
+    async fn query_one_with_timeout(
+        &self,
+        stmt: Statement,
+        _timeout: Option<Duration>,
+    ) -> Result<Option<QueryResult>, DbErr> {
+        self.query_one(stmt).await
+    }
+
+    /// Same as [ConnectionTrait::query_all], with a timeout overriding the statement timeout of the connection.
+    /// The timeout is ignored by default
+    async fn query_all_with_timeout(
+        &self,
+        stmt: Statement,
+        _timeout: Option<Duration>,
+    ) -> Result<Vec<QueryResult>, DbErr> {
+        self.query_all(stmt).await
+    }
+
     /// Check if the connection supports `RETURNING` syntax on insert and update
     fn support_returning(&self) -> bool {
         let db_backend = self.get_database_backend();
@@ -31,6 +63,48 @@ pub trait ConnectionTrait: Sync {
     fn is_mock_connection(&self) -> bool {
         false
     }
+
+    /// Check if the database can be reached, by running `SELECT 1` by default
+    async fn ping(&self) -> Result<(), DbErr> {
+        let stmt = Statement::from_string(self.get_database_backend(), "SELECT 1".to_owned());
+        self.query_one(stmt).await.map(|_| ())
+    }
+
+    /// Close the connection pool, waiting for the statements in progress to complete.
+    /// Does nothing by default, for connections without a pool such as a transaction
+    async fn close(&self) {}
+
+    /// Get the statistics of the connection pool.
+    /// Every count is zero by default, for connections without a pool
+    fn pool_stats(&self) -> PoolStats {
+        PoolStats::default()
+    }
+}
+
+/// Statistics of a connection pool, see [ConnectionTrait::pool_stats]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PoolStats {
+    /// The number of open connections, idle or in use
+    pub size: u32,
+    /// The number of idle connections
+    pub idle: u32,
+    /// The number of connections in use
+    pub in_use: u32,
+    /// The number of tasks waiting to acquire a connection
+    pub waiting: u32,
+}
+
+impl std::ops::Add for PoolStats {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self {
+            size: self.size + other.size,
+            idle: self.idle + other.idle,
+            in_use: self.in_use + other.in_use,
+            waiting: self.waiting + other.waiting,
+        }
+    }
 }
 
 /// Stream query results
@@ -53,6 +127,27 @@ pub trait TransactionTrait {
     /// Returns a Transaction that can be committed or rolled back
     async fn begin(&self) -> Result<DatabaseTransaction, DbErr>;
 
+    /// Execute SQL `BEGIN` transaction with an optional isolation level and access mode.
+    /// Returns a Transaction that can be committed or rolled back.
+    ///
+    /// By default, the transaction is begun with [TransactionTrait::begin]
+    /// and giving an isolation level or access mode returns an error
+    async fn begin_with_config(
+        &self,
+        isolation_level: Option<IsolationLevel>,
+        access_mode: Option<AccessMode>,
+    ) -> Result<DatabaseTransaction, DbErr>
+    where
+        Self: Sync,
+    {
+        if isolation_level.is_some() || access_mode.is_some() {
+            return Err(DbErr::Exec(
+                "Isolation level and access mode are not supported by the connection".to_owned(),
+            ));
+        }
+        self.begin().await
+    }
+
     /// Execute the function inside a transaction.
     /// If the function returns an error, the transaction will be rolled back. If it does not return an error, the transaction will be committed.
     async fn transaction<F, T, E>(&self, callback: F) -> Result<T, TransactionError<E>>
@@ -63,4 +158,67 @@ pub trait TransactionTrait {
             + Send,
         T: Send,
         E: std::error::Error + Send;
+
+    /// Execute the function inside a transaction with an optional isolation level and access mode.
+    /// If the function returns an error, the transaction will be rolled back. If it does not return an error, the transaction will be committed.
+    ///
+    /// By default, the transaction is begun with [TransactionTrait::begin_with_config]
+    async fn transaction_with_config<F, T, E>(
+        &self,
+        callback: F,
+        isolation_level: Option<IsolationLevel>,
+        access_mode: Option<AccessMode>,
+    ) -> Result<T, TransactionError<E>>
+    where
+        F: for<'c> FnOnce(
+                &'c DatabaseTransaction,
+            ) -> Pin<Box<dyn Future<Output = Result<T, E>> + Send + 'c>>
+            + Send,
+        T: Send,
+        E: std::error::Error + Send,
+        Self: Sync,
+    {
+        let transaction = self
+            .begin_with_config(isolation_level, access_mode)
+            .await
+            .map_err(TransactionError::Connection)?;
+        transaction.run(callback).await
+    }
+
+    /// Execute the function inside a transaction, and run the whole transaction again
+    /// when it fails because of a concurrent transaction, see [TransactionError::is_retryable].
+    /// The function may therefore be called several times, up to the attempts of the [RetryPolicy].
+    /// Each attempt is run with [TransactionTrait::transaction_with_config], with the optional isolation level and access mode.
+    async fn transaction_with_retry<F, T, E>(
+        &self,
+        policy: RetryPolicy,
+        callback: F,
+        isolation_level: Option<IsolationLevel>,
+        access_mode: Option<AccessMode>,
+    ) -> Result<T, TransactionError<E>>
+    where
+        F: for<'c> Fn(
+                &'c DatabaseTransaction,
+            ) -> Pin<Box<dyn Future<Output = Result<T, E>> + Send + 'c>>
+            + Send
+            + Sync,
+        T: Send,
+        E: std::error::Error + Send + 'static,
+        Self: Sync,
+    {
+        let mut attempt = 1;
+        loop {
+            match self
+                .transaction_with_config(|txn| callback(txn), isolation_level, access_mode)
+                .await
+            {
+                Err(err) if err.is_retryable() && attempt < policy.get_max_attempts() => {
+                    tracing::debug!("Retrying transaction after attempt {}: {}", attempt, err);
+                    crate::database::sleep(policy.get_backoff(attempt)).await;
+                    attempt += 1;
+                }
+                res => return res,
+            }
+        }
+    }
 }
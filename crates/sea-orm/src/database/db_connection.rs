@@ -1,16 +1,16 @@
 use crate::{
-    error::*, ConnectionTrait, DatabaseTransaction, ExecResult, QueryResult, Statement,
-    StatementBuilder, StreamTrait, TransactionError, TransactionTrait,
+    error::*, AccessMode, ConnectionTrait, DatabaseTransaction, ExecResult, IsolationLevel,
+    PoolStats, QueryResult, Statement, StatementBuilder, StreamTrait, TransactionError,
+    TransactionTrait,
 };
 use sea_query::{MysqlQueryBuilder, PostgresQueryBuilder, QueryBuilder, SqliteQueryBuilder};
-use std::{future::Future, pin::Pin};
+use std::{future::Future, pin::Pin, time::Duration};
 use tracing::instrument;
 use url::Url;
 
 #[cfg(feature = "sqlx-dep")]
 use sqlx::pool::PoolConnection;
 
-#[cfg(feature = "mock")]
 use std::sync::Arc;
 
 /// Handle a database connection depending on the backend
@@ -29,6 +29,8 @@ pub enum DatabaseConnection {
     /// Create a  Mock database connection useful for testing
     #[cfg(feature = "mock")]
     MockDatabaseConnection(Arc<crate::MockDatabaseConnection>),
+    /// Create a primary database connection with read replicas
+    ReplicatedConnection(Box<crate::ReplicatedConnection>),
     /// The connection to the database has been severed
     Disconnected,
 }
@@ -82,6 +84,7 @@ impl std::fmt::Debug for DatabaseConnection {
                 Self::SqlxSqlitePoolConnection(_) => "SqlxSqlitePoolConnection",
                 #[cfg(feature = "mock")]
                 Self::MockDatabaseConnection(_) => "MockDatabaseConnection",
+                Self::ReplicatedConnection(_) => "ReplicatedConnection",
                 Self::Disconnected => "Disconnected",
             }
         )
@@ -100,58 +103,184 @@ impl ConnectionTrait for DatabaseConnection {
             DatabaseConnection::SqlxSqlitePoolConnection(_) => DbBackend::Sqlite,
             #[cfg(feature = "mock")]
             DatabaseConnection::MockDatabaseConnection(conn) => conn.get_database_backend(),
+            DatabaseConnection::ReplicatedConnection(conn) => conn.primary().get_database_backend(),
             DatabaseConnection::Disconnected => panic!("Disconnected"),
         }
     }
 
     #[instrument(level = "trace")]
     async fn execute(&self, stmt: Statement) -> Result<ExecResult, DbErr> {
+        self.execute_with_timeout(stmt, None).await
+    }
+
+    #[instrument(level = "trace")]
+    async fn query_one(&self, stmt: Statement) -> Result<Option<QueryResult>, DbErr> {
+        self.query_one_with_timeout(stmt, None).await
+    }
+
+    #[instrument(level = "trace")]
+    async fn query_all(&self, stmt: Statement) -> Result<Vec<QueryResult>, DbErr> {
+        self.query_all_with_timeout(stmt, None).await
+    }
+
+    #[instrument(level = "trace")]
+    async fn execute_with_timeout(
+        &self,
+        stmt: Statement,
+        timeout: Option<Duration>,
+    ) -> Result<ExecResult, DbErr> {
         match self {
             #[cfg(feature = "sqlx-mysql")]
-            DatabaseConnection::SqlxMySqlPoolConnection(conn) => conn.execute(stmt).await,
+            DatabaseConnection::SqlxMySqlPoolConnection(conn) => {
+                conn.execute_with_timeout(stmt, timeout).await
+            }
             #[cfg(feature = "sqlx-postgres")]
-            DatabaseConnection::SqlxPostgresPoolConnection(conn) => conn.execute(stmt).await,
+            DatabaseConnection::SqlxPostgresPoolConnection(conn) => {
+                conn.execute_with_timeout(stmt, timeout).await
+            }
             #[cfg(feature = "sqlx-sqlite")]
-            DatabaseConnection::SqlxSqlitePoolConnection(conn) => conn.execute(stmt).await,
+            DatabaseConnection::SqlxSqlitePoolConnection(conn) => {
+                conn.execute_with_timeout(stmt, timeout).await
+            }
             #[cfg(feature = "mock")]
             DatabaseConnection::MockDatabaseConnection(conn) => conn.execute(stmt),
+            DatabaseConnection::ReplicatedConnection(conn) => {
+                conn.primary().execute_with_timeout(stmt, timeout).await
+            }
             DatabaseConnection::Disconnected => Err(DbErr::Conn("Disconnected".to_owned())),
         }
     }
 
     #[instrument(level = "trace")]
-    async fn query_one(&self, stmt: Statement) -> Result<Option<QueryResult>, DbErr> {
+    async fn query_one_with_timeout(
+        &self,
+        stmt: Statement,
+        timeout: Option<Duration>,
+    ) -> Result<Option<QueryResult>, DbErr> {
         match self {
             #[cfg(feature = "sqlx-mysql")]
-            DatabaseConnection::SqlxMySqlPoolConnection(conn) => conn.query_one(stmt).await,
+            DatabaseConnection::SqlxMySqlPoolConnection(conn) => {
+                conn.query_one_with_timeout(stmt, timeout).await
+            }
             #[cfg(feature = "sqlx-postgres")]
-            DatabaseConnection::SqlxPostgresPoolConnection(conn) => conn.query_one(stmt).await,
+            DatabaseConnection::SqlxPostgresPoolConnection(conn) => {
+                conn.query_one_with_timeout(stmt, timeout).await
+            }
             #[cfg(feature = "sqlx-sqlite")]
-            DatabaseConnection::SqlxSqlitePoolConnection(conn) => conn.query_one(stmt).await,
+            DatabaseConnection::SqlxSqlitePoolConnection(conn) => {
+                conn.query_one_with_timeout(stmt, timeout).await
+            }
             #[cfg(feature = "mock")]
             DatabaseConnection::MockDatabaseConnection(conn) => conn.query_one(stmt),
+            DatabaseConnection::ReplicatedConnection(conn) => {
+                conn.route(&stmt)
+                    .query_one_with_timeout(stmt, timeout)
+                    .await
+            }
             DatabaseConnection::Disconnected => Err(DbErr::Conn("Disconnected".to_owned())),
         }
     }
 
     #[instrument(level = "trace")]
-    async fn query_all(&self, stmt: Statement) -> Result<Vec<QueryResult>, DbErr> {
+    async fn query_all_with_timeout(
+        &self,
+        stmt: Statement,
+        timeout: Option<Duration>,
+    ) -> Result<Vec<QueryResult>, DbErr> {
         match self {
             #[cfg(feature = "sqlx-mysql")]
-            DatabaseConnection::SqlxMySqlPoolConnection(conn) => conn.query_all(stmt).await,
+            DatabaseConnection::SqlxMySqlPoolConnection(conn) => {
+                conn.query_all_with_timeout(stmt, timeout).await
+            }
             #[cfg(feature = "sqlx-postgres")]
-            DatabaseConnection::SqlxPostgresPoolConnection(conn) => conn.query_all(stmt).await,
+            DatabaseConnection::SqlxPostgresPoolConnection(conn) => {
+                conn.query_all_with_timeout(stmt, timeout).await
+            }
             #[cfg(feature = "sqlx-sqlite")]
-            DatabaseConnection::SqlxSqlitePoolConnection(conn) => conn.query_all(stmt).await,
+            DatabaseConnection::SqlxSqlitePoolConnection(conn) => {
+                conn.query_all_with_timeout(stmt, timeout).await
+            }
             #[cfg(feature = "mock")]
             DatabaseConnection::MockDatabaseConnection(conn) => conn.query_all(stmt),
+            DatabaseConnection::ReplicatedConnection(conn) => {
+                conn.route(&stmt)
+                    .query_all_with_timeout(stmt, timeout)
+                    .await
+            }
             DatabaseConnection::Disconnected => Err(DbErr::Conn("Disconnected".to_owned())),
         }
     }
 
+    #[instrument(level = "trace")]
+    async fn ping(&self) -> Result<(), DbErr> {
+        match self {
+            #[cfg(feature = "sqlx-mysql")]
+            DatabaseConnection::SqlxMySqlPoolConnection(conn) => conn.ping().await,
+            #[cfg(feature = "sqlx-postgres")]
+            DatabaseConnection::SqlxPostgresPoolConnection(conn) => conn.ping().await,
+            #[cfg(feature = "sqlx-sqlite")]
+            DatabaseConnection::SqlxSqlitePoolConnection(conn) => conn.ping().await,
+            #[cfg(feature = "mock")]
+            DatabaseConnection::MockDatabaseConnection(conn) => conn.ping(),
+            DatabaseConnection::ReplicatedConnection(conn) => {
+                for db in conn.connections() {
+                    db.ping().await?;
+                }
+                Ok(())
+            }
+            DatabaseConnection::Disconnected => Err(DbErr::Conn("Disconnected".to_owned())),
+        }
+    }
+
+    #[instrument(level = "trace")]
+    async fn close(&self) {
+        match self {
+            #[cfg(feature = "sqlx-mysql")]
+            DatabaseConnection::SqlxMySqlPoolConnection(conn) => conn.close().await,
+            #[cfg(feature = "sqlx-postgres")]
+            DatabaseConnection::SqlxPostgresPoolConnection(conn) => conn.close().await,
+            #[cfg(feature = "sqlx-sqlite")]
+            DatabaseConnection::SqlxSqlitePoolConnection(conn) => conn.close().await,
+            #[cfg(feature = "mock")]
+            DatabaseConnection::MockDatabaseConnection(conn) => conn.close(),
+            DatabaseConnection::ReplicatedConnection(conn) => {
+                futures::future::join_all(conn.connections().map(|db| db.close())).await;
+            }
+            DatabaseConnection::Disconnected => {}
+        }
+    }
+
+    fn pool_stats(&self) -> PoolStats {
+        match self {
+            #[cfg(feature = "sqlx-mysql")]
+            DatabaseConnection::SqlxMySqlPoolConnection(conn) => conn.pool_stats(),
+            #[cfg(feature = "sqlx-postgres")]
+            DatabaseConnection::SqlxPostgresPoolConnection(conn) => conn.pool_stats(),
+            #[cfg(feature = "sqlx-sqlite")]
+            DatabaseConnection::SqlxSqlitePoolConnection(conn) => conn.pool_stats(),
+            DatabaseConnection::ReplicatedConnection(conn) => conn
+                .connections()
+                .fold(PoolStats::default(), |stats, db| stats + db.pool_stats()),
+            _ => PoolStats::default(),
+        }
+    }
+
+    fn support_returning(&self) -> bool {
+        match self {
+            #[cfg(feature = "sqlx-sqlite")]
+            DatabaseConnection::SqlxSqlitePoolConnection(_) => crate::sqlite_support_returning(),
+            DatabaseConnection::ReplicatedConnection(conn) => conn.primary().support_returning(),
+            _ => self.get_database_backend().support_returning(),
+        }
+    }
+
     #[cfg(feature = "mock")]
     fn is_mock_connection(&self) -> bool {
-        matches!(self, DatabaseConnection::MockDatabaseConnection(_))
+        match self {
+            DatabaseConnection::MockDatabaseConnection(_) => true,
+            DatabaseConnection::ReplicatedConnection(conn) => conn.primary().is_mock_connection(),
+            _ => false,
+        }
     }
 }
 
@@ -176,6 +305,9 @@ impl<'a> StreamTrait<'a> for DatabaseConnection {
                 DatabaseConnection::MockDatabaseConnection(conn) => {
                     crate::QueryStream::from((Arc::clone(conn), stmt, None))
                 }
+                DatabaseConnection::ReplicatedConnection(conn) => {
+                    conn.route(&stmt).stream(stmt).await?
+                }
                 DatabaseConnection::Disconnected => panic!("Disconnected"),
             })
         })
@@ -186,16 +318,42 @@ impl<'a> StreamTrait<'a> for DatabaseConnection {
 impl TransactionTrait for DatabaseConnection {
     #[instrument(level = "trace")]
     async fn begin(&self) -> Result<DatabaseTransaction, DbErr> {
+        self.begin_with_config(None, None).await
+    }
+
+    #[instrument(level = "trace")]
+    async fn begin_with_config(
+        &self,
+        _isolation_level: Option<IsolationLevel>,
+        _access_mode: Option<AccessMode>,
+    ) -> Result<DatabaseTransaction, DbErr> {
         match self {
             #[cfg(feature = "sqlx-mysql")]
-            DatabaseConnection::SqlxMySqlPoolConnection(conn) => conn.begin().await,
+            DatabaseConnection::SqlxMySqlPoolConnection(conn) => {
+                conn.begin_with_config(_isolation_level, _access_mode).await
+            }
             #[cfg(feature = "sqlx-postgres")]
-            DatabaseConnection::SqlxPostgresPoolConnection(conn) => conn.begin().await,
+            DatabaseConnection::SqlxPostgresPoolConnection(conn) => {
+                conn.begin_with_config(_isolation_level, _access_mode).await
+            }
             #[cfg(feature = "sqlx-sqlite")]
-            DatabaseConnection::SqlxSqlitePoolConnection(conn) => conn.begin().await,
+            DatabaseConnection::SqlxSqlitePoolConnection(conn) => {
+                conn.begin_with_config(_isolation_level, _access_mode).await
+            }
             #[cfg(feature = "mock")]
             DatabaseConnection::MockDatabaseConnection(conn) => {
-                DatabaseTransaction::new_mock(Arc::clone(conn), None).await
+                DatabaseTransaction::new_mock(
+                    Arc::clone(conn),
+                    None,
+                    _isolation_level,
+                    _access_mode,
+                )
+                .await
+            }
+            DatabaseConnection::ReplicatedConnection(conn) => {
+                conn.primary()
+                    .begin_with_config(_isolation_level, _access_mode)
+                    .await
             }
             DatabaseConnection::Disconnected => panic!("Disconnected"),
         }
@@ -205,6 +363,26 @@ impl TransactionTrait for DatabaseConnection {
     /// If the function returns an error, the transaction will be rolled back. If it does not return an error, the transaction will be committed.
     #[instrument(level = "trace", skip(_callback))]
     async fn transaction<F, T, E>(&self, _callback: F) -> Result<T, TransactionError<E>>
+    where
+        F: for<'c> FnOnce(
+                &'c DatabaseTransaction,
+            ) -> Pin<Box<dyn Future<Output = Result<T, E>> + Send + 'c>>
+            + Send,
+        T: Send,
+        E: std::error::Error + Send,
+    {
+        self.transaction_with_config(_callback, None, None).await
+    }
+
+    /// Execute the function inside a transaction with an optional isolation level and access mode.
+    /// If the function returns an error, the transaction will be rolled back. If it does not return an error, the transaction will be committed.
+    #[instrument(level = "trace", skip(_callback))]
+    async fn transaction_with_config<F, T, E>(
+        &self,
+        _callback: F,
+        _isolation_level: Option<IsolationLevel>,
+        _access_mode: Option<AccessMode>,
+    ) -> Result<T, TransactionError<E>>
     where
         F: for<'c> FnOnce(
                 &'c DatabaseTransaction,
@@ -215,20 +393,37 @@ impl TransactionTrait for DatabaseConnection {
     {
         match self {
             #[cfg(feature = "sqlx-mysql")]
-            DatabaseConnection::SqlxMySqlPoolConnection(conn) => conn.transaction(_callback).await,
+            DatabaseConnection::SqlxMySqlPoolConnection(conn) => {
+                conn.transaction_with_config(_callback, _isolation_level, _access_mode)
+                    .await
+            }
             #[cfg(feature = "sqlx-postgres")]
             DatabaseConnection::SqlxPostgresPoolConnection(conn) => {
-                conn.transaction(_callback).await
+                conn.transaction_with_config(_callback, _isolation_level, _access_mode)
+                    .await
             }
             #[cfg(feature = "sqlx-sqlite")]
-            DatabaseConnection::SqlxSqlitePoolConnection(conn) => conn.transaction(_callback).await,
+            DatabaseConnection::SqlxSqlitePoolConnection(conn) => {
+                conn.transaction_with_config(_callback, _isolation_level, _access_mode)
+                    .await
+            }
             #[cfg(feature = "mock")]
             DatabaseConnection::MockDatabaseConnection(conn) => {
-                let transaction = DatabaseTransaction::new_mock(Arc::clone(conn), None)
-                    .await
-                    .map_err(TransactionError::Connection)?;
+                let transaction = DatabaseTransaction::new_mock(
+                    Arc::clone(conn),
+                    None,
+                    _isolation_level,
+                    _access_mode,
+                )
+                .await
+                .map_err(TransactionError::Connection)?;
                 transaction.run(_callback).await
             }
+            DatabaseConnection::ReplicatedConnection(conn) => {
+                conn.primary()
+                    .transaction_with_config(_callback, _isolation_level, _access_mode)
+                    .await
+            }
             DatabaseConnection::Disconnected => panic!("Disconnected"),
         }
     }
@@ -244,6 +439,12 @@ impl DatabaseConnection {
         }
     }
 
+    /// Check that the expectations of the [MockDatabase](crate::MockDatabase) have been matched,
+    /// see [MockDatabase::verify](crate::MockDatabase::verify)
+    pub fn verify(&self) -> Result<(), DbErr> {
+        self.as_mock_connection().verify()
+    }
+
     /// Get the transaction log as a collection  Vec<[crate::Transaction]>
     pub fn into_transaction_log(self) -> Vec<crate::Transaction> {
         let mut mocker = self.as_mock_connection().get_mocker_mutex().lock().unwrap();
@@ -253,26 +454,49 @@ impl DatabaseConnection {
 
 impl DatabaseConnection {
     /// Sets a callback to metric this connection
-    pub fn set_metric_callback<F>(&mut self, _callback: F)
+    pub fn set_metric_callback<F>(&mut self, callback: F)
     where
         F: Fn(&crate::metric::Info<'_>) + Send + Sync + 'static,
     {
+        self.set_shared_metric_callback(Arc::new(callback))
+    }
+
+    fn set_shared_metric_callback(&mut self, _callback: crate::metric::Callback) {
         match self {
             #[cfg(feature = "sqlx-mysql")]
             DatabaseConnection::SqlxMySqlPoolConnection(conn) => {
-                conn.set_metric_callback(_callback)
+                conn.set_metric_callback(move |info| _callback(info))
             }
             #[cfg(feature = "sqlx-postgres")]
             DatabaseConnection::SqlxPostgresPoolConnection(conn) => {
-                conn.set_metric_callback(_callback)
+                conn.set_metric_callback(move |info| _callback(info))
             }
             #[cfg(feature = "sqlx-sqlite")]
             DatabaseConnection::SqlxSqlitePoolConnection(conn) => {
-                conn.set_metric_callback(_callback)
+                conn.set_metric_callback(move |info| _callback(info))
             }
+            DatabaseConnection::ReplicatedConnection(conn) => {
+                for db in conn.connections_mut() {
+                    db.set_shared_metric_callback(Arc::clone(&_callback));
+                }
+            }
+            #[cfg(feature = "mock")]
+            DatabaseConnection::MockDatabaseConnection(conn) => conn.set_metric_callback(_callback),
+            #[allow(unreachable_patterns)]
             _ => {}
         }
     }
+
+    /// Get the primary database connection when there are read replicas,
+    /// otherwise get this connection.
+    /// Useful to read the changes that have just been written, or to call a user-defined
+    /// function with side effects in a `SELECT` statement, which would be sent to a replica.
+    pub fn on_primary(&self) -> &DatabaseConnection {
+        match self {
+            DatabaseConnection::ReplicatedConnection(conn) => conn.primary(),
+            _ => self,
+        }
+    }
 }
 
 impl DbBackend {
@@ -306,10 +530,21 @@ impl DbBackend {
         }
     }
 
-    /// Check if the database supports `RETURNING` syntax on insert and update
+    /// Check if the database supports `RETURNING` syntax on insert and update.
+    /// SQLite supports it since 3.35, which is checked by [ConnectionTrait::support_returning]
+    /// of a connection instead, as it depends on the linked library.
     pub fn support_returning(&self) -> bool {
         matches!(self, Self::Postgres)
     }
+
+    /// The maximum number of values that can be bound to a single statement
+    pub fn max_bind_params(&self) -> usize {
+        match self {
+            Self::MySql => 65535,
+            Self::Postgres => 65535,
+            Self::Sqlite => 32766,
+        }
+    }
 }
 
 #[cfg(test)]
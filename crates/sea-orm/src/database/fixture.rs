@@ -0,0 +1,556 @@
+use crate::{
+    unpack_table_ref, ActiveModelBehavior, ActiveModelTrait, ColumnTrait, ColumnType,
+    ConnectionTrait, DatabaseTransaction, DbBackend, DbErr, EntityName, EntityTrait, Iden,
+    IntoActiveModel, Iterable, ModelTrait, PrimaryKeyToColumn, RelationTrait, Schema,
+    TransactionTrait,
+};
+use sea_query::Value;
+use serde_json::{Map, Value as Json};
+use std::{collections::HashMap, marker::PhantomData, path::Path};
+
+/// The key naming a fixture row, to be referenced by other rows
+const ID_KEY: &str = "$id";
+/// The key of an object referencing the primary key of a named fixture row
+const REF_KEY: &str = "$ref";
+
+/// Rows declared per entity in JSON or YAML, inserted in the order of the `belongs_to` relations between the entities.
+///
+/// A row named with `"$id": "<name>"` can be referenced by the rows loaded after it with `{ "$ref": "<name>" }`,
+/// which is replaced by the primary key the row was inserted with.
+///
+/// ```
+/// # use sea_orm::{error::*, tests_cfg::*, *};
+/// # use serde_json::json;
+/// #
+/// # let _: Result<(), DbErr> = smol::block_on(async {
+/// #
+/// # let db = MockDatabase::new(DbBackend::Postgres)
+/// #     .append_query_results(vec![
+/// #         vec![cake::Model { id: 1, name: "Cheese Cake".to_owned() }],
+/// #     ])
+/// #     .append_query_results(vec![
+/// #         vec![fruit::Model { id: 1, name: "Lemon".to_owned(), cake_id: Some(1) }],
+/// #     ])
+/// #     .into_connection();
+/// #
+/// // The rows can also be read with `Fixtures::file` from a `.json`, `.yaml` or `.yml` file
+/// let fixtures = Fixtures::new()
+///     .rows::<fruit::ActiveModel>(json!([
+///         { "name": "Lemon", "cake_id": { "$ref": "cheese_cake" } },
+///     ]))
+///     .rows::<cake::ActiveModel>(json!([
+///         { "$id": "cheese_cake", "name": "Cheese Cake" },
+///     ]));
+///
+/// let ids = fixtures.load(&db).await?;
+/// assert_eq!(ids["cheese_cake"], 1.into());
+/// #
+/// # Ok(())
+/// # });
+/// ```
+#[derive(Default)]
+pub struct Fixtures {
+    entities: Vec<EntityRows>,
+    create_tables: bool,
+}
+
+struct EntityRows {
+    entity: Box<dyn EntityFixture>,
+    rows: Vec<Json>,
+}
+
+impl std::fmt::Debug for Fixtures {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut map = f.debug_map();
+        for EntityRows { entity, rows } in self.entities.iter() {
+            map.entry(&entity.table_name(), rows);
+        }
+        map.finish()
+    }
+}
+
+impl Fixtures {
+    /// Create an empty set of fixtures
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add the rows of an entity, from a JSON array of objects
+    pub fn rows<A>(mut self, rows: Json) -> Self
+    where
+        A: ActiveModelTrait + ActiveModelBehavior + Send + 'static,
+        <A::Entity as EntityTrait>::Model: IntoActiveModel<A>,
+        for<'de> <A::Entity as EntityTrait>::Model: serde::de::Deserialize<'de>,
+    {
+        let rows = match rows {
+            Json::Array(rows) => rows,
+            row => vec![row],
+        };
+        let entity: Box<dyn EntityFixture> = Box::new(ActiveModelFixture::<A>(PhantomData));
+        match self
+            .entities
+            .iter_mut()
+            .find(|entity_rows| entity_rows.entity.table_name() == entity.table_name())
+        {
+            Some(entity_rows) => entity_rows.rows.extend(rows),
+            None => self.entities.push(EntityRows { entity, rows }),
+        }
+        self
+    }
+
+    /// Add the rows of an entity, from a file containing an array of objects.
+    /// The file is read as YAML if its extension is `.yaml` or `.yml`, which requires the `with-yaml` feature,
+    /// and as JSON otherwise
+    pub fn file<A, P>(self, path: P) -> Result<Self, DbErr>
+    where
+        A: ActiveModelTrait + ActiveModelBehavior + Send + 'static,
+        <A::Entity as EntityTrait>::Model: IntoActiveModel<A>,
+        for<'de> <A::Entity as EntityTrait>::Model: serde::de::Deserialize<'de>,
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref();
+        let file = std::fs::read_to_string(path)
+            .map_err(|e| DbErr::Custom(format!("Failed to read {}: {}", path.display(), e)))?;
+        let rows = match path.extension().and_then(|ext| ext.to_str()) {
+            Some("yaml") | Some("yml") => parse_yaml(&file)?,
+            _ => serde_json::from_str(&file).map_err(|e| DbErr::Json(e.to_string()))?,
+        };
+        Ok(self.rows::<A>(rows))
+    }
+
+    /// Create the tables of the entities with [Schema::create_table_from_entity] before loading the rows,
+    /// e.g. on an in-memory SQLite database
+    pub fn create_tables(mut self, create_tables: bool) -> Self {
+        self.create_tables = create_tables;
+        self
+    }
+
+    /// Insert the rows in a transaction, the rows of an entity after the rows of the entities it belongs to.
+    /// Returns the primary keys of the named rows
+    pub async fn load<C>(&self, db: &C) -> Result<HashMap<String, Value>, DbErr>
+    where
+        C: TransactionTrait,
+    {
+        let order = self.dependency_order()?;
+        let txn = db.begin().await?;
+        if self.create_tables {
+            self.create_tables_in(&txn, &order).await?;
+        }
+        let ids = self.insert_in(&txn, &order).await?;
+        txn.commit().await?;
+        Ok(ids)
+    }
+
+    /// Delete all the rows of the entities, the rows of an entity before the rows of the entities it belongs to,
+    /// then insert the rows again, all in a transaction. Returns the primary keys of the named rows
+    pub async fn reload<C>(&self, db: &C) -> Result<HashMap<String, Value>, DbErr>
+    where
+        C: TransactionTrait,
+    {
+        let order = self.dependency_order()?;
+        let txn = db.begin().await?;
+        for &i in order.iter().rev() {
+            self.entities[i].entity.delete_all(&txn).await?;
+        }
+        let ids = self.insert_in(&txn, &order).await?;
+        txn.commit().await?;
+        Ok(ids)
+    }
+
+    async fn create_tables_in(
+        &self,
+        txn: &DatabaseTransaction,
+        order: &[usize],
+    ) -> Result<(), DbErr> {
+        let db_backend = txn.get_database_backend();
+        for &i in order {
+            let stmt = self.entities[i].entity.create_table(db_backend);
+            txn.execute(stmt).await?;
+        }
+        Ok(())
+    }
+
+    async fn insert_in(
+        &self,
+        txn: &DatabaseTransaction,
+        order: &[usize],
+    ) -> Result<HashMap<String, Value>, DbErr> {
+        let mut ids: HashMap<String, Value> = HashMap::new();
+        for &i in order {
+            let EntityRows { entity, rows } = &self.entities[i];
+            for row in rows {
+                let mut row = match row {
+                    Json::Object(row) => row.clone(),
+                    row => {
+                        return Err(DbErr::Json(format!(
+                            "A fixture row of `{}` is not an object: {}",
+                            entity.table_name(),
+                            row
+                        )))
+                    }
+                };
+                let id = match row.remove(ID_KEY) {
+                    Some(Json::String(id)) => Some(id),
+                    Some(id) => {
+                        return Err(DbErr::Json(format!(
+                            "The `{}` of a fixture row of `{}` is not a string: {}",
+                            ID_KEY,
+                            entity.table_name(),
+                            id
+                        )))
+                    }
+                    None => None,
+                };
+                resolve_refs(&mut row, &ids)?;
+                let primary_key = entity.insert(txn, Json::Object(row)).await?;
+                if let Some(id) = id {
+                    let primary_key = primary_key.ok_or_else(|| {
+                        DbErr::Custom(format!(
+                            "The fixture row `{}` of `{}` can not be named, as its primary key has more than one column",
+                            id,
+                            entity.table_name()
+                        ))
+                    })?;
+                    if ids.insert(id.clone(), primary_key).is_some() {
+                        return Err(DbErr::Custom(format!(
+                            "More than one fixture row is named `{}`",
+                            id
+                        )));
+                    }
+                }
+            }
+        }
+        Ok(ids)
+    }
+
+    /// The indexes of the entities, ordered such that an entity comes after the entities it belongs to
+    fn dependency_order(&self) -> Result<Vec<usize>, DbErr> {
+        let tables: Vec<String> = self
+            .entities
+            .iter()
+            .map(|entity_rows| entity_rows.entity.table_name())
+            .collect();
+        let parents: Vec<Vec<usize>> = self
+            .entities
+            .iter()
+            .enumerate()
+            .map(|(i, entity_rows)| {
+                entity_rows
+                    .entity
+                    .parent_tables()
+                    .iter()
+                    .filter_map(|parent| tables.iter().position(|table| table == parent))
+                    .filter(|&parent| parent != i)
+                    .collect()
+            })
+            .collect();
+
+        let mut order = Vec::with_capacity(tables.len());
+        while order.len() < tables.len() {
+            let next = (0..tables.len()).find(|i| {
+                !order.contains(i) && parents[*i].iter().all(|parent| order.contains(parent))
+            });
+            match next {
+                Some(i) => order.push(i),
+                None => {
+                    let cycle: Vec<&str> = (0..tables.len())
+                        .filter(|i| !order.contains(i))
+                        .map(|i| tables[i].as_str())
+                        .collect();
+                    return Err(DbErr::Custom(format!(
+                        "The fixtures of {} belong to each other in a cycle",
+                        cycle.join(", ")
+                    )));
+                }
+            }
+        }
+        Ok(order)
+    }
+}
+
+/// Replace the `{ "$ref": "<name>" }` objects in a row with the primary key of the named row
+fn resolve_refs(row: &mut Map<String, Json>, ids: &HashMap<String, Value>) -> Result<(), DbErr> {
+    for value in row.values_mut() {
+        let name = match value.as_object() {
+            Some(object) if object.len() == 1 => match object.get(REF_KEY) {
+                Some(Json::String(name)) => name,
+                _ => continue,
+            },
+            _ => continue,
+        };
+        let primary_key = ids.get(name).ok_or_else(|| {
+            DbErr::Custom(format!(
+                "The fixture row `{}` is referenced before it is loaded",
+                name
+            ))
+        })?;
+        *value = sea_query::sea_value_to_json_value(primary_key);
+    }
+    Ok(())
+}
+
+#[cfg(feature = "with-yaml")]
+fn parse_yaml(file: &str) -> Result<Json, DbErr> {
+    serde_yaml::from_str(file).map_err(|e| DbErr::Json(e.to_string()))
+}
+
+#[cfg(not(feature = "with-yaml"))]
+fn parse_yaml(_: &str) -> Result<Json, DbErr> {
+    Err(DbErr::Custom(
+        "Reading YAML fixtures requires the `with-yaml` feature".to_owned(),
+    ))
+}
+
+/// The operations on the rows of an entity, regardless of its type
+#[async_trait::async_trait]
+trait EntityFixture: Send + Sync {
+    fn table_name(&self) -> String;
+
+    fn parent_tables(&self) -> Vec<String>;
+
+    fn create_table(&self, db_backend: DbBackend) -> crate::Statement;
+
+    async fn delete_all(&self, txn: &DatabaseTransaction) -> Result<(), DbErr>;
+
+    /// Insert a row, returning its primary key if it has a single column
+    async fn insert(&self, txn: &DatabaseTransaction, row: Json) -> Result<Option<Value>, DbErr>;
+}
+
+struct ActiveModelFixture<A>(PhantomData<fn() -> A>);
+
+#[async_trait::async_trait]
+impl<A> EntityFixture for ActiveModelFixture<A>
+where
+    A: ActiveModelTrait + ActiveModelBehavior + Send + 'static,
+    <A::Entity as EntityTrait>::Model: IntoActiveModel<A>,
+    for<'de> <A::Entity as EntityTrait>::Model: serde::de::Deserialize<'de>,
+{
+    fn table_name(&self) -> String {
+        A::Entity::default().table_name().to_owned()
+    }
+
+    fn parent_tables(&self) -> Vec<String> {
+        <A::Entity as EntityTrait>::Relation::iter()
+            .map(|relation| relation.def())
+            .filter(|def| !def.is_owner)
+            .map(|def| unpack_table_ref(&def.to_tbl).to_string())
+            .collect()
+    }
+
+    fn create_table(&self, db_backend: DbBackend) -> crate::Statement {
+        let stmt = Schema::new(db_backend).create_table_from_entity(A::Entity::default());
+        db_backend.build(&stmt)
+    }
+
+    async fn delete_all(&self, txn: &DatabaseTransaction) -> Result<(), DbErr> {
+        A::Entity::delete_many().exec(txn).await?;
+        Ok(())
+    }
+
+    async fn insert(&self, txn: &DatabaseTransaction, row: Json) -> Result<Option<Value>, DbErr> {
+        // A generated primary key is left out of the row, but the model can not be deserialized without it
+        let mut row = row;
+        let mut generated = Vec::new();
+        for key in <A::Entity as EntityTrait>::PrimaryKey::iter() {
+            let col = key.into_column();
+            let name = col.to_string();
+            if row.get(&name).is_none() {
+                if let Some(placeholder) = placeholder(col.def().get_column_type()) {
+                    row[name] = placeholder;
+                    generated.push(col);
+                }
+            }
+        }
+        let mut am = A::from_json(row)?;
+        for col in generated {
+            am.not_set(col);
+        }
+        let model = am.insert(txn).await?;
+        let mut primary_key = <A::Entity as EntityTrait>::PrimaryKey::iter();
+        Ok(match (primary_key.next(), primary_key.next()) {
+            (Some(key), None) => Some(model.get(key.into_column())),
+            _ => None,
+        })
+    }
+}
+
+/// A value of the type of a primary key column, to deserialize a row without it
+fn placeholder(col_type: &ColumnType) -> Option<Json> {
+    match col_type {
+        ColumnType::TinyInteger
+        | ColumnType::SmallInteger
+        | ColumnType::Integer
+        | ColumnType::BigInteger
+        | ColumnType::TinyUnsigned
+        | ColumnType::SmallUnsigned
+        | ColumnType::Unsigned
+        | ColumnType::BigUnsigned => Some(Json::from(0)),
+        ColumnType::Char(_) | ColumnType::String(_) | ColumnType::Text => Some(Json::from("")),
+        ColumnType::Uuid => Some(Json::from("00000000-0000-0000-0000-000000000000")),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "mock")]
+mod tests {
+    use super::*;
+    use crate::{tests_cfg::*, MockDatabase, MockExecResult, Statement, Transaction};
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    fn fixtures() -> Fixtures {
+        Fixtures::new()
+            .rows::<fruit::ActiveModel>(json!([
+                { "name": "Lemon", "cake_id": { "$ref": "cheese_cake" } },
+                { "name": "Apple", "cake_id": null },
+            ]))
+            .rows::<cake::ActiveModel>(json!([
+                { "$id": "cheese_cake", "name": "Cheese Cake" },
+            ]))
+    }
+
+    #[smol_potat::test]
+    async fn load_in_dependency_order() -> Result<(), DbErr> {
+        let db = MockDatabase::new(DbBackend::Postgres)
+            .append_query_results(vec![vec![cake::Model {
+                id: 5,
+                name: "Cheese Cake".to_owned(),
+            }]])
+            .append_query_results(vec![vec![fruit::Model {
+                id: 1,
+                name: "Lemon".to_owned(),
+                cake_id: Some(5),
+            }]])
+            .append_query_results(vec![vec![fruit::Model {
+                id: 2,
+                name: "Apple".to_owned(),
+                cake_id: None,
+            }]])
+            .into_connection();
+
+        let ids = fixtures().load(&db).await?;
+        assert_eq!(
+            ids,
+            [("cheese_cake".to_owned(), 5.into())].into_iter().collect()
+        );
+
+        assert_eq!(
+            db.into_transaction_log(),
+            vec![Transaction::many(vec![
+                Statement::from_string(DbBackend::Postgres, "BEGIN".to_owned()),
+                Statement::from_sql_and_values(
+                    DbBackend::Postgres,
+                    r#"INSERT INTO "cake" ("name") VALUES ($1) RETURNING "id", "name""#,
+                    vec!["Cheese Cake".into()]
+                ),
+                Statement::from_sql_and_values(
+                    DbBackend::Postgres,
+                    r#"INSERT INTO "fruit" ("name", "cake_id") VALUES ($1, $2) RETURNING "id", "name", "cake_id""#,
+                    vec!["Lemon".into(), 5.into()]
+                ),
+                Statement::from_sql_and_values(
+                    DbBackend::Postgres,
+                    r#"INSERT INTO "fruit" ("name", "cake_id") VALUES ($1, $2) RETURNING "id", "name", "cake_id""#,
+                    vec!["Apple".into(), Option::<i32>::None.into()]
+                ),
+                Statement::from_string(DbBackend::Postgres, "COMMIT".to_owned()),
+            ])]
+        );
+
+        Ok(())
+    }
+
+    #[smol_potat::test]
+    async fn reload_and_create_tables() -> Result<(), DbErr> {
+        let db = MockDatabase::new(DbBackend::Postgres)
+            .append_exec_results(vec![
+                MockExecResult {
+                    last_insert_id: 0,
+                    rows_affected: 3,
+                };
+                2
+            ])
+            .append_query_results(vec![vec![cake::Model {
+                id: 1,
+                name: "Cheese Cake".to_owned(),
+            }]])
+            .append_query_results(vec![vec![fruit::Model {
+                id: 1,
+                name: "Lemon".to_owned(),
+                cake_id: Some(1),
+            }]])
+            .append_query_results(vec![vec![fruit::Model {
+                id: 2,
+                name: "Apple".to_owned(),
+                cake_id: None,
+            }]])
+            .into_connection();
+
+        fixtures().reload(&db).await?;
+        assert_eq!(
+            db.into_transaction_log(),
+            vec![Transaction::many(vec![
+                Statement::from_string(DbBackend::Postgres, "BEGIN".to_owned()),
+                Statement::from_sql_and_values(
+                    DbBackend::Postgres,
+                    r#"DELETE FROM "fruit""#,
+                    vec![]
+                ),
+                Statement::from_sql_and_values(
+                    DbBackend::Postgres,
+                    r#"DELETE FROM "cake""#,
+                    vec![]
+                ),
+                Statement::from_sql_and_values(
+                    DbBackend::Postgres,
+                    r#"INSERT INTO "cake" ("name") VALUES ($1) RETURNING "id", "name""#,
+                    vec!["Cheese Cake".into()]
+                ),
+                Statement::from_sql_and_values(
+                    DbBackend::Postgres,
+                    r#"INSERT INTO "fruit" ("name", "cake_id") VALUES ($1, $2) RETURNING "id", "name", "cake_id""#,
+                    vec!["Lemon".into(), 1.into()]
+                ),
+                Statement::from_sql_and_values(
+                    DbBackend::Postgres,
+                    r#"INSERT INTO "fruit" ("name", "cake_id") VALUES ($1, $2) RETURNING "id", "name", "cake_id""#,
+                    vec!["Apple".into(), Option::<i32>::None.into()]
+                ),
+                Statement::from_string(DbBackend::Postgres, "COMMIT".to_owned()),
+            ])]
+        );
+
+        let create_tables = fixtures().create_tables(true);
+        let order = create_tables.dependency_order()?;
+        assert_eq!(
+            order
+                .iter()
+                .map(|&i| create_tables.entities[i]
+                    .entity
+                    .create_table(DbBackend::Sqlite)
+                    .sql)
+                .collect::<Vec<_>>(),
+            vec![
+                r#"CREATE TABLE "cake" ( "id" integer NOT NULL PRIMARY KEY AUTOINCREMENT, "name" text NOT NULL )"#,
+                r#"CREATE TABLE "fruit" ( "id" integer NOT NULL PRIMARY KEY AUTOINCREMENT, "name" text NOT NULL, "cake_id" integer, FOREIGN KEY ("cake_id") REFERENCES "cake" ("id") )"#,
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[smol_potat::test]
+    async fn unresolved_ref() {
+        let db = MockDatabase::new(DbBackend::Postgres).into_connection();
+        let fixtures = Fixtures::new().rows::<fruit::ActiveModel>(json!([
+            { "name": "Lemon", "cake_id": { "$ref": "cheese_cake" } },
+        ]));
+        assert_eq!(
+            fixtures.load(&db).await,
+            Err(DbErr::Custom(
+                "The fixture row `cheese_cake` is referenced before it is loaded".to_owned()
+            ))
+        );
+    }
+}
@@ -1,10 +1,14 @@
 use crate::{
-    error::*, DatabaseConnection, DbBackend, EntityTrait, ExecResult, ExecResultHolder, Iden,
-    IdenStatic, Iterable, MockDatabaseConnection, MockDatabaseTrait, ModelTrait, QueryResult,
+    error::*, set_transaction_config_sql, sqlite_begin_sql, AccessMode, DatabaseConnection,
+    DbBackend, EntityTrait, ExecResult, ExecResultHolder, Iden, IdenStatic, IsolationLevel,
+    Iterable, MockDatabaseConnection, MockDatabaseTrait, MockExpectation, ModelTrait, QueryResult,
     QueryResultRow, SelectA, SelectB, Statement,
 };
 use sea_query::{Value, ValueType, Values};
-use std::{collections::BTreeMap, sync::Arc};
+use std::{
+    collections::{BTreeMap, VecDeque},
+    sync::Arc,
+};
 use tracing::instrument;
 
 /// Defines a Mock database suitable for testing
@@ -13,8 +17,12 @@ pub struct MockDatabase {
     db_backend: DbBackend,
     transaction: Option<OpenTransaction>,
     transaction_log: Vec<Transaction>,
-    exec_results: Vec<MockExecResult>,
-    query_results: Vec<Vec<MockRow>>,
+    exec_results: Vec<Result<MockExecResult, DbErr>>,
+    query_results: Vec<Result<Vec<MockRow>, DbErr>>,
+    expectations: Vec<MockExpectation>,
+    begin_results: VecDeque<Result<(), DbErr>>,
+    commit_results: VecDeque<Result<(), DbErr>>,
+    rollback_results: VecDeque<Result<(), DbErr>>,
 }
 
 /// Defines the results obtained from a [MockDatabase]
@@ -62,6 +70,10 @@ impl MockDatabase {
             transaction_log: Vec::new(),
             exec_results: Vec::new(),
             query_results: Vec::new(),
+            expectations: Vec::new(),
+            begin_results: VecDeque::new(),
+            commit_results: VecDeque::new(),
+            rollback_results: VecDeque::new(),
         }
     }
 
@@ -71,8 +83,44 @@ impl MockDatabase {
     }
 
     /// Add the [MockExecResult]s to the `exec_results` field for `Self`
-    pub fn append_exec_results(mut self, mut vec: Vec<MockExecResult>) -> Self {
-        self.exec_results.append(&mut vec);
+    pub fn append_exec_results(mut self, vec: Vec<MockExecResult>) -> Self {
+        self.exec_results.extend(vec.into_iter().map(Ok));
+        self
+    }
+
+    /// Add errors to the `exec_results` field for `Self`, the executions fail with them
+    /// in the order the results and errors are added
+    ///
+    /// ```
+    /// # use sea_orm::{entity::*, error::*, tests_cfg::*, *};
+    /// #
+    /// # let _: Result<(), DbErr> = smol::block_on(async {
+    /// #
+    /// let db = MockDatabase::new(DbBackend::Postgres)
+    ///     .append_exec_results(vec![MockExecResult {
+    ///         last_insert_id: 1,
+    ///         rows_affected: 1,
+    ///     }])
+    ///     .append_exec_errors(vec![DbErr::Driver(Box::new(DriverErr {
+    ///         kind: Some(DriverErrKind::UniqueViolation),
+    ///         constraint: Some("cake_name_key".to_owned()),
+    ///         ..Default::default()
+    ///     }))])
+    ///     .into_connection();
+    ///
+    /// let apple = cake::ActiveModel {
+    ///     name: Set("Apple Cake".to_owned()),
+    ///     ..Default::default()
+    /// };
+    /// cake::Entity::insert(apple.clone()).exec(&db).await?;
+    /// let err = cake::Entity::insert(apple).exec(&db).await.unwrap_err();
+    /// assert_eq!(err.kind(), Some(DriverErrKind::UniqueViolation));
+    /// #
+    /// # Ok(())
+    /// # });
+    /// ```
+    pub fn append_exec_errors(mut self, vec: Vec<DbErr>) -> Self {
+        self.exec_results.extend(vec.into_iter().map(Err));
         self
     }
 
@@ -83,84 +131,258 @@ impl MockDatabase {
     {
         for row in vec.into_iter() {
             let row = row.into_iter().map(|vec| vec.into_mock_row()).collect();
-            self.query_results.push(row);
+            self.query_results.push(Ok(row));
         }
         self
     }
-}
 
-impl MockDatabaseTrait for MockDatabase {
-    #[instrument(level = "trace")]
-    fn execute(&mut self, counter: usize, statement: Statement) -> Result<ExecResult, DbErr> {
+    /// Add errors to the `query_results` field for `Self`, the queries fail with them
+    /// in the order the results and errors are added
+    pub fn append_query_errors(mut self, vec: Vec<DbErr>) -> Self {
+        self.query_results.extend(vec.into_iter().map(Err));
+        self
+    }
+
+    /// Add the results of the next `begin`s, of a transaction or a nested one.
+    /// Once they are used up, `begin` succeeds. A failed `begin` does not open a transaction
+    pub fn append_begin_results(mut self, vec: Vec<Result<(), DbErr>>) -> Self {
+        self.begin_results.extend(vec);
+        self
+    }
+
+    /// Add the results of the next `commit`s. Once they are used up, `commit` succeeds.
+    /// A failed `commit` still ends the transaction, recorded as rolled back in the transaction log
+    pub fn append_commit_results(mut self, vec: Vec<Result<(), DbErr>>) -> Self {
+        self.commit_results.extend(vec);
+        self
+    }
+
+    /// Add the results of the next `rollback`s. Once they are used up, `rollback` succeeds.
+    /// A failed `rollback` still ends the transaction, as recorded in the transaction log
+    pub fn append_rollback_results(mut self, vec: Vec<Result<(), DbErr>>) -> Self {
+        self.rollback_results.extend(vec);
+        self
+    }
+
+    /// Add a [MockExpectation]. Once an expectation is added, every statement is served by the first
+    /// expectation matching it instead of the `exec_results` and `query_results` in call order,
+    /// and a statement no expectation matches fails with a diff against the closest expectation
+    ///
+    /// ```
+    /// # use sea_orm::{entity::*, error::*, tests_cfg::*, *};
+    /// #
+    /// # let _: Result<(), DbErr> = smol::block_on(async {
+    /// #
+    /// let db = MockDatabase::new(DbBackend::Postgres)
+    ///     .expect(MockExpectation::query(
+    ///         r#"SELECT % FROM "fruit"%"#,
+    ///         vec![fruit::Model {
+    ///             id: 2,
+    ///             name: "Orange".to_owned(),
+    ///             cake_id: None,
+    ///         }],
+    ///     ))
+    ///     .expect(
+    ///         MockExpectation::query(
+    ///             r#"SELECT % FROM "cake"%"#,
+    ///             vec![cake::Model {
+    ///                 id: 1,
+    ///                 name: "Apple Cake".to_owned(),
+    ///             }],
+    ///         )
+    ///         .times(1),
+    ///     )
+    ///     .into_connection();
+    ///
+    /// let cakes = cake::Entity::find().all(&db).await?;
+    /// let fruits = fruit::Entity::find().all(&db).await?;
+    /// assert_eq!(cakes[0].name, "Apple Cake");
+    /// assert_eq!(fruits[0].name, "Orange");
+    ///
+    /// db.verify()?;
+    /// #
+    /// # Ok(())
+    /// # });
+    /// ```
+    pub fn expect(mut self, expectation: MockExpectation) -> Self {
+        self.expectations.push(expectation);
+        self
+    }
+
+    /// Add the [MockExpectation]s, see [MockDatabase::expect]
+    pub fn append_expectations(mut self, mut vec: Vec<MockExpectation>) -> Self {
+        self.expectations.append(&mut vec);
+        self
+    }
+
+    /// Check that every expectation has been matched, exactly as many times as expected if set
+    pub fn verify(&self) -> Result<(), DbErr> {
+        super::mock_expectation::verify_expectations(&self.expectations)
+    }
+
+    fn log(&mut self, statement: Statement) {
         if let Some(transaction) = &mut self.transaction {
             transaction.push(statement);
         } else {
             self.transaction_log.push(Transaction::one(statement));
         }
-        if counter < self.exec_results.len() {
-            Ok(ExecResult {
-                result: ExecResultHolder::Mock(std::mem::take(&mut self.exec_results[counter])),
-            })
+    }
+}
+
+impl MockDatabaseTrait for MockDatabase {
+    #[instrument(level = "trace")]
+    fn execute(&mut self, counter: usize, statement: Statement) -> Result<ExecResult, DbErr> {
+        let result = if !self.expectations.is_empty() {
+            super::mock_expectation::serve_exec(&mut self.expectations, &statement)
+        } else if counter < self.exec_results.len() {
+            std::mem::replace(&mut self.exec_results[counter], Ok(Default::default()))
         } else {
             Err(DbErr::Exec("`exec_results` buffer is empty.".to_owned()))
-        }
+        };
+        self.log(statement);
+        Ok(ExecResult {
+            result: ExecResultHolder::Mock(result?),
+        })
     }
 
     #[instrument(level = "trace")]
     fn query(&mut self, counter: usize, statement: Statement) -> Result<Vec<QueryResult>, DbErr> {
-        if let Some(transaction) = &mut self.transaction {
-            transaction.push(statement);
-        } else {
-            self.transaction_log.push(Transaction::one(statement));
-        }
-        if counter < self.query_results.len() {
-            Ok(std::mem::take(&mut self.query_results[counter])
-                .into_iter()
-                .map(|row| QueryResult {
-                    row: QueryResultRow::Mock(row),
-                })
-                .collect())
+        let rows = if !self.expectations.is_empty() {
+            super::mock_expectation::serve_query(&mut self.expectations, &statement)
+        } else if counter < self.query_results.len() {
+            std::mem::replace(&mut self.query_results[counter], Ok(Vec::new()))
         } else {
             Err(DbErr::Query("`query_results` buffer is empty.".to_owned()))
-        }
+        };
+        self.log(statement);
+        Ok(rows?
+            .into_iter()
+            .map(|row| QueryResult {
+                row: QueryResultRow::Mock(row),
+            })
+            .collect())
     }
 
     #[instrument(level = "trace")]
     fn begin(&mut self) {
-        if self.transaction.is_some() {
-            self.transaction
-                .as_mut()
-                .unwrap()
-                .begin_nested(self.db_backend);
+        if let Some(transaction) = &mut self.transaction {
+            transaction.begin_nested(self.db_backend);
         } else {
             self.transaction = Some(OpenTransaction::init());
         }
     }
 
+    #[instrument(level = "trace")]
+    fn begin_with_config(
+        &mut self,
+        isolation_level: Option<IsolationLevel>,
+        access_mode: Option<AccessMode>,
+    ) {
+        if self.transaction.is_some() || (isolation_level.is_none() && access_mode.is_none()) {
+            return self.begin();
+        }
+        let begin = |sql: &str| Statement::from_string(self.db_backend, sql.to_owned());
+        let set_transaction = set_transaction_config_sql(isolation_level, access_mode)
+            .map(|sql| Statement::from_string(self.db_backend, sql));
+        let stmts = match self.db_backend {
+            DbBackend::MySql => set_transaction
+                .into_iter()
+                .chain([begin("BEGIN")])
+                .collect(),
+            DbBackend::Postgres => [begin("BEGIN")]
+                .into_iter()
+                .chain(set_transaction)
+                .collect(),
+            DbBackend::Sqlite => vec![begin(
+                sqlite_begin_sql(isolation_level, access_mode).unwrap_or("BEGIN"),
+            )],
+        };
+        self.transaction = Some(OpenTransaction::init_with(stmts));
+    }
+
+    #[instrument(level = "trace")]
+    fn savepoint(&mut self, stmt: Statement) {
+        match &mut self.transaction {
+            Some(transaction) => transaction.push(stmt),
+            None => panic!("There is no open transaction to create a savepoint"),
+        }
+    }
+
     #[instrument(level = "trace")]
     fn commit(&mut self) {
-        if self.transaction.is_some() {
-            if self.transaction.as_mut().unwrap().commit(self.db_backend) {
-                let transaction = self.transaction.take().unwrap();
-                self.transaction_log.push(transaction.into_transaction());
+        match &mut self.transaction {
+            Some(transaction) => {
+                if transaction.commit(self.db_backend) {
+                    let transaction = self.transaction.take().unwrap();
+                    self.transaction_log.push(transaction.into_transaction());
+                }
             }
-        } else {
-            panic!("There is no open transaction to commit");
+            None => panic!("There is no open transaction to commit"),
         }
     }
 
     #[instrument(level = "trace")]
     fn rollback(&mut self) {
-        if self.transaction.is_some() {
-            if self.transaction.as_mut().unwrap().rollback(self.db_backend) {
-                let transaction = self.transaction.take().unwrap();
-                self.transaction_log.push(transaction.into_transaction());
+        match &mut self.transaction {
+            Some(transaction) => {
+                if transaction.rollback(self.db_backend) {
+                    let transaction = self.transaction.take().unwrap();
+                    self.transaction_log.push(transaction.into_transaction());
+                }
             }
-        } else {
-            panic!("There is no open transaction to rollback");
+            None => panic!("There is no open transaction to rollback"),
         }
     }
 
+    #[instrument(level = "trace")]
+    fn try_begin(&mut self) -> Result<(), DbErr> {
+        self.begin_results.pop_front().unwrap_or(Ok(()))?;
+        self.begin();
+        Ok(())
+    }
+
+    #[instrument(level = "trace")]
+    fn try_begin_with_config(
+        &mut self,
+        isolation_level: Option<IsolationLevel>,
+        access_mode: Option<AccessMode>,
+    ) -> Result<(), DbErr> {
+        self.begin_results.pop_front().unwrap_or(Ok(()))?;
+        self.begin_with_config(isolation_level, access_mode);
+        Ok(())
+    }
+
+    #[instrument(level = "trace")]
+    fn try_commit(&mut self) -> Result<(), DbErr> {
+        if self.transaction.is_none() {
+            return Err(DbErr::Query(
+                "There is no open transaction to commit".to_owned(),
+            ));
+        }
+        match self.commit_results.pop_front().unwrap_or(Ok(())) {
+            Ok(()) => {
+                self.commit();
+                Ok(())
+            }
+            // a failed commit leaves nothing behind, so it is logged as rolled back
+            Err(err) => {
+                self.rollback();
+                Err(err)
+            }
+        }
+    }
+
+    #[instrument(level = "trace")]
+    fn try_rollback(&mut self) -> Result<(), DbErr> {
+        if self.transaction.is_none() {
+            return Err(DbErr::Query(
+                "There is no open transaction to rollback".to_owned(),
+            ));
+        }
+        self.rollback();
+        self.rollback_results.pop_front().unwrap_or(Ok(()))
+    }
+
     fn drain_transaction_log(&mut self) -> Vec<Transaction> {
         std::mem::take(&mut self.transaction_log)
     }
@@ -168,9 +390,21 @@ impl MockDatabaseTrait for MockDatabase {
     fn get_database_backend(&self) -> DbBackend {
         self.db_backend
     }
+
+    fn is_in_transaction(&self) -> bool {
+        self.transaction.is_some()
+    }
+
+    fn verify(&self) -> Result<(), DbErr> {
+        MockDatabase::verify(self)
+    }
 }
 
 impl MockRow {
+    pub(crate) fn get(&self, col: &str) -> Option<&Value> {
+        self.values.get(col)
+    }
+
     /// Try to get the values of a [MockRow] and fail gracefully on error
     pub fn try_get<T>(&self, col: &str) -> Result<T, DbErr>
     where
@@ -292,6 +526,13 @@ impl OpenTransaction {
         }
     }
 
+    fn init_with(stmts: Vec<Statement>) -> Self {
+        Self {
+            stmts,
+            transaction_depth: 0,
+        }
+    }
+
     fn begin_nested(&mut self, db_backend: DbBackend) {
         self.transaction_depth += 1;
         self.push(Statement::from_string(
@@ -344,8 +585,9 @@ impl OpenTransaction {
 #[cfg(feature = "mock")]
 mod tests {
     use crate::{
-        entity::*, tests_cfg::*, DbBackend, DbErr, IntoMockRow, MockDatabase, Statement,
-        Transaction, TransactionError, TransactionTrait,
+        entity::*, tests_cfg::*, AccessMode, ConnectionTrait, DbBackend, DbErr, IntoMockRow,
+        IsolationLevel, MockDatabase, MockExecResult, PoolStats, Statement, Transaction,
+        TransactionError, TransactionTrait,
     };
     use pretty_assertions::assert_eq;
 
@@ -437,6 +679,175 @@ mod tests {
         );
     }
 
+    #[smol_potat::test]
+    async fn test_transaction_with_config() {
+        for (db_backend, begin) in [
+            (
+                DbBackend::MySql,
+                vec![
+                    "SET TRANSACTION ISOLATION LEVEL SERIALIZABLE, READ WRITE",
+                    "BEGIN",
+                ],
+            ),
+            (
+                DbBackend::Postgres,
+                vec![
+                    "BEGIN",
+                    "SET TRANSACTION ISOLATION LEVEL SERIALIZABLE, READ WRITE",
+                ],
+            ),
+            (DbBackend::Sqlite, vec!["BEGIN EXCLUSIVE"]),
+        ] {
+            let db = MockDatabase::new(db_backend).into_connection();
+
+            db.transaction_with_config::<_, (), DbErr>(
+                |_| Box::pin(async move { Ok(()) }),
+                Some(IsolationLevel::Serializable),
+                Some(AccessMode::ReadWrite),
+            )
+            .await
+            .unwrap();
+
+            let mut stmts: Vec<_> = begin
+                .into_iter()
+                .map(|sql| Statement::from_string(db_backend, sql.to_owned()))
+                .collect();
+            stmts.push(Statement::from_string(db_backend, "COMMIT".to_owned()));
+
+            assert_eq!(db.into_transaction_log(), vec![Transaction::many(stmts)]);
+        }
+    }
+
+    #[smol_potat::test]
+    async fn test_transaction_with_config_access_mode() -> Result<(), DbErr> {
+        let db = MockDatabase::new(DbBackend::Sqlite).into_connection();
+        db.begin_with_config(None, Some(AccessMode::ReadWrite))
+            .await?
+            .commit()
+            .await?;
+        assert!(matches!(
+            db.begin_with_config(None, Some(AccessMode::ReadOnly)).await,
+            Err(DbErr::Exec(_))
+        ));
+
+        assert_eq!(
+            db.into_transaction_log(),
+            vec![Transaction::many(vec![
+                Statement::from_string(DbBackend::Sqlite, "BEGIN IMMEDIATE".to_owned()),
+                Statement::from_string(DbBackend::Sqlite, "COMMIT".to_owned()),
+            ])]
+        );
+
+        let db = MockDatabase::new(DbBackend::Postgres).into_connection();
+        db.begin_with_config(None, Some(AccessMode::ReadOnly))
+            .await?
+            .commit()
+            .await?;
+
+        assert_eq!(
+            db.into_transaction_log(),
+            vec![Transaction::many(vec![
+                Statement::from_string(DbBackend::Postgres, "BEGIN".to_owned()),
+                Statement::from_string(DbBackend::Postgres, "SET TRANSACTION READ ONLY".to_owned()),
+                Statement::from_string(DbBackend::Postgres, "COMMIT".to_owned()),
+            ])]
+        );
+
+        Ok(())
+    }
+
+    #[smol_potat::test]
+    async fn test_nested_transaction_with_config() -> Result<(), DbErr> {
+        let db = MockDatabase::new(DbBackend::Postgres).into_connection();
+
+        let txn = db.begin().await?;
+        assert!(matches!(
+            txn.begin_with_config(Some(IsolationLevel::ReadCommitted), None)
+                .await,
+            Err(DbErr::Exec(_))
+        ));
+        txn.begin_with_config(None, None).await?.commit().await?;
+        txn.commit().await?;
+
+        assert_eq!(
+            db.into_transaction_log(),
+            vec![Transaction::many(vec![
+                Statement::from_string(DbBackend::Postgres, "BEGIN".to_owned()),
+                Statement::from_string(DbBackend::Postgres, "SAVEPOINT savepoint_1".to_owned()),
+                Statement::from_string(
+                    DbBackend::Postgres,
+                    "RELEASE SAVEPOINT savepoint_1".to_owned()
+                ),
+                Statement::from_string(DbBackend::Postgres, "COMMIT".to_owned()),
+            ])]
+        );
+
+        Ok(())
+    }
+
+    #[smol_potat::test]
+    async fn test_savepoint() -> Result<(), DbErr> {
+        let db = MockDatabase::new(DbBackend::MySql)
+            .append_exec_results(vec![
+                MockExecResult {
+                    last_insert_id: 1,
+                    rows_affected: 1,
+                },
+                MockExecResult {
+                    last_insert_id: 2,
+                    rows_affected: 1,
+                },
+            ])
+            .into_connection();
+
+        let txn = db.begin().await?;
+        cake::Entity::insert(cake::ActiveModel {
+            name: Set("Apple Pie".to_owned()),
+            ..Default::default()
+        })
+        .exec(&txn)
+        .await?;
+        txn.savepoint("after_apple").await?;
+        cake::Entity::insert(cake::ActiveModel {
+            name: Set("Orange Cake".to_owned()),
+            ..Default::default()
+        })
+        .exec(&txn)
+        .await?;
+        txn.rollback_to_savepoint("after_apple").await?;
+        txn.release_savepoint("after_apple").await?;
+        txn.commit().await?;
+
+        assert_eq!(
+            db.into_transaction_log(),
+            vec![Transaction::many(vec![
+                Statement::from_string(DbBackend::Postgres, "BEGIN".to_owned()),
+                Statement::from_sql_and_values(
+                    DbBackend::MySql,
+                    r#"INSERT INTO `cake` (`name`) VALUES (?)"#,
+                    vec!["Apple Pie".into()]
+                ),
+                Statement::from_string(DbBackend::MySql, "SAVEPOINT `after_apple`".to_owned()),
+                Statement::from_sql_and_values(
+                    DbBackend::MySql,
+                    r#"INSERT INTO `cake` (`name`) VALUES (?)"#,
+                    vec!["Orange Cake".into()]
+                ),
+                Statement::from_string(
+                    DbBackend::MySql,
+                    "ROLLBACK TO SAVEPOINT `after_apple`".to_owned()
+                ),
+                Statement::from_string(
+                    DbBackend::MySql,
+                    "RELEASE SAVEPOINT `after_apple`".to_owned()
+                ),
+                Statement::from_string(DbBackend::MySql, "COMMIT".to_owned()),
+            ])]
+        );
+
+        Ok(())
+    }
+
     #[smol_potat::test]
     async fn test_nested_transaction_1() {
         let db = MockDatabase::new(DbBackend::Postgres).into_connection();
@@ -707,4 +1118,245 @@ mod tests {
 
         Ok(())
     }
+
+    #[smol_potat::test]
+    async fn test_ping_and_close() -> Result<(), DbErr> {
+        let db = MockDatabase::new(DbBackend::Postgres)
+            .append_query_results(vec![vec![cake::Model {
+                id: 1,
+                name: "Apple Cake".to_owned(),
+            }]])
+            .into_connection();
+
+        assert_eq!(db.ping().await, Ok(()));
+        assert_eq!(db.pool_stats(), PoolStats::default());
+        cake::Entity::find().one(&db).await?;
+
+        db.close().await;
+        assert!(db.as_mock_connection().is_closed());
+        assert_eq!(
+            db.ping().await,
+            Err(DbErr::Conn("Connection is closed".to_owned()))
+        );
+        assert_eq!(
+            cake::Entity::find().one(&db).await,
+            Err(DbErr::Conn("Connection is closed".to_owned()))
+        );
+
+        assert_eq!(
+            db.into_transaction_log(),
+            vec![Transaction::from_sql_and_values(
+                DbBackend::Postgres,
+                r#"SELECT "cake"."id", "cake"."name" FROM "cake" LIMIT $1"#,
+                vec![1u64.into()]
+            )]
+        );
+
+        Ok(())
+    }
+
+    #[smol_potat::test]
+    async fn test_metric_info() -> Result<(), DbErr> {
+        use std::sync::{Arc, Mutex};
+
+        let mut db = MockDatabase::new(DbBackend::Postgres)
+            .append_query_results(vec![vec![
+                cake::Model {
+                    id: 1,
+                    name: "Apple Cake".to_owned(),
+                },
+                cake::Model {
+                    id: 2,
+                    name: "Orange Cake".to_owned(),
+                },
+            ]])
+            .append_exec_results(vec![MockExecResult {
+                last_insert_id: 0,
+                rows_affected: 2,
+            }])
+            .into_connection();
+
+        let infos = Arc::new(Mutex::new(Vec::new()));
+        let captured = Arc::clone(&infos);
+        db.set_metric_callback(move |info| {
+            captured.lock().unwrap().push((
+                info.operation.to_owned(),
+                info.table.map(ToOwned::to_owned),
+                info.rows_returned,
+                info.rows_affected,
+                info.in_transaction,
+                info.failed,
+            ))
+        });
+
+        cake::Entity::find().all(&db).await?;
+        db.transaction::<_, _, DbErr>(|txn| {
+            Box::pin(async move {
+                cake::Entity::delete_many().exec(txn).await?;
+                Ok(())
+            })
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(
+            *infos.lock().unwrap(),
+            vec![
+                (
+                    "SELECT".to_owned(),
+                    Some("cake".to_owned()),
+                    Some(2),
+                    None,
+                    false,
+                    false
+                ),
+                (
+                    "DELETE".to_owned(),
+                    Some("cake".to_owned()),
+                    None,
+                    Some(2),
+                    true,
+                    false
+                ),
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[smol_potat::test]
+    async fn test_expectations() -> Result<(), DbErr> {
+        use crate::{MockExpectation, StatementMatcher};
+
+        let db = MockDatabase::new(DbBackend::Postgres)
+            .expect(MockExpectation::query(
+                Statement::from_sql_and_values(
+                    DbBackend::Postgres,
+                    r#"SELECT "cake"."id", "cake"."name" FROM "cake" WHERE "cake"."id" = $1 LIMIT $2"#,
+                    vec![1i32.into(), 1u64.into()],
+                ),
+                vec![cake::Model {
+                    id: 1,
+                    name: "Apple Cake".to_owned(),
+                }],
+            ))
+            .expect(
+                MockExpectation::exec(
+                    StatementMatcher::predicate(|stmt| stmt.sql.starts_with("DELETE")),
+                    MockExecResult {
+                        last_insert_id: 0,
+                        rows_affected: 3,
+                    },
+                )
+                .times(1),
+            )
+            .expect(MockExpectation::query::<_, cake::Model>(
+                r#"SELECT % FROM "fruit"%"#,
+                vec![],
+            ))
+            .into_connection();
+
+        let res = cake::Entity::delete_many().exec(&db).await?;
+        assert_eq!(res.rows_affected, 3);
+        assert_eq!(
+            cake::Entity::find_by_id(1).one(&db).await?,
+            Some(cake::Model {
+                id: 1,
+                name: "Apple Cake".to_owned(),
+            })
+        );
+        assert_eq!(
+            cake::Entity::find_by_id(1)
+                .one(&db)
+                .await?
+                .map(|cake| cake.id),
+            Some(1)
+        );
+
+        assert_eq!(
+            cake::Entity::find_by_id(2).one(&db).await,
+            Err(DbErr::Query(
+                [
+                    r#"No expectation matches the query:"#,
+                    r#"  SELECT "cake"."id", "cake"."name" FROM "cake" WHERE "cake"."id" = 2 LIMIT 1"#,
+                    r#"The closest expectation is the query matching the statement `SELECT "cake"."id", "cake"."name" FROM "cake" WHERE "cake"."id" = 1 LIMIT 1`, matched 2 time(s):"#,
+                    r#"  SELECT "cake"."id", "cake"."name" FROM "cake" WHERE "cake"."id" = 1 LIMIT 1"#,
+                    r#"Difference ([-expected-] {+actual+}):"#,
+                    r#"  SELECT "cake"."id", "cake"."name" FROM "cake" WHERE "cake"."id" = [-1-] {+2+} LIMIT 1"#,
+                ]
+                .join("\n")
+            ))
+        );
+        assert_eq!(
+            cake::Entity::delete_many().exec(&db).await,
+            Err(DbErr::Exec(
+                [
+                    r#"No expectation matches the execution:"#,
+                    r#"  DELETE FROM "cake""#,
+                    r#"The execution matching a predicate, expected 1 time(s), matched 1 has been served already"#,
+                ]
+                .join("\n")
+            ))
+        );
+
+        assert_eq!(
+            db.verify(),
+            Err(DbErr::Custom(
+                [
+                    r#"Unsatisfied expectations:"#,
+                    r#"  query matching the SQL pattern `SELECT % FROM "fruit"%`, matched 0 time(s)"#,
+                ]
+                .join("\n")
+            ))
+        );
+        fruit::Entity::find().all(&db).await?;
+        assert_eq!(db.verify(), Ok(()));
+
+        Ok(())
+    }
+
+    #[smol_potat::test]
+    async fn test_error_injection() {
+        let conn_err = || DbErr::Conn("Connection reset".to_owned());
+        let db = MockDatabase::new(DbBackend::Postgres)
+            .append_query_results(vec![vec![cake::Model {
+                id: 1,
+                name: "Apple Cake".to_owned(),
+            }]])
+            .append_query_errors(vec![conn_err()])
+            .append_begin_results(vec![Err(conn_err())])
+            .append_commit_results(vec![Err(conn_err())])
+            .into_connection();
+
+        assert!(cake::Entity::find().one(&db).await.unwrap().is_some());
+        assert_eq!(cake::Entity::find().one(&db).await, Err(conn_err()));
+
+        assert_eq!(db.begin().await.err(), Some(conn_err()));
+        assert!(matches!(
+            db.transaction::<_, (), DbErr>(|_| Box::pin(async move { Ok(()) }))
+                .await,
+            Err(TransactionError::Connection(err)) if err == conn_err()
+        ));
+        db.transaction::<_, (), DbErr>(|_| Box::pin(async move { Ok(()) }))
+            .await
+            .unwrap();
+
+        let end = |sql: &str| {
+            Transaction::many(vec![
+                Statement::from_string(DbBackend::Postgres, "BEGIN".to_owned()),
+                Statement::from_string(DbBackend::Postgres, sql.to_owned()),
+            ])
+        };
+        let select = || {
+            Transaction::from_sql_and_values(
+                DbBackend::Postgres,
+                r#"SELECT "cake"."id", "cake"."name" FROM "cake" LIMIT $1"#,
+                vec![1u64.into()],
+            )
+        };
+        assert_eq!(
+            db.into_transaction_log(),
+            vec![select(), select(), end("ROLLBACK"), end("COMMIT")]
+        );
+    }
 }
@@ -0,0 +1,422 @@
+use crate::{DbErr, IntoMockRow, MockExecResult, MockRow, Statement};
+use sea_query::Value;
+use std::{fmt, sync::Arc};
+
+/// Matches the statements served by a [MockExpectation]
+#[derive(Clone)]
+pub enum StatementMatcher {
+    /// The SQL and the values of the statement are equal to the ones of this statement
+    Exact(Statement),
+    /// The SQL of the statement matches this pattern, where `%` matches any sequence of characters.
+    /// The values of the statement are ignored
+    Sql(String),
+    /// The predicate returns true for the statement
+    Predicate(Arc<dyn Fn(&Statement) -> bool + Send + Sync>),
+}
+
+/// Serves a result to the statements matching a [StatementMatcher] in a [MockDatabase](crate::MockDatabase),
+/// regardless of the order in which the statements are executed
+#[derive(Debug, Clone)]
+pub struct MockExpectation {
+    matcher: StatementMatcher,
+    result: MockExpectedResult,
+    times: Option<usize>,
+    hits: usize,
+}
+
+#[derive(Debug, Clone)]
+enum MockExpectedResult {
+    Exec(Result<MockExecResult, DbErr>),
+    Query(Result<Vec<MockRow>, DbErr>),
+}
+
+impl StatementMatcher {
+    /// Match a statement with the same SQL and values
+    pub fn exact(stmt: Statement) -> Self {
+        Self::Exact(stmt)
+    }
+
+    /// Match the SQL of a statement against a pattern, where `%` matches any sequence of characters
+    pub fn sql<T>(pattern: T) -> Self
+    where
+        T: Into<String>,
+    {
+        Self::Sql(pattern.into())
+    }
+
+    /// Match the statements for which the predicate returns true
+    pub fn predicate<F>(predicate: F) -> Self
+    where
+        F: Fn(&Statement) -> bool + Send + Sync + 'static,
+    {
+        Self::Predicate(Arc::new(predicate))
+    }
+
+    /// Check if the statement matches
+    pub fn matches(&self, stmt: &Statement) -> bool {
+        match self {
+            Self::Exact(expected) => {
+                expected.sql == stmt.sql && values_of(expected) == values_of(stmt)
+            }
+            Self::Sql(pattern) => matches_pattern(pattern, &stmt.sql),
+            Self::Predicate(predicate) => predicate(stmt),
+        }
+    }
+
+    /// The SQL this matcher expects, if it can be told
+    fn expected_sql(&self) -> Option<String> {
+        match self {
+            Self::Exact(expected) => Some(expected.to_string()),
+            Self::Sql(pattern) => Some(pattern.clone()),
+            Self::Predicate(_) => None,
+        }
+    }
+}
+
+impl fmt::Debug for StatementMatcher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Exact(stmt) => f.debug_tuple("Exact").field(stmt).finish(),
+            Self::Sql(pattern) => f.debug_tuple("Sql").field(pattern).finish(),
+            Self::Predicate(_) => f.write_str("Predicate"),
+        }
+    }
+}
+
+impl fmt::Display for StatementMatcher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Exact(stmt) => write!(f, "the statement `{}`", stmt),
+            Self::Sql(pattern) => write!(f, "the SQL pattern `{}`", pattern),
+            Self::Predicate(_) => write!(f, "a predicate"),
+        }
+    }
+}
+
+impl From<Statement> for StatementMatcher {
+    fn from(stmt: Statement) -> Self {
+        Self::exact(stmt)
+    }
+}
+
+impl From<&str> for StatementMatcher {
+    fn from(pattern: &str) -> Self {
+        Self::sql(pattern)
+    }
+}
+
+impl From<String> for StatementMatcher {
+    fn from(pattern: String) -> Self {
+        Self::sql(pattern)
+    }
+}
+
+impl MockExpectation {
+    /// Expect an execution matching the statement, and serve it the result
+    pub fn exec<M>(matcher: M, result: MockExecResult) -> Self
+    where
+        M: Into<StatementMatcher>,
+    {
+        Self {
+            matcher: matcher.into(),
+            result: MockExpectedResult::Exec(Ok(result)),
+            times: None,
+            hits: 0,
+        }
+    }
+
+    /// Expect a query matching the statement, and serve it the rows
+    pub fn query<M, T>(matcher: M, rows: Vec<T>) -> Self
+    where
+        M: Into<StatementMatcher>,
+        T: IntoMockRow,
+    {
+        Self {
+            matcher: matcher.into(),
+            result: MockExpectedResult::Query(Ok(rows
+                .into_iter()
+                .map(|row| row.into_mock_row())
+                .collect())),
+            times: None,
+            hits: 0,
+        }
+    }
+
+    /// Expect an execution matching the statement, and fail it with the error
+    pub fn exec_error<M>(matcher: M, err: DbErr) -> Self
+    where
+        M: Into<StatementMatcher>,
+    {
+        Self {
+            matcher: matcher.into(),
+            result: MockExpectedResult::Exec(Err(err)),
+            times: None,
+            hits: 0,
+        }
+    }
+
+    /// Expect a query matching the statement, and fail it with the error
+    pub fn query_error<M>(matcher: M, err: DbErr) -> Self
+    where
+        M: Into<StatementMatcher>,
+    {
+        Self {
+            matcher: matcher.into(),
+            result: MockExpectedResult::Query(Err(err)),
+            times: None,
+            hits: 0,
+        }
+    }
+
+    /// Expect the statement to be executed exactly this many times.
+    /// Otherwise it may be executed any number of times, but at least once to pass [MockDatabase::verify](crate::MockDatabase::verify)
+    pub fn times(mut self, times: usize) -> Self {
+        self.times = Some(times);
+        self
+    }
+
+    /// The number of statements served by this expectation
+    pub fn hits(&self) -> usize {
+        self.hits
+    }
+
+    fn is_query(&self) -> bool {
+        matches!(self.result, MockExpectedResult::Query(_))
+    }
+
+    fn is_exhausted(&self) -> bool {
+        matches!(self.times, Some(times) if self.hits >= times)
+    }
+
+    fn is_satisfied(&self) -> bool {
+        match self.times {
+            Some(times) => self.hits == times,
+            None => self.hits > 0,
+        }
+    }
+
+    fn kind(&self) -> &'static str {
+        if self.is_query() {
+            "query"
+        } else {
+            "execution"
+        }
+    }
+}
+
+impl fmt::Display for MockExpectation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} matching {}", self.kind(), self.matcher)?;
+        match self.times {
+            Some(times) => write!(f, ", expected {} time(s), matched {}", times, self.hits),
+            None => write!(f, ", matched {} time(s)", self.hits),
+        }
+    }
+}
+
+/// Serve an execution from the first expectation matching the statement
+pub(crate) fn serve_exec(
+    expectations: &mut [MockExpectation],
+    stmt: &Statement,
+) -> Result<MockExecResult, DbErr> {
+    let expectation = find(expectations, stmt, false).map_err(DbErr::Exec)?;
+    match &expectation.result {
+        MockExpectedResult::Exec(result) => result.clone(),
+        MockExpectedResult::Query(_) => unreachable!(),
+    }
+}
+
+/// Serve a query from the first expectation matching the statement
+pub(crate) fn serve_query(
+    expectations: &mut [MockExpectation],
+    stmt: &Statement,
+) -> Result<Vec<MockRow>, DbErr> {
+    let expectation = find(expectations, stmt, true).map_err(DbErr::Query)?;
+    match &expectation.result {
+        MockExpectedResult::Query(rows) => rows.clone(),
+        MockExpectedResult::Exec(_) => unreachable!(),
+    }
+}
+
+fn find<'a>(
+    expectations: &'a mut [MockExpectation],
+    stmt: &Statement,
+    is_query: bool,
+) -> Result<&'a mut MockExpectation, String> {
+    let position = expectations.iter().position(|expectation| {
+        expectation.is_query() == is_query
+            && !expectation.is_exhausted()
+            && expectation.matcher.matches(stmt)
+    });
+    match position {
+        Some(i) => {
+            let expectation = &mut expectations[i];
+            expectation.hits += 1;
+            Ok(expectation)
+        }
+        None => Err(unmatched(expectations, stmt, is_query)),
+    }
+}
+
+/// Describe a statement no expectation matches, with a diff against the closest expectation
+fn unmatched(expectations: &[MockExpectation], stmt: &Statement, is_query: bool) -> String {
+    let kind = if is_query { "query" } else { "execution" };
+    let actual = stmt.to_string();
+    let mut message = format!("No expectation matches the {}:\n  {}", kind, actual);
+
+    if let Some(exhausted) = expectations
+        .iter()
+        .find(|expectation| expectation.is_query() == is_query && expectation.matcher.matches(stmt))
+    {
+        message.push_str(&format!("\nThe {} has been served already", exhausted));
+        return message;
+    }
+
+    let closest = expectations
+        .iter()
+        .filter(|expectation| expectation.is_query() == is_query)
+        .filter_map(|expectation| {
+            let expected = expectation.matcher.expected_sql()?;
+            let distance = edit_distance(&expected, &actual);
+            Some((distance, expectation, expected))
+        })
+        .min_by_key(|(distance, _, _)| *distance);
+    match closest {
+        Some((_, expectation, expected)) => message.push_str(&format!(
+            "\nThe closest expectation is the {}:\n  {}\nDifference ([-expected-] {{+actual+}}):\n  {}",
+            expectation,
+            expected,
+            word_diff(&expected, &actual)
+        )),
+        None => message.push_str(&format!("\nThere is no {} expectation to compare with", kind)),
+    }
+    message
+}
+
+/// Report the expectations never matched, or not matched the expected number of times
+pub(crate) fn verify_expectations(expectations: &[MockExpectation]) -> Result<(), DbErr> {
+    let unsatisfied: Vec<String> = expectations
+        .iter()
+        .filter(|expectation| !expectation.is_satisfied())
+        .map(|expectation| format!("  {}", expectation))
+        .collect();
+    if unsatisfied.is_empty() {
+        Ok(())
+    } else {
+        Err(DbErr::Custom(format!(
+            "Unsatisfied expectations:\n{}",
+            unsatisfied.join("\n")
+        )))
+    }
+}
+
+fn values_of(stmt: &Statement) -> &[Value] {
+    match &stmt.values {
+        Some(values) => &values.0,
+        None => &[],
+    }
+}
+
+/// Match a string against a pattern, where `%` matches any sequence of characters
+fn matches_pattern(pattern: &str, sql: &str) -> bool {
+    let mut parts = pattern.split('%');
+    let first = parts.next().unwrap_or_default();
+    let mut rest = match sql.strip_prefix(first) {
+        Some(rest) => rest,
+        None => return false,
+    };
+    let parts: Vec<&str> = parts.collect();
+    let (last, middle) = match parts.split_last() {
+        Some(split) => split,
+        None => return rest.is_empty(),
+    };
+    for part in middle {
+        match rest.find(part) {
+            Some(i) => rest = &rest[i + part.len()..],
+            None => return false,
+        }
+    }
+    rest.ends_with(last)
+}
+
+/// The number of characters to insert, delete or substitute to turn a string into another
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(ca != *cb);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+    row[b.len()]
+}
+
+/// Diff two strings word by word, marking the removed words with `[-` `-]` and the added ones with `{+` `+}`
+fn word_diff(expected: &str, actual: &str) -> String {
+    let a: Vec<&str> = expected.split_whitespace().collect();
+    let b: Vec<&str> = actual.split_whitespace().collect();
+    // The length of the longest common subsequence of the suffixes
+    let mut lcs = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            lcs[i][j] = if a[i] == b[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+    let mut words = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() || j < b.len() {
+        if i < a.len() && j < b.len() && a[i] == b[j] {
+            words.push(a[i].to_owned());
+            i += 1;
+            j += 1;
+        } else if j == b.len() || (i < a.len() && lcs[i + 1][j] >= lcs[i][j + 1]) {
+            words.push(format!("[-{}-]", a[i]));
+            i += 1;
+        } else {
+            words.push(format!("{{+{}+}}", b[j]));
+            j += 1;
+        }
+    }
+    words.join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn pattern() {
+        assert!(matches_pattern(
+            r#"SELECT % FROM "cake" WHERE %"#,
+            r#"SELECT "cake"."id" FROM "cake" WHERE "cake"."id" = $1"#
+        ));
+        assert!(matches_pattern("SELECT 1", "SELECT 1"));
+        assert!(matches_pattern("%", "SELECT 1"));
+        assert!(!matches_pattern("SELECT 1", "SELECT 12"));
+        assert!(!matches_pattern(
+            r#"% FROM "fruit"%"#,
+            r#"SELECT * FROM "cake""#
+        ));
+    }
+
+    #[test]
+    fn diff() {
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(
+            word_diff(
+                r#"SELECT "id" FROM "cake" LIMIT 1"#,
+                r#"SELECT "id" FROM "fruit" LIMIT 1"#
+            ),
+            r#"SELECT "id" FROM [-"cake"-] {+"fruit"+} LIMIT 1"#
+        );
+    }
+}
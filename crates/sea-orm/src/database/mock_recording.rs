@@ -0,0 +1,1120 @@
+use crate::{
+    AccessMode, ConnectionTrait, DatabaseConnection, DatabaseTransaction, DbBackend, DbErr,
+    DriverErr, DriverErrKind, ExecResult, IntoMockRow, IsolationLevel, MockDatabase,
+    MockExecResult, MockExpectation, MockRow, QueryResult, QueryResultRow, QueryStream, Statement,
+    StreamTrait, TransactionError, TransactionTrait,
+};
+use futures::Stream;
+use sea_query::{Value, Values};
+use serde_json::{json, Map, Value as Json};
+use std::{
+    collections::BTreeMap,
+    future::Future,
+    path::Path,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+    time::Duration,
+};
+
+/// A [ConnectionTrait] recording every statement run on the wrapped connection, with its result,
+/// to replay them later on a [MockDatabase] with [Recording::into_mock_database].
+///
+/// The statements run in a transaction begun on this connection are recorded too,
+/// along with the `BEGIN`, `COMMIT` and `ROLLBACK` of the transaction and its savepoints.
+/// The rollback of a transaction dropped without being committed or rolled back is not recorded.
+///
+/// ```
+/// # use sea_orm::{error::*, tests_cfg::*, *};
+/// #
+/// # let _: Result<(), DbErr> = smol::block_on(async {
+/// #
+/// # let db = MockDatabase::new(DbBackend::Postgres)
+/// #     .append_query_results(vec![vec![cake::Model {
+/// #         id: 1,
+/// #         name: "New York Cheese".to_owned(),
+/// #     }]])
+/// #     .into_connection();
+/// #
+/// let recorder = RecordingConnection::new(db);
+/// let recorded = cake::Entity::find_by_id(1).one(&recorder).await?;
+///
+/// // Save the recording with `recorder.save("tests/fixtures/cake.json")`,
+/// // then load it with `Recording::load` to replay it
+/// let recording = Recording::from_json(&recorder.recording().to_json())?;
+/// let db = recording.into_mock_database().into_connection();
+///
+/// assert_eq!(cake::Entity::find_by_id(1).one(&db).await?, recorded);
+/// db.verify()?;
+/// #
+/// # Ok(())
+/// # });
+/// ```
+#[derive(Debug)]
+pub struct RecordingConnection {
+    conn: DatabaseConnection,
+    recorder: Recorder,
+}
+
+/// The statements recorded by a [RecordingConnection], with their results
+#[derive(Debug, Clone)]
+pub struct Recording {
+    /// The database backend the statements were run on
+    pub db_backend: DbBackend,
+    /// The statements, in the order they were run
+    pub statements: Vec<RecordedStatement>,
+}
+
+/// A statement recorded by a [RecordingConnection]
+#[derive(Debug, Clone)]
+pub struct RecordedStatement {
+    /// The statement
+    pub statement: Statement,
+    /// The result of the statement
+    pub result: RecordedResult,
+}
+
+/// The result of a statement recorded by a [RecordingConnection]
+#[derive(Debug, Clone)]
+pub enum RecordedResult {
+    /// The result of an execution
+    Exec(MockExecResult),
+    /// The rows returned by a query
+    Query(Vec<MockRow>),
+    /// The error an execution failed with
+    ExecError(DbErr),
+    /// The error a query failed with
+    QueryError(DbErr),
+    /// A `BEGIN`, `COMMIT` or `ROLLBACK` of a transaction, or a statement of a savepoint, succeeded
+    Transaction,
+    /// The error a `BEGIN`, `COMMIT` or `ROLLBACK` of a transaction, or a statement of a savepoint, failed with
+    TransactionError(DbErr),
+}
+
+/// Records the statements run on a [RecordingConnection] and in the transactions begun on it
+#[derive(Debug, Clone)]
+pub(crate) struct Recorder(Arc<Mutex<Recording>>);
+
+/// A stream of the rows of a query run on a [RecordingConnection],
+/// the rows are recorded as they are streamed
+pub struct RecordingStream<S> {
+    stream: S,
+    recorder: Recorder,
+    index: usize,
+}
+
+impl RecordingConnection {
+    /// Record the statements run on the connection
+    pub fn new(conn: DatabaseConnection) -> Self {
+        let recording = Recording {
+            db_backend: conn.get_database_backend(),
+            statements: Vec::new(),
+        };
+        Self {
+            conn,
+            recorder: Recorder(Arc::new(Mutex::new(recording))),
+        }
+    }
+
+    /// The statements recorded so far
+    pub fn recording(&self) -> Recording {
+        self.recorder.0.lock().unwrap().clone()
+    }
+
+    /// Get the recording and the wrapped connection back
+    pub fn into_parts(self) -> (Recording, DatabaseConnection) {
+        // a transaction begun on the connection may still share the recording
+        let recording = match Arc::try_unwrap(self.recorder.0) {
+            Ok(recording) => recording.into_inner().unwrap(),
+            Err(recording) => recording.lock().unwrap().clone(),
+        };
+        (recording, self.conn)
+    }
+
+    /// Write the statements recorded so far to a JSON file
+    pub fn save<P>(&self, path: P) -> Result<(), DbErr>
+    where
+        P: AsRef<Path>,
+    {
+        self.recording().save(path)
+    }
+}
+
+impl Recorder {
+    fn record(&self, statement: Statement, result: RecordedResult) -> usize {
+        let mut recording = self.0.lock().unwrap();
+        recording
+            .statements
+            .push(RecordedStatement { statement, result });
+        recording.statements.len() - 1
+    }
+
+    pub(crate) fn exec(&self, stmt: Statement, res: &Result<ExecResult, DbErr>) {
+        let recorded = match res {
+            Ok(res) => RecordedResult::Exec(exec_result_to_mock(res)),
+            Err(err) => RecordedResult::ExecError(err.clone()),
+        };
+        self.record(stmt, recorded);
+    }
+
+    pub(crate) fn query_one(&self, stmt: Statement, res: &Result<Option<QueryResult>, DbErr>) {
+        let recorded = match res {
+            Ok(row) => RecordedResult::Query(row.iter().map(query_result_to_mock).collect()),
+            Err(err) => RecordedResult::QueryError(err.clone()),
+        };
+        self.record(stmt, recorded);
+    }
+
+    pub(crate) fn query_all(&self, stmt: Statement, res: &Result<Vec<QueryResult>, DbErr>) {
+        let recorded = match res {
+            Ok(rows) => RecordedResult::Query(rows.iter().map(query_result_to_mock).collect()),
+            Err(err) => RecordedResult::QueryError(err.clone()),
+        };
+        self.record(stmt, recorded);
+    }
+
+    /// Record a `BEGIN`, `COMMIT` or `ROLLBACK` of a transaction, or a statement of a savepoint
+    pub(crate) fn transaction(&self, sql: &str, res: &Result<(), DbErr>) {
+        let db_backend = self.0.lock().unwrap().db_backend;
+        let stmt = Statement::from_string(db_backend, sql.to_owned());
+        let recorded = match res {
+            Ok(()) => RecordedResult::Transaction,
+            Err(err) => RecordedResult::TransactionError(err.clone()),
+        };
+        self.record(stmt, recorded);
+    }
+
+    /// Record the rows of a stream as they are streamed, in the place of the statement
+    /// at the time the stream is created
+    pub(crate) fn stream<S>(&self, stmt: Statement, stream: S) -> RecordingStream<S> {
+        let index = self.record(stmt, RecordedResult::Query(Vec::new()));
+        RecordingStream {
+            stream,
+            recorder: self.clone(),
+            index,
+        }
+    }
+}
+
+impl<S> std::fmt::Debug for RecordingStream<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "RecordingStream")
+    }
+}
+
+impl<S> Stream for RecordingStream<S>
+where
+    S: Stream<Item = Result<QueryResult, DbErr>> + Unpin,
+{
+    type Item = Result<QueryResult, DbErr>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        let res = Pin::new(&mut this.stream).poll_next(cx);
+        if let Poll::Ready(Some(res)) = &res {
+            let mut recording = this.recorder.0.lock().unwrap();
+            let recorded = &mut recording.statements[this.index].result;
+            match (res, recorded) {
+                (Ok(row), RecordedResult::Query(rows)) => rows.push(query_result_to_mock(row)),
+                (Err(err), recorded) => *recorded = RecordedResult::QueryError(err.clone()),
+                _ => {}
+            }
+        }
+        res
+    }
+}
+
+#[async_trait::async_trait]
+impl ConnectionTrait for RecordingConnection {
+    fn get_database_backend(&self) -> DbBackend {
+        self.conn.get_database_backend()
+    }
+
+    async fn execute(&self, stmt: Statement) -> Result<ExecResult, DbErr> {
+        self.execute_with_timeout(stmt, None).await
+    }
+
+    async fn query_one(&self, stmt: Statement) -> Result<Option<QueryResult>, DbErr> {
+        self.query_one_with_timeout(stmt, None).await
+    }
+
+    async fn query_all(&self, stmt: Statement) -> Result<Vec<QueryResult>, DbErr> {
+        self.query_all_with_timeout(stmt, None).await
+    }
+
+    async fn execute_with_timeout(
+        &self,
+        stmt: Statement,
+        timeout: Option<Duration>,
+    ) -> Result<ExecResult, DbErr> {
+        let res = self.conn.execute_with_timeout(stmt.clone(), timeout).await;
+        self.recorder.exec(stmt, &res);
+        res
+    }
+
+    async fn query_one_with_timeout(
+        &self,
+        stmt: Statement,
+        timeout: Option<Duration>,
+    ) -> Result<Option<QueryResult>, DbErr> {
+        let res = self
+            .conn
+            .query_one_with_timeout(stmt.clone(), timeout)
+            .await;
+        self.recorder.query_one(stmt, &res);
+        res
+    }
+
+    async fn query_all_with_timeout(
+        &self,
+        stmt: Statement,
+        timeout: Option<Duration>,
+    ) -> Result<Vec<QueryResult>, DbErr> {
+        let res = self
+            .conn
+            .query_all_with_timeout(stmt.clone(), timeout)
+            .await;
+        self.recorder.query_all(stmt, &res);
+        res
+    }
+
+    fn support_returning(&self) -> bool {
+        self.conn.support_returning()
+    }
+
+    fn is_mock_connection(&self) -> bool {
+        self.conn.is_mock_connection()
+    }
+}
+
+#[async_trait::async_trait]
+impl<'a> StreamTrait<'a> for RecordingConnection {
+    type Stream = RecordingStream<QueryStream>;
+
+    fn stream(
+        &'a self,
+        stmt: Statement,
+    ) -> Pin<Box<dyn Future<Output = Result<Self::Stream, DbErr>> + 'a + Send>> {
+        Box::pin(async move {
+            match self.conn.stream(stmt.clone()).await {
+                Ok(stream) => Ok(self.recorder.stream(stmt, stream)),
+                Err(err) => {
+                    self.recorder
+                        .record(stmt, RecordedResult::QueryError(err.clone()));
+                    Err(err)
+                }
+            }
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl TransactionTrait for RecordingConnection {
+    async fn begin(&self) -> Result<DatabaseTransaction, DbErr> {
+        self.begin_with_config(None, None).await
+    }
+
+    async fn begin_with_config(
+        &self,
+        isolation_level: Option<IsolationLevel>,
+        access_mode: Option<AccessMode>,
+    ) -> Result<DatabaseTransaction, DbErr> {
+        let res = self
+            .conn
+            .begin_with_config(isolation_level, access_mode)
+            .await;
+        self.recorder
+            .transaction("BEGIN", &res.as_ref().map(|_| ()).map_err(Clone::clone));
+        res.map(|transaction| transaction.with_recorder(self.recorder.clone()))
+    }
+
+    async fn transaction<F, T, E>(&self, callback: F) -> Result<T, TransactionError<E>>
+    where
+        F: for<'c> FnOnce(
+                &'c DatabaseTransaction,
+            ) -> Pin<Box<dyn Future<Output = Result<T, E>> + Send + 'c>>
+            + Send,
+        T: Send,
+        E: std::error::Error + Send,
+    {
+        self.transaction_with_config(callback, None, None).await
+    }
+}
+
+impl Recording {
+    /// Build a [MockDatabase] serving the recorded results, each to one run of the exact recorded statement.
+    ///
+    /// A statement diverging from the recording fails with a diff against the closest recorded statement,
+    /// and [MockDatabase::verify] reports the recorded statements that were not replayed.
+    /// The recorded `BEGIN`, `COMMIT` and `ROLLBACK` of the transactions give the results of
+    /// [MockDatabase::append_begin_results], [MockDatabase::append_commit_results] and [MockDatabase::append_rollback_results]
+    pub fn into_mock_database(self) -> MockDatabase {
+        let mut expectations = Vec::new();
+        let mut begin_results = Vec::new();
+        let mut commit_results = Vec::new();
+        let mut rollback_results = Vec::new();
+        for RecordedStatement { statement, result } in self.statements {
+            let expectation = match result {
+                RecordedResult::Exec(res) => MockExpectation::exec(statement, res),
+                RecordedResult::Query(rows) => MockExpectation::query(statement, rows),
+                RecordedResult::ExecError(err) => MockExpectation::exec_error(statement, err),
+                RecordedResult::QueryError(err) => MockExpectation::query_error(statement, err),
+                RecordedResult::Transaction | RecordedResult::TransactionError(_) => {
+                    let res = match result {
+                        RecordedResult::TransactionError(err) => Err(err),
+                        _ => Ok(()),
+                    };
+                    match statement.sql.as_str() {
+                        "BEGIN" => begin_results.push(res),
+                        "COMMIT" => commit_results.push(res),
+                        "ROLLBACK" => rollback_results.push(res),
+                        // the statements of a savepoint always succeed on a MockDatabase
+                        _ => {}
+                    }
+                    continue;
+                }
+            };
+            expectations.push(expectation.times(1));
+        }
+        MockDatabase::new(self.db_backend)
+            .append_begin_results(begin_results)
+            .append_commit_results(commit_results)
+            .append_rollback_results(rollback_results)
+            .append_expectations(expectations)
+    }
+
+    /// Read a recording from a JSON file written by [Recording::save]
+    pub fn load<P>(path: P) -> Result<Self, DbErr>
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref();
+        let file = std::fs::read_to_string(path)
+            .map_err(|e| DbErr::Custom(format!("Failed to read {}: {}", path.display(), e)))?;
+        let json = serde_json::from_str(&file).map_err(|e| DbErr::Json(e.to_string()))?;
+        Self::from_json(&json)
+    }
+
+    /// Write the recording to a JSON file
+    pub fn save<P>(&self, path: P) -> Result<(), DbErr>
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref();
+        let file = serde_json::to_string_pretty(&self.to_json())
+            .map_err(|e| DbErr::Json(e.to_string()))?;
+        std::fs::write(path, file)
+            .map_err(|e| DbErr::Custom(format!("Failed to write {}: {}", path.display(), e)))
+    }
+
+    /// Convert the recording to JSON, where every value is tagged with its type, e.g. `{ "Int": 1 }`
+    pub fn to_json(&self) -> Json {
+        let statements: Vec<Json> = self
+            .statements
+            .iter()
+            .map(|RecordedStatement { statement, result }| {
+                let result = match result {
+                    RecordedResult::Exec(res) => json!({
+                        "exec": {
+                            "last_insert_id": res.last_insert_id,
+                            "rows_affected": res.rows_affected,
+                        }
+                    }),
+                    RecordedResult::Query(rows) => {
+                        let rows: Vec<Json> = rows
+                            .iter()
+                            .map(|row| {
+                                let map: Map<String, Json> = row
+                                    .clone()
+                                    .into_column_value_tuples()
+                                    .map(|(col, value)| (col, value_to_json(&value)))
+                                    .collect();
+                                Json::Object(map)
+                            })
+                            .collect();
+                        json!({ "query": rows })
+                    }
+                    RecordedResult::ExecError(err) => json!({ "exec_error": error_to_json(err) }),
+                    RecordedResult::QueryError(err) => json!({ "query_error": error_to_json(err) }),
+                    RecordedResult::Transaction => json!({ "transaction": Json::Null }),
+                    RecordedResult::TransactionError(err) => {
+                        json!({ "transaction_error": error_to_json(err) })
+                    }
+                };
+                json!({
+                    "sql": statement.sql,
+                    "values": statement
+                        .values
+                        .as_ref()
+                        .map(|values| values.iter().map(value_to_json).collect::<Vec<_>>()),
+                    "result": result,
+                })
+            })
+            .collect();
+        json!({
+            "db_backend": format!("{:?}", self.db_backend),
+            "statements": statements,
+        })
+    }
+
+    /// Read a recording from the JSON of [Recording::to_json]
+    pub fn from_json(json: &Json) -> Result<Self, DbErr> {
+        let db_backend = match json["db_backend"].as_str() {
+            Some("MySql") => DbBackend::MySql,
+            Some("Postgres") => DbBackend::Postgres,
+            Some("Sqlite") => DbBackend::Sqlite,
+            _ => return Err(invalid("db_backend", &json["db_backend"])),
+        };
+        let statements = json["statements"]
+            .as_array()
+            .ok_or_else(|| invalid("statements", &json["statements"]))?
+            .iter()
+            .map(|json| {
+                let sql = json["sql"]
+                    .as_str()
+                    .ok_or_else(|| invalid("sql", &json["sql"]))?
+                    .to_owned();
+                let values = match &json["values"] {
+                    Json::Null => None,
+                    Json::Array(values) => Some(Values(
+                        values.iter().map(json_to_value).collect::<Result<_, _>>()?,
+                    )),
+                    values => return Err(invalid("values", values)),
+                };
+                let statement = Statement {
+                    sql,
+                    values,
+                    db_backend,
+                };
+                let result = json_to_result(&json["result"])?;
+                Ok(RecordedStatement { statement, result })
+            })
+            .collect::<Result<_, DbErr>>()?;
+        Ok(Self {
+            db_backend,
+            statements,
+        })
+    }
+}
+
+fn json_to_result(json: &Json) -> Result<RecordedResult, DbErr> {
+    if let Some(res) = json.get("exec") {
+        let get = |key: &str| res[key].as_u64().ok_or_else(|| invalid(key, &res[key]));
+        Ok(RecordedResult::Exec(MockExecResult {
+            last_insert_id: get("last_insert_id")?,
+            rows_affected: get("rows_affected")?,
+        }))
+    } else if let Some(rows) = json.get("query") {
+        let rows = rows.as_array().ok_or_else(|| invalid("query", rows))?;
+        let rows = rows
+            .iter()
+            .map(|row| {
+                let row = row.as_object().ok_or_else(|| invalid("row", row))?;
+                row.iter()
+                    .map(|(col, value)| Ok((col.clone(), json_to_value(value)?)))
+                    .collect::<Result<BTreeMap<String, Value>, DbErr>>()
+                    .map(IntoMockRow::into_mock_row)
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(RecordedResult::Query(rows))
+    } else if let Some(err) = json.get("exec_error") {
+        Ok(RecordedResult::ExecError(json_to_error(err, DbErr::Exec)?))
+    } else if let Some(err) = json.get("query_error") {
+        Ok(RecordedResult::QueryError(json_to_error(
+            err,
+            DbErr::Query,
+        )?))
+    } else if json.get("transaction").is_some() {
+        Ok(RecordedResult::Transaction)
+    } else if let Some(err) = json.get("transaction_error") {
+        Ok(RecordedResult::TransactionError(json_to_error(
+            err,
+            DbErr::Query,
+        )?))
+    } else {
+        Err(invalid("result", json))
+    }
+}
+
+/// A driver error is kept with its details, e.g. `{ "driver": { "kind": "UniqueViolation", .. } }`,
+/// any other error is kept as its message
+fn error_to_json(err: &DbErr) -> Json {
+    match err {
+        DbErr::Driver(err) => json!({
+            "driver": {
+                "kind": err.kind.map(|kind| format!("{:?}", kind)),
+                "code": err.code,
+                "vendor_code": err.vendor_code,
+                "message": err.message,
+                "constraint": err.constraint,
+                "table": err.table,
+                "column": err.column,
+            }
+        }),
+        DbErr::Exec(msg) | DbErr::Query(msg) => json!(msg),
+        err => json!(err.to_string()),
+    }
+}
+
+/// Read an error written by [error_to_json], a message is read with `from_message`
+fn json_to_error<F>(json: &Json, from_message: F) -> Result<DbErr, DbErr>
+where
+    F: FnOnce(String) -> DbErr,
+{
+    if let Some(msg) = json.as_str() {
+        return Ok(from_message(msg.to_owned()));
+    }
+    let err = json
+        .get("driver")
+        .and_then(Json::as_object)
+        .ok_or_else(|| invalid("error", json))?;
+    let get_str = |key: &str| -> Result<Option<String>, DbErr> {
+        match &err[key] {
+            Json::Null => Ok(None),
+            Json::String(s) => Ok(Some(s.clone())),
+            v => Err(invalid(key, v)),
+        }
+    };
+    let kind = match get_str("kind")?.as_deref() {
+        None => None,
+        Some("UniqueViolation") => Some(DriverErrKind::UniqueViolation),
+        Some("ForeignKeyViolation") => Some(DriverErrKind::ForeignKeyViolation),
+        Some("NotNullViolation") => Some(DriverErrKind::NotNullViolation),
+        Some("CheckViolation") => Some(DriverErrKind::CheckViolation),
+        Some("Deadlock") => Some(DriverErrKind::Deadlock),
+        Some("SerializationFailure") => Some(DriverErrKind::SerializationFailure),
+        Some("LockTimeout") => Some(DriverErrKind::LockTimeout),
+        Some("ConnectionLost") => Some(DriverErrKind::ConnectionLost),
+        Some("Timeout") => Some(DriverErrKind::Timeout),
+        Some(_) => return Err(invalid("kind", &err["kind"])),
+    };
+    let vendor_code = match &err["vendor_code"] {
+        Json::Null => None,
+        v => Some(
+            v.as_i64()
+                .and_then(|v| i32::try_from(v).ok())
+                .ok_or_else(|| invalid("vendor_code", v))?,
+        ),
+    };
+    Ok(DbErr::Driver(Box::new(DriverErr {
+        kind,
+        code: get_str("code")?,
+        vendor_code,
+        message: get_str("message")?.unwrap_or_default(),
+        constraint: get_str("constraint")?,
+        table: get_str("table")?,
+        column: get_str("column")?,
+        source: None,
+    })))
+}
+
+fn invalid(field: &str, json: &Json) -> DbErr {
+    DbErr::Json(format!("Invalid `{}` in the recording: {}", field, json))
+}
+
+fn exec_result_to_mock(res: &ExecResult) -> MockExecResult {
+    let last_insert_id = match &res.result {
+        #[cfg(feature = "sqlx-postgres")]
+        crate::ExecResultHolder::SqlxPostgres(_) => 0,
+        _ => res.last_insert_id(),
+    };
+    MockExecResult {
+        last_insert_id,
+        rows_affected: res.rows_affected(),
+    }
+}
+
+/// Read the columns of a row as [Value]s of the type of the column.
+///
+/// A column of a type that can not be recorded is left out of the row, so reading it
+/// from the replayed row fails instead of the recorded statement
+fn query_result_to_mock(res: &QueryResult) -> MockRow {
+    let mut values: BTreeMap<String, Value> = BTreeMap::new();
+    #[allow(unused_macros)]
+    macro_rules! try_get_type {
+        ( $type: ty, $col: ident ) => {
+            if let Ok(v) = res.try_get::<Option<$type>>("", &$col) {
+                values.insert($col, v.into());
+                continue;
+            }
+        };
+    }
+    match &res.row {
+        #[cfg(feature = "sqlx-mysql")]
+        QueryResultRow::SqlxMySql(row) => {
+            use sqlx::{Column, MySql, Row, Type};
+            for column in row.columns() {
+                let col = column.name().to_owned();
+                let col_type = column.type_info();
+                macro_rules! match_mysql_type {
+                    ( $type: ty ) => {
+                        if <$type as Type<MySql>>::type_info().eq(col_type) {
+                            try_get_type!($type, col)
+                        }
+                    };
+                }
+                match_mysql_type!(bool);
+                match_mysql_type!(i8);
+                match_mysql_type!(i16);
+                match_mysql_type!(i32);
+                match_mysql_type!(i64);
+                match_mysql_type!(u8);
+                match_mysql_type!(u16);
+                match_mysql_type!(u32);
+                match_mysql_type!(u64);
+                match_mysql_type!(f32);
+                match_mysql_type!(f64);
+                match_mysql_type!(String);
+                #[cfg(feature = "with-chrono")]
+                match_mysql_type!(chrono::NaiveDate);
+                #[cfg(feature = "with-chrono")]
+                match_mysql_type!(chrono::NaiveTime);
+                #[cfg(feature = "with-chrono")]
+                match_mysql_type!(chrono::NaiveDateTime);
+                #[cfg(feature = "with-chrono")]
+                match_mysql_type!(chrono::DateTime<chrono::Utc>);
+                #[cfg(feature = "with-rust_decimal")]
+                match_mysql_type!(rust_decimal::Decimal);
+                try_get_type!(serde_json::Value, col);
+                try_get_type!(String, col);
+                #[cfg(feature = "with-uuid")]
+                try_get_type!(uuid::Uuid, col);
+                try_get_type!(Vec<u8>, col);
+            }
+        }
+        #[cfg(feature = "sqlx-postgres")]
+        QueryResultRow::SqlxPostgres(row) => {
+            use sqlx::{Column, Postgres, Row, Type};
+            for column in row.columns() {
+                let col = column.name().to_owned();
+                let col_type = column.type_info();
+                macro_rules! match_postgres_type {
+                    ( $type: ty ) => {
+                        if <$type as Type<Postgres>>::type_info().eq(col_type) {
+                            try_get_type!($type, col)
+                        }
+                    };
+                }
+                match_postgres_type!(bool);
+                match_postgres_type!(i8);
+                match_postgres_type!(i16);
+                match_postgres_type!(i32);
+                match_postgres_type!(i64);
+                match_postgres_type!(u32);
+                match_postgres_type!(f32);
+                match_postgres_type!(f64);
+                #[cfg(feature = "with-chrono")]
+                match_postgres_type!(chrono::NaiveDate);
+                #[cfg(feature = "with-chrono")]
+                match_postgres_type!(chrono::NaiveTime);
+                #[cfg(feature = "with-chrono")]
+                match_postgres_type!(chrono::NaiveDateTime);
+                #[cfg(feature = "with-chrono")]
+                match_postgres_type!(chrono::DateTime<chrono::FixedOffset>);
+                #[cfg(feature = "with-rust_decimal")]
+                match_postgres_type!(rust_decimal::Decimal);
+                try_get_type!(serde_json::Value, col);
+                try_get_type!(String, col);
+                #[cfg(feature = "with-uuid")]
+                try_get_type!(uuid::Uuid, col);
+                try_get_type!(Vec<u8>, col);
+            }
+        }
+        #[cfg(feature = "sqlx-sqlite")]
+        QueryResultRow::SqlxSqlite(row) => {
+            use sqlx::{Column, Row, Sqlite, Type};
+            for column in row.columns() {
+                let col = column.name().to_owned();
+                let col_type = column.type_info();
+                macro_rules! match_sqlite_type {
+                    ( $type: ty ) => {
+                        if <$type as Type<Sqlite>>::type_info().eq(col_type) {
+                            try_get_type!($type, col)
+                        }
+                    };
+                }
+                match_sqlite_type!(bool);
+                match_sqlite_type!(i8);
+                match_sqlite_type!(i16);
+                match_sqlite_type!(i32);
+                match_sqlite_type!(i64);
+                match_sqlite_type!(u8);
+                match_sqlite_type!(u16);
+                match_sqlite_type!(u32);
+                match_sqlite_type!(f32);
+                match_sqlite_type!(f64);
+                #[cfg(feature = "with-chrono")]
+                match_sqlite_type!(chrono::NaiveDate);
+                #[cfg(feature = "with-chrono")]
+                match_sqlite_type!(chrono::NaiveTime);
+                #[cfg(feature = "with-chrono")]
+                match_sqlite_type!(chrono::NaiveDateTime);
+                try_get_type!(String, col);
+                #[cfg(feature = "with-uuid")]
+                try_get_type!(uuid::Uuid, col);
+                try_get_type!(Vec<u8>, col);
+            }
+        }
+        QueryResultRow::Mock(row) => values.extend(row.clone().into_column_value_tuples()),
+    }
+    values.into_mock_row()
+}
+
+/// Tag a value with its type, e.g. `{ "Int": 1 }`, or `{ "Int": null }` for a null of the type
+fn value_to_json(value: &Value) -> Json {
+    macro_rules! tag {
+        ( $name: expr, $v: expr ) => {
+            json!({ $name: $v })
+        };
+    }
+    match value {
+        Value::Bool(v) => tag!("Bool", v),
+        Value::TinyInt(v) => tag!("TinyInt", v),
+        Value::SmallInt(v) => tag!("SmallInt", v),
+        Value::Int(v) => tag!("Int", v),
+        Value::BigInt(v) => tag!("BigInt", v),
+        Value::TinyUnsigned(v) => tag!("TinyUnsigned", v),
+        Value::SmallUnsigned(v) => tag!("SmallUnsigned", v),
+        Value::Unsigned(v) => tag!("Unsigned", v),
+        Value::BigUnsigned(v) => tag!("BigUnsigned", v),
+        Value::Float(v) => tag!("Float", v),
+        Value::Double(v) => tag!("Double", v),
+        Value::String(v) => tag!("String", v),
+        Value::Bytes(v) => tag!("Bytes", v),
+        Value::Json(v) => tag!("Json", v),
+        #[cfg(feature = "with-chrono")]
+        Value::ChronoDate(v) => tag!("ChronoDate", v.as_ref().map(|v| v.to_string())),
+        #[cfg(feature = "with-chrono")]
+        Value::ChronoTime(v) => tag!("ChronoTime", v.as_ref().map(|v| v.to_string())),
+        #[cfg(feature = "with-chrono")]
+        Value::ChronoDateTime(v) => tag!(
+            "ChronoDateTime",
+            v.as_ref().map(|v| v.format(CHRONO_DATE_TIME).to_string())
+        ),
+        #[cfg(feature = "with-chrono")]
+        Value::ChronoDateTimeUtc(v) => {
+            tag!("ChronoDateTimeUtc", v.as_ref().map(|v| v.to_rfc3339()))
+        }
+        #[cfg(feature = "with-chrono")]
+        Value::ChronoDateTimeLocal(v) => {
+            tag!("ChronoDateTimeLocal", v.as_ref().map(|v| v.to_rfc3339()))
+        }
+        #[cfg(feature = "with-chrono")]
+        Value::ChronoDateTimeWithTimeZone(v) => tag!(
+            "ChronoDateTimeWithTimeZone",
+            v.as_ref().map(|v| v.to_rfc3339())
+        ),
+        #[cfg(feature = "with-time")]
+        Value::TimeDate(v) => tag!("TimeDate", v.as_ref().map(|v| v.format(TIME_DATE))),
+        #[cfg(feature = "with-time")]
+        Value::TimeTime(v) => tag!("TimeTime", v.as_ref().map(|v| v.format(TIME_TIME))),
+        #[cfg(feature = "with-time")]
+        Value::TimeDateTime(v) => {
+            tag!("TimeDateTime", v.as_ref().map(|v| v.format(TIME_DATE_TIME)))
+        }
+        #[cfg(feature = "with-time")]
+        Value::TimeDateTimeWithTimeZone(v) => tag!(
+            "TimeDateTimeWithTimeZone",
+            v.as_ref().map(|v| v.format(TIME_DATE_TIME_WITH_TIME_ZONE))
+        ),
+        #[cfg(feature = "with-uuid")]
+        Value::Uuid(v) => tag!("Uuid", v.as_ref().map(|v| v.to_string())),
+        #[cfg(feature = "with-rust_decimal")]
+        Value::Decimal(v) => tag!("Decimal", v.as_ref().map(|v| v.to_string())),
+        #[allow(unreachable_patterns)]
+        _ => tag!("Unsupported", format!("{:?}", value)),
+    }
+}
+
+/// Read a value tagged with its type by [value_to_json]
+fn json_to_value(json: &Json) -> Result<Value, DbErr> {
+    let (tag, v) = match json.as_object() {
+        Some(map) if map.len() == 1 => map.iter().next().unwrap(),
+        _ => return Err(invalid("value", json)),
+    };
+    macro_rules! from_json {
+        ( $type: ty ) => {
+            serde_json::from_value::<Option<$type>>(v.clone())
+                .map(Value::from)
+                .map_err(|_| invalid("value", json))
+        };
+    }
+    #[allow(unused_macros)]
+    macro_rules! parse {
+        ( $type: ty, $parse: expr ) => {
+            match v {
+                Json::Null => Ok(Value::from(None::<$type>)),
+                Json::String(s) => $parse(s.as_str())
+                    .map(Value::from)
+                    .map_err(|_| invalid("value", json)),
+                _ => Err(invalid("value", json)),
+            }
+        };
+    }
+    match tag.as_str() {
+        "Bool" => from_json!(bool),
+        "TinyInt" => from_json!(i8),
+        "SmallInt" => from_json!(i16),
+        "Int" => from_json!(i32),
+        "BigInt" => from_json!(i64),
+        "TinyUnsigned" => from_json!(u8),
+        "SmallUnsigned" => from_json!(u16),
+        "Unsigned" => from_json!(u32),
+        "BigUnsigned" => from_json!(u64),
+        "Float" => from_json!(f32),
+        "Double" => from_json!(f64),
+        "String" => from_json!(String),
+        "Bytes" => from_json!(Vec<u8>),
+        "Json" => Ok(match v {
+            Json::Null => Value::Json(None),
+            v => Value::Json(Some(Box::new(v.clone()))),
+        }),
+        #[cfg(feature = "with-chrono")]
+        "ChronoDate" => parse!(chrono::NaiveDate, str::parse::<chrono::NaiveDate>),
+        #[cfg(feature = "with-chrono")]
+        "ChronoTime" => parse!(chrono::NaiveTime, str::parse::<chrono::NaiveTime>),
+        #[cfg(feature = "with-chrono")]
+        "ChronoDateTime" => parse!(chrono::NaiveDateTime, |s| {
+            chrono::NaiveDateTime::parse_from_str(s, CHRONO_DATE_TIME)
+        }),
+        #[cfg(feature = "with-chrono")]
+        "ChronoDateTimeUtc" => parse!(chrono::DateTime<chrono::Utc>, |s| {
+            chrono::DateTime::parse_from_rfc3339(s).map(|v| v.with_timezone(&chrono::Utc))
+        }),
+        #[cfg(feature = "with-chrono")]
+        "ChronoDateTimeLocal" => parse!(chrono::DateTime<chrono::Local>, |s| {
+            chrono::DateTime::parse_from_rfc3339(s).map(|v| v.with_timezone(&chrono::Local))
+        }),
+        #[cfg(feature = "with-chrono")]
+        "ChronoDateTimeWithTimeZone" => parse!(
+            chrono::DateTime<chrono::FixedOffset>,
+            chrono::DateTime::parse_from_rfc3339
+        ),
+        #[cfg(feature = "with-time")]
+        "TimeDate" => parse!(time::Date, |s| time::Date::parse(s, TIME_DATE)),
+        #[cfg(feature = "with-time")]
+        "TimeTime" => parse!(time::Time, |s| time::Time::parse(s, TIME_TIME)),
+        #[cfg(feature = "with-time")]
+        "TimeDateTime" => parse!(time::PrimitiveDateTime, |s| {
+            time::PrimitiveDateTime::parse(s, TIME_DATE_TIME)
+        }),
+        #[cfg(feature = "with-time")]
+        "TimeDateTimeWithTimeZone" => parse!(time::OffsetDateTime, |s| {
+            time::OffsetDateTime::parse(s, TIME_DATE_TIME_WITH_TIME_ZONE)
+        }),
+        #[cfg(feature = "with-uuid")]
+        "Uuid" => parse!(uuid::Uuid, uuid::Uuid::parse_str),
+        #[cfg(feature = "with-rust_decimal")]
+        "Decimal" => parse!(rust_decimal::Decimal, str::parse::<rust_decimal::Decimal>),
+        _ => Err(invalid("value", json)),
+    }
+}
+
+#[cfg(feature = "with-chrono")]
+const CHRONO_DATE_TIME: &str = "%Y-%m-%dT%H:%M:%S%.f";
+#[cfg(feature = "with-time")]
+const TIME_DATE: &str = "%F";
+#[cfg(feature = "with-time")]
+const TIME_TIME: &str = "%T.%N";
+#[cfg(feature = "with-time")]
+const TIME_DATE_TIME: &str = "%F %T.%N";
+#[cfg(feature = "with-time")]
+const TIME_DATE_TIME_WITH_TIME_ZONE: &str = "%F %T.%N %z";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{tests_cfg::*, EntityTrait, MockDatabase};
+    use futures::TryStreamExt;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn tagged_values() -> Result<(), DbErr> {
+        let values: Vec<Value> = vec![
+            true.into(),
+            (-1i8).into(),
+            1u64.into(),
+            1.5f64.into(),
+            "apple".into(),
+            Value::String(None),
+            vec![1u8, 2].into(),
+            json!({ "fruit": "apple" }).into(),
+            #[cfg(feature = "with-chrono")]
+            chrono::NaiveDate::from_ymd_opt(2022, 1, 2)
+                .and_then(|date| date.and_hms_micro_opt(3, 4, 5, 6))
+                .unwrap()
+                .into(),
+            #[cfg(feature = "with-chrono")]
+            chrono::DateTime::parse_from_rfc3339("2022-01-02T03:04:05+08:00")
+                .unwrap()
+                .into(),
+            #[cfg(feature = "with-time")]
+            time::date!(2022 - 01 - 02)
+                .with_time(time::time!(03:04:05.123))
+                .assume_offset(time::offset!(+8))
+                .into(),
+            #[cfg(feature = "with-uuid")]
+            uuid::Uuid::new_v4().into(),
+            #[cfg(feature = "with-rust_decimal")]
+            rust_decimal::Decimal::new(314, 2).into(),
+        ];
+
+        assert_eq!(value_to_json(&1i32.into()), json!({ "Int": 1 }));
+        for value in values {
+            assert_eq!(json_to_value(&value_to_json(&value))?, value);
+        }
+        assert_eq!(
+            json_to_value(&json!({ "Int": "one" })),
+            Err(DbErr::Json(
+                "Invalid `value` in the recording: {\"Int\":\"one\"}".to_owned()
+            ))
+        );
+
+        Ok(())
+    }
+
+    #[smol_potat::test]
+    async fn record_and_replay() -> Result<(), DbErr> {
+        let db = MockDatabase::new(DbBackend::Postgres)
+            .append_query_results(vec![vec![cake::Model {
+                id: 1,
+                name: "New York Cheese".to_owned(),
+            }]])
+            .append_exec_results(vec![MockExecResult {
+                last_insert_id: 0,
+                rows_affected: 1,
+            }])
+            .append_exec_errors(vec![DbErr::Driver(Box::new(DriverErr {
+                kind: Some(DriverErrKind::ForeignKeyViolation),
+                code: Some("23503".to_owned()),
+                message: "violates foreign key constraint".to_owned(),
+                constraint: Some("fk-fruit-cake".to_owned()),
+                ..Default::default()
+            }))])
+            .append_query_errors(vec![DbErr::Query("relation does not exist".to_owned())])
+            .into_connection();
+
+        let recorder = RecordingConnection::new(db);
+        let cake = cake::Entity::find_by_id(1).one(&recorder).await?;
+        cake::Entity::delete_by_id(2).exec(&recorder).await?;
+        let driver_err = cake::Entity::delete_by_id(1)
+            .exec(&recorder)
+            .await
+            .unwrap_err();
+        let err = fruit::Entity::find().all(&recorder).await.unwrap_err();
+
+        let dir = std::env::temp_dir().join(format!("sea-orm-recording-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("cake.json");
+        recorder.save(&path)?;
+        let recording = Recording::load(&path)?;
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(recording.to_json(), recorder.recording().to_json());
+
+        let db = recording.clone().into_mock_database().into_connection();
+        assert_eq!(cake::Entity::find_by_id(1).one(&db).await?, cake);
+        assert_eq!(
+            cake::Entity::delete_by_id(2).exec(&db).await?.rows_affected,
+            1
+        );
+        let replayed_err = cake::Entity::delete_by_id(1).exec(&db).await.unwrap_err();
+        assert_eq!(replayed_err, driver_err);
+        assert_eq!(
+            replayed_err.kind(),
+            Some(DriverErrKind::ForeignKeyViolation)
+        );
+        assert_eq!(fruit::Entity::find().all(&db).await.unwrap_err(), err);
+        db.verify()?;
+
+        let db = recording.into_mock_database().into_connection();
+        assert_eq!(
+            cake::Entity::find_by_id(3).one(&db).await,
+            Err(DbErr::Query(
+                [
+                    r#"No expectation matches the query:"#,
+                    r#"  SELECT "cake"."id", "cake"."name" FROM "cake" WHERE "cake"."id" = 3 LIMIT 1"#,
+                    r#"The closest expectation is the query matching the statement `SELECT "cake"."id", "cake"."name" FROM "cake" WHERE "cake"."id" = 1 LIMIT 1`, expected 1 time(s), matched 0:"#,
+                    r#"  SELECT "cake"."id", "cake"."name" FROM "cake" WHERE "cake"."id" = 1 LIMIT 1"#,
+                    r#"Difference ([-expected-] {+actual+}):"#,
+                    r#"  SELECT "cake"."id", "cake"."name" FROM "cake" WHERE "cake"."id" = [-1-] {+3+} LIMIT 1"#,
+                ]
+                .join("\n")
+            ))
+        );
+        assert!(db.verify().is_err());
+
+        Ok(())
+    }
+
+    #[smol_potat::test]
+    async fn record_and_replay_transaction() -> Result<(), DbErr> {
+        let db = MockDatabase::new(DbBackend::Postgres)
+            .append_query_results(vec![
+                vec![cake::Model {
+                    id: 1,
+                    name: "New York Cheese".to_owned(),
+                }],
+                vec![cake::Model {
+                    id: 2,
+                    name: "Chocolate Forest".to_owned(),
+                }],
+            ])
+            .append_exec_results(vec![MockExecResult {
+                last_insert_id: 0,
+                rows_affected: 1,
+            }])
+            .append_commit_results(vec![Err(DbErr::Query(
+                "could not serialize access".to_owned(),
+            ))])
+            .into_connection();
+
+        let recorder = RecordingConnection::new(db);
+        let txn = recorder.begin().await?;
+        let cake = cake::Entity::find_by_id(1).one(&txn).await?;
+        txn.savepoint("delete").await?;
+        cake::Entity::delete_by_id(1).exec(&txn).await?;
+        let commit_err = txn.commit().await.unwrap_err();
+        let cakes: Vec<cake::Model> = cake::Entity::find()
+            .stream(&recorder)
+            .await?
+            .try_collect()
+            .await?;
+
+        let recording = Recording::from_json(&recorder.recording().to_json())?;
+        assert_eq!(recording.to_json(), recorder.recording().to_json());
+        assert_eq!(
+            recording
+                .statements
+                .iter()
+                .map(|recorded| recorded.statement.sql.as_str())
+                .collect::<Vec<_>>(),
+            vec![
+                "BEGIN",
+                r#"SELECT "cake"."id", "cake"."name" FROM "cake" WHERE "cake"."id" = $1 LIMIT $2"#,
+                r#"SAVEPOINT "delete""#,
+                r#"DELETE FROM "cake" WHERE "cake"."id" = $1"#,
+                "COMMIT",
+                r#"SELECT "cake"."id", "cake"."name" FROM "cake""#,
+            ]
+        );
+
+        let db = recording.into_mock_database().into_connection();
+        let txn = db.begin().await?;
+        assert_eq!(cake::Entity::find_by_id(1).one(&txn).await?, cake);
+        txn.savepoint("delete").await?;
+        assert_eq!(
+            cake::Entity::delete_by_id(1).exec(&txn).await?.rows_affected,
+            1
+        );
+        assert_eq!(txn.commit().await.unwrap_err(), commit_err);
+        let replayed: Vec<cake::Model> = cake::Entity::find()
+            .stream(&db)
+            .await?
+            .try_collect()
+            .await?;
+        assert_eq!(replayed, cakes);
+        db.verify()?;
+
+        Ok(())
+    }
+}
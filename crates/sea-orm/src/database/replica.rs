@@ -0,0 +1,357 @@
+use crate::{ConnectionTrait, DatabaseConnection, Statement};
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
+/// Keywords that make a `SELECT` or `WITH` statement write, such as a data modifying CTE or
+/// `SELECT ... INTO`
+const WRITE_KEYWORDS: [&str; 6] = ["INSERT", "UPDATE", "DELETE", "MERGE", "INTO", "CALL"];
+
+/// Locking clauses that make a `SELECT` statement run on the primary, such as `FOR UPDATE`,
+/// `FOR NO KEY UPDATE`, `FOR SHARE`, `FOR KEY SHARE` and `LOCK IN SHARE MODE`
+const LOCKING_CLAUSES: [[&str; 2]; 4] = [
+    ["FOR", "UPDATE"],
+    ["FOR", "NO"],
+    ["FOR", "SHARE"],
+    ["FOR", "KEY"],
+];
+const LOCK_IN_SHARE_MODE: [&str; 2] = ["LOCK", "IN"];
+
+/// Built-in functions that make a `SELECT` statement run on the primary, as they write,
+/// take locks or read the state of the session, such as `nextval`, `GET_LOCK` and `LAST_INSERT_ID`
+const PRIMARY_FUNCTIONS: [&str; 10] = [
+    "NEXTVAL",
+    "SETVAL",
+    "CURRVAL",
+    "LASTVAL",
+    "SET_CONFIG",
+    "PG_NOTIFY",
+    "GET_LOCK",
+    "RELEASE_LOCK",
+    "RELEASE_ALL_LOCKS",
+    "LAST_INSERT_ID",
+];
+/// The prefixes of the advisory lock functions of Postgres, e.g. `pg_advisory_lock` and
+/// `pg_try_advisory_xact_lock_shared`
+const ADVISORY_LOCK_PREFIXES: [&str; 2] = ["PG_ADVISORY_", "PG_TRY_ADVISORY_"];
+
+/// How a replica is selected for a read only query
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReplicaSelection {
+    /// Take turns among the replicas
+    RoundRobin,
+    /// Pick the replica with the fewest connections in use
+    LeastBusy,
+}
+
+impl Default for ReplicaSelection {
+    fn default() -> Self {
+        Self::RoundRobin
+    }
+}
+
+/// A primary database with read replicas.
+///
+/// Outside transactions, `SELECT` statements without a locking clause are sent to a replica.
+/// Everything else, including transactions, is sent to the primary, as are the `SELECT`
+/// statements calling a built-in function with side effects, such as `nextval`,
+/// `pg_advisory_lock` or `GET_LOCK`. A user-defined function with side effects is not detected,
+/// a statement calling one has to be executed on [DatabaseConnection::on_primary].
+#[cfg_attr(not(feature = "mock"), derive(Clone))]
+pub struct ReplicatedConnection {
+    primary: DatabaseConnection,
+    replicas: Vec<DatabaseConnection>,
+    selection: ReplicaSelection,
+    next: Arc<AtomicUsize>,
+}
+
+impl std::fmt::Debug for ReplicatedConnection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ReplicatedConnection")
+            .field("primary", &self.primary)
+            .field("replicas", &self.replicas)
+            .field("selection", &self.selection)
+            .finish()
+    }
+}
+
+impl ReplicatedConnection {
+    /// Create a [DatabaseConnection] from a primary connection and its replica connections
+    pub fn connect(
+        primary: DatabaseConnection,
+        replicas: Vec<DatabaseConnection>,
+        selection: ReplicaSelection,
+    ) -> DatabaseConnection {
+        DatabaseConnection::ReplicatedConnection(Box::new(Self {
+            primary,
+            replicas,
+            selection,
+            next: Arc::new(AtomicUsize::new(0)),
+        }))
+    }
+
+    /// Get the connection to the primary database
+    pub fn primary(&self) -> &DatabaseConnection {
+        &self.primary
+    }
+
+    /// Get the connections to the replica databases
+    pub fn replicas(&self) -> &[DatabaseConnection] {
+        &self.replicas
+    }
+
+    /// Get the primary and the replica connections
+    pub(crate) fn connections(&self) -> impl Iterator<Item = &DatabaseConnection> {
+        std::iter::once(&self.primary).chain(self.replicas.iter())
+    }
+
+    /// Get the primary and the replica connections
+    pub(crate) fn connections_mut(&mut self) -> impl Iterator<Item = &mut DatabaseConnection> {
+        std::iter::once(&mut self.primary).chain(self.replicas.iter_mut())
+    }
+
+    /// Get the connection a statement should be executed on
+    pub(crate) fn route(&self, stmt: &Statement) -> &DatabaseConnection {
+        if self.replicas.is_empty() || !is_read_only(stmt) {
+            return &self.primary;
+        }
+        let next = self.next.fetch_add(1, Ordering::Relaxed);
+        let len = self.replicas.len();
+        match self.selection {
+            ReplicaSelection::RoundRobin => &self.replicas[next % len],
+            // start from the next replica in turn, so that idle replicas are used evenly
+            ReplicaSelection::LeastBusy => (0..len)
+                .map(|i| &self.replicas[(next + i) % len])
+                .min_by_key(|replica| replica.pool_stats().in_use)
+                .unwrap(),
+        }
+    }
+}
+
+/// A statement is read only if its leading keyword is `SELECT` or `WITH` and it neither writes,
+/// takes locks nor calls a function with side effects
+fn is_read_only(stmt: &Statement) -> bool {
+    let keywords = keywords(&stmt.sql);
+    if !matches!(
+        keywords.first().map(String::as_str),
+        Some("SELECT" | "WITH")
+    ) {
+        return false;
+    }
+    let writes = keywords.iter().any(|keyword| {
+        WRITE_KEYWORDS.contains(&keyword.as_str())
+            || PRIMARY_FUNCTIONS.contains(&keyword.as_str())
+            || ADVISORY_LOCK_PREFIXES
+                .iter()
+                .any(|prefix| keyword.starts_with(prefix))
+    });
+    let locks = keywords.windows(2).any(|pair| {
+        let pair = [pair[0].as_str(), pair[1].as_str()];
+        LOCKING_CLAUSES.contains(&pair) || pair == LOCK_IN_SHARE_MODE
+    });
+    !writes && !locks
+}
+
+/// The upper cased words of a SQL statement, skipping string literals, quoted identifiers and
+/// comments
+fn keywords(sql: &str) -> Vec<String> {
+    let mut keywords = Vec::new();
+    let mut chars = sql.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' | '"' | '`' => {
+                // a doubled quote is an escaped quote, which reads as two adjacent literals
+                for next in chars.by_ref() {
+                    if next == c {
+                        break;
+                    }
+                }
+            }
+            '-' if chars.peek() == Some(&'-') => {
+                for next in chars.by_ref() {
+                    if next == '\n' {
+                        break;
+                    }
+                }
+            }
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut prev = ' ';
+                for next in chars.by_ref() {
+                    if prev == '*' && next == '/' {
+                        break;
+                    }
+                    prev = next;
+                }
+            }
+            c if c.is_ascii_alphabetic() || c == '_' => {
+                let mut keyword = c.to_ascii_uppercase().to_string();
+                while let Some(&next) = chars.peek() {
+                    if !(next.is_ascii_alphanumeric() || next == '_' || next == '$') {
+                        break;
+                    }
+                    keyword.push(next.to_ascii_uppercase());
+                    chars.next();
+                }
+                keywords.push(keyword);
+            }
+            _ => {}
+        }
+    }
+    keywords
+}
+
+#[cfg(test)]
+#[cfg(feature = "mock")]
+mod tests {
+    use super::*;
+    use crate::{
+        entity::*, tests_cfg::*, DbBackend, DbErr, MockDatabase, MockExecResult, QuerySelect,
+        Transaction, TransactionTrait,
+    };
+    use pretty_assertions::assert_eq;
+
+    fn mock_db(query_results: usize) -> DatabaseConnection {
+        MockDatabase::new(DbBackend::Postgres)
+            .append_query_results(vec![Vec::<cake::Model>::new(); query_results])
+            .append_exec_results(vec![MockExecResult {
+                last_insert_id: 1,
+                rows_affected: 1,
+            }])
+            .into_connection()
+    }
+
+    fn transaction_log(db: &DatabaseConnection) -> Vec<Transaction> {
+        db.as_mock_connection()
+            .get_mocker_mutex()
+            .lock()
+            .unwrap()
+            .drain_transaction_log()
+    }
+
+    fn select_cake(lock: &str) -> Transaction {
+        Transaction::from_sql_and_values(
+            DbBackend::Postgres,
+            &format!(r#"SELECT "cake"."id", "cake"."name" FROM "cake"{}"#, lock),
+            vec![],
+        )
+    }
+
+    #[smol_potat::test]
+    async fn replica_1() -> Result<(), DbErr> {
+        let db = ReplicatedConnection::connect(
+            mock_db(4),
+            vec![mock_db(1), mock_db(1)],
+            ReplicaSelection::RoundRobin,
+        );
+
+        cake::Entity::find().all(&db).await?;
+        cake::Entity::find().all(&db).await?;
+        cake::Entity::find().all(db.on_primary()).await?;
+        cake::Entity::find().lock_exclusive().all(&db).await?;
+        cake::Entity::delete_many().exec(&db).await?;
+
+        let txn = db.begin().await?;
+        cake::Entity::find().all(&txn).await?;
+        txn.commit().await?;
+
+        let conn = match &db {
+            DatabaseConnection::ReplicatedConnection(conn) => conn,
+            _ => unreachable!(),
+        };
+        assert_eq!(
+            transaction_log(conn.primary()),
+            vec![
+                select_cake(""),
+                select_cake(" FOR UPDATE"),
+                Transaction::from_sql_and_values(
+                    DbBackend::Postgres,
+                    r#"DELETE FROM "cake""#,
+                    vec![]
+                ),
+                Transaction::many(vec![
+                    crate::Statement::from_string(DbBackend::Postgres, "BEGIN".to_owned()),
+                    crate::Statement::from_sql_and_values(
+                        DbBackend::Postgres,
+                        r#"SELECT "cake"."id", "cake"."name" FROM "cake""#,
+                        vec![]
+                    ),
+                    crate::Statement::from_string(DbBackend::Postgres, "COMMIT".to_owned()),
+                ]),
+            ]
+        );
+        assert_eq!(transaction_log(&conn.replicas()[0]), vec![select_cake("")]);
+        assert_eq!(transaction_log(&conn.replicas()[1]), vec![select_cake("")]);
+
+        Ok(())
+    }
+
+    #[smol_potat::test]
+    async fn replica_2() -> Result<(), DbErr> {
+        let db = ReplicatedConnection::connect(
+            mock_db(0),
+            vec![mock_db(2), mock_db(1)],
+            ReplicaSelection::LeastBusy,
+        );
+
+        for _ in 0..3 {
+            cake::Entity::find().all(&db).await?;
+        }
+
+        let conn = match &db {
+            DatabaseConnection::ReplicatedConnection(conn) => conn,
+            _ => unreachable!(),
+        };
+        assert_eq!(transaction_log(conn.primary()), vec![]);
+        assert_eq!(
+            transaction_log(&conn.replicas()[0]),
+            vec![select_cake(""), select_cake("")]
+        );
+        assert_eq!(transaction_log(&conn.replicas()[1]), vec![select_cake("")]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn replica_3() {
+        let stmt = |sql: &str| Statement::from_string(DbBackend::MySql, sql.to_owned());
+
+        assert!(is_read_only(&stmt("SELECT 1")));
+        assert!(is_read_only(&stmt("  select * from `cake`")));
+        assert!(!is_read_only(&stmt(
+            "SELECT * FROM `cake` LOCK IN SHARE MODE"
+        )));
+        assert!(!is_read_only(&stmt("SELECT * FROM `cake` for share")));
+        assert!(!is_read_only(&stmt(
+            "INSERT INTO `cake` (`name`) VALUES ('SELECT')"
+        )));
+        assert!(!is_read_only(&stmt(
+            r#"UPDATE "cake" SET "name" = $1 RETURNING "id""#
+        )));
+        assert!(!is_read_only(&stmt("SELECT * FROM `cake`\nFOR\tUPDATE")));
+        assert!(!is_read_only(&stmt(
+            r#"SELECT * FROM "cake" FOR NO KEY UPDATE SKIP LOCKED"#
+        )));
+        assert!(!is_read_only(&stmt(
+            r#"WITH "deleted" AS (DELETE FROM "cake" RETURNING *) SELECT * FROM "deleted""#
+        )));
+        assert!(!is_read_only(&stmt(
+            r#"SELECT * INTO "cake_backup" FROM "cake""#
+        )));
+        assert!(!is_read_only(&stmt("SELECTED")));
+        assert!(!is_read_only(&stmt(r#"SELECT nextval('"cake_id_seq"')"#)));
+        assert!(!is_read_only(&stmt("SELECT pg_try_advisory_xact_lock($1)")));
+        assert!(!is_read_only(&stmt("SELECT GET_LOCK('cake', 10)")));
+        assert!(!is_read_only(&stmt("SELECT LAST_INSERT_ID()")));
+        assert!(is_read_only(&stmt(
+            r#"WITH "c" AS (SELECT * FROM "cake") SELECT * FROM "c""#
+        )));
+        assert!(is_read_only(&stmt(
+            "/* FOR UPDATE */ SELECT 'FOR UPDATE', `update` FROM `cake` -- DELETE"
+        )));
+        assert!(is_read_only(&stmt(
+            "SELECT * FROM `cake` WHERE `name` = 'it''s for update'"
+        )));
+    }
+}
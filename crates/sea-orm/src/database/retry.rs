@@ -0,0 +1,220 @@
+use crate::{DbErr, TransactionError};
+use std::time::Duration;
+
+/// Defines how many times and how often a transaction is retried,
+/// see [TransactionTrait::transaction_with_retry](crate::TransactionTrait::transaction_with_retry)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(10),
+            max_backoff: Duration::from_secs(1),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Run the transaction at most `max_attempts` times, including the first attempt
+    pub fn new(max_attempts: u32) -> Self {
+        Self {
+            max_attempts,
+            ..Default::default()
+        }
+    }
+
+    /// Wait `initial` before the first retry, then double the wait before each subsequent retry,
+    /// up to `max`
+    pub fn backoff(mut self, initial: Duration, max: Duration) -> Self {
+        self.initial_backoff = initial;
+        self.max_backoff = max;
+        self
+    }
+
+    /// Get the maximum number of attempts
+    pub fn get_max_attempts(&self) -> u32 {
+        self.max_attempts
+    }
+
+    /// Get the wait before retrying after the given failed attempt, starting from 1
+    pub fn get_backoff(&self, attempt: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+        self.initial_backoff
+            .checked_mul(factor)
+            .map_or(self.max_backoff, |backoff| backoff.min(self.max_backoff))
+    }
+}
+
+impl<E> TransactionError<E>
+where
+    E: std::error::Error + 'static,
+{
+    /// Check if the transaction failed because of a concurrent transaction and can be retried.
+    /// An error returned by the callback is retryable if it is, or is caused by, a retryable [DbErr]
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            TransactionError::Connection(err) => err.is_retryable(),
+            TransactionError::Transaction(err) => {
+                let mut source: Option<&(dyn std::error::Error + 'static)> = Some(err);
+                while let Some(err) = source {
+                    if let Some(err) = err.downcast_ref::<DbErr>() {
+                        return err.is_retryable();
+                    }
+                    source = err.source();
+                }
+                false
+            }
+        }
+    }
+}
+
+/// Wait with the timer of the async runtime selected by the `runtime-*` feature.
+/// Without a runtime feature, e.g. with only the mock database, the wait is done in a separate thread
+pub(crate) async fn sleep(duration: Duration) {
+    if duration.is_zero() {
+        return;
+    }
+    #[cfg(any(feature = "runtime-tokio", feature = "runtime-actix"))]
+    tokio::time::sleep(duration).await;
+    #[cfg(all(
+        feature = "runtime-async-std",
+        not(any(feature = "runtime-tokio", feature = "runtime-actix"))
+    ))]
+    async_std::task::sleep(duration).await;
+    #[cfg(not(any(
+        feature = "runtime-tokio",
+        feature = "runtime-actix",
+        feature = "runtime-async-std"
+    )))]
+    {
+        let (sender, receiver) = futures::channel::oneshot::channel();
+        std::thread::spawn(move || {
+            std::thread::sleep(duration);
+            let _ = sender.send(());
+        });
+        let _ = receiver.await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn retry_policy_backoff() {
+        let policy =
+            RetryPolicy::new(5).backoff(Duration::from_millis(100), Duration::from_secs(1));
+
+        assert_eq!(policy.get_max_attempts(), 5);
+        assert_eq!(policy.get_backoff(1), Duration::from_millis(100));
+        assert_eq!(policy.get_backoff(2), Duration::from_millis(200));
+        assert_eq!(policy.get_backoff(4), Duration::from_millis(800));
+        assert_eq!(policy.get_backoff(5), Duration::from_secs(1));
+        assert_eq!(policy.get_backoff(100), Duration::from_secs(1));
+    }
+
+    #[smol_potat::test]
+    #[cfg(feature = "mock")]
+    async fn transaction_with_retry() {
+        use crate::{
+            DbBackend, DriverErr, DriverErrKind, IsolationLevel, MockDatabase, Statement,
+            Transaction, TransactionError, TransactionTrait,
+        };
+        use pretty_assertions::assert_eq;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let deadlock = || {
+            DbErr::Driver(Box::new(DriverErr {
+                kind: Some(DriverErrKind::Deadlock),
+                message: "deadlock detected".to_owned(),
+                ..Default::default()
+            }))
+        };
+        let db = MockDatabase::new(DbBackend::Postgres).into_connection();
+        let policy = RetryPolicy::new(3).backoff(Duration::ZERO, Duration::ZERO);
+
+        let attempts = AtomicUsize::new(0);
+        let res = db
+            .transaction_with_retry(
+                policy,
+                |_| {
+                    let attempt = attempts.fetch_add(1, Ordering::SeqCst) + 1;
+                    Box::pin(async move {
+                        if attempt < 2 {
+                            Err(deadlock())
+                        } else {
+                            Ok(attempt)
+                        }
+                    })
+                },
+                Some(IsolationLevel::Serializable),
+                None,
+            )
+            .await;
+        assert_eq!(res.unwrap(), 2);
+
+        attempts.store(0, Ordering::SeqCst);
+        let res = db
+            .transaction_with_retry::<_, (), _>(
+                policy,
+                |_| {
+                    attempts.fetch_add(1, Ordering::SeqCst);
+                    Box::pin(async move { Err(deadlock()) })
+                },
+                None,
+                None,
+            )
+            .await;
+        assert!(matches!(
+            res,
+            Err(TransactionError::Transaction(DbErr::Driver(_)))
+        ));
+        assert_eq!(attempts.load(Ordering::SeqCst), 3);
+
+        attempts.store(0, Ordering::SeqCst);
+        let res = db
+            .transaction_with_retry::<_, (), _>(
+                policy,
+                |_| {
+                    attempts.fetch_add(1, Ordering::SeqCst);
+                    Box::pin(async move { Err(DbErr::Exec("syntax error".to_owned())) })
+                },
+                None,
+                None,
+            )
+            .await;
+        assert!(matches!(
+            res,
+            Err(TransactionError::Transaction(DbErr::Exec(_)))
+        ));
+        assert_eq!(attempts.load(Ordering::SeqCst), 1);
+
+        let begin = || Statement::from_string(DbBackend::Postgres, "BEGIN".to_owned());
+        let end = |sql: &str| Statement::from_string(DbBackend::Postgres, sql.to_owned());
+        let rollback = || Transaction::many(vec![begin(), end("ROLLBACK")]);
+        let serializable = |sql: &str| {
+            Transaction::many(vec![
+                begin(),
+                end("SET TRANSACTION ISOLATION LEVEL SERIALIZABLE"),
+                end(sql),
+            ])
+        };
+        assert_eq!(
+            db.into_transaction_log(),
+            vec![
+                serializable("ROLLBACK"),
+                serializable("COMMIT"),
+                rollback(),
+                rollback(),
+                rollback(),
+                rollback(),
+            ]
+        );
+    }
+}
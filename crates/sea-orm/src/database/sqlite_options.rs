@@ -0,0 +1,44 @@
+use std::time::Duration;
+
+/// The journal mode of a SQLite database, see <https://www.sqlite.org/pragma.html#pragma_journal_mode>
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SqliteJournalMode {
+    /// The rollback journal is deleted at the end of each transaction
+    Delete,
+    /// The rollback journal is truncated to zero length at the end of each transaction
+    Truncate,
+    /// The header of the rollback journal is overwritten at the end of each transaction
+    Persist,
+    /// The rollback journal is kept in memory
+    Memory,
+    /// A write-ahead log, readers do not block the writer and the writer does not block readers
+    Wal,
+    /// No rollback journal, transactions can not be rolled back safely
+    Off,
+}
+
+/// How often SQLite syncs the database file to disk, see <https://www.sqlite.org/pragma.html#pragma_synchronous>
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SqliteSynchronous {
+    /// No sync, the database may be corrupted by a power loss
+    Off,
+    /// Sync at critical moments, safe from corruption in WAL mode but a commit may be lost on power loss
+    Normal,
+    /// Sync at every commit
+    Full,
+    /// Like [SqliteSynchronous::Full], also syncing the directory of the rollback journal
+    Extra,
+}
+
+/// The SQLite settings of [ConnectOptions](crate::ConnectOptions)
+#[derive(Debug, Clone, Default)]
+pub(crate) struct SqliteOptions {
+    pub(crate) journal_mode: Option<SqliteJournalMode>,
+    pub(crate) synchronous: Option<SqliteSynchronous>,
+    pub(crate) busy_timeout: Option<Duration>,
+    pub(crate) foreign_keys: Option<bool>,
+    pub(crate) create_if_missing: Option<bool>,
+    pub(crate) shared_cache: Option<bool>,
+    pub(crate) cache_size: Option<i64>,
+    pub(crate) split_writer: bool,
+}
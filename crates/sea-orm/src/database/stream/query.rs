@@ -124,7 +124,7 @@ impl QueryStream {
                 #[cfg(feature = "sqlx-mysql")]
                 InnerConnection::MySql(c) => {
                     let query = crate::driver::sqlx_mysql::sqlx_query(stmt);
-                    crate::metric::metric_ok!(_metric_callback, stmt, {
+                    crate::metric::metric_ok!(_metric_callback, stmt, false, {
                         Box::pin(
                             c.fetch(query)
                                 .map_ok(Into::into)
@@ -135,7 +135,7 @@ impl QueryStream {
                 #[cfg(feature = "sqlx-postgres")]
                 InnerConnection::Postgres(c) => {
                     let query = crate::driver::sqlx_postgres::sqlx_query(stmt);
-                    crate::metric::metric_ok!(_metric_callback, stmt, {
+                    crate::metric::metric_ok!(_metric_callback, stmt, false, {
                         Box::pin(
                             c.fetch(query)
                                 .map_ok(Into::into)
@@ -146,7 +146,7 @@ impl QueryStream {
                 #[cfg(feature = "sqlx-sqlite")]
                 InnerConnection::Sqlite(c) => {
                     let query = crate::driver::sqlx_sqlite::sqlx_query(stmt);
-                    crate::metric::metric_ok!(_metric_callback, stmt, {
+                    crate::metric::metric_ok!(_metric_callback, stmt, false, {
                         Box::pin(
                             c.fetch(query)
                                 .map_ok(Into::into)
@@ -48,7 +48,7 @@ impl<'a> TransactionStream<'a> {
                 #[cfg(feature = "sqlx-mysql")]
                 InnerConnection::MySql(c) => {
                     let query = crate::driver::sqlx_mysql::sqlx_query(stmt);
-                    crate::metric::metric_ok!(_metric_callback, stmt, {
+                    crate::metric::metric_ok!(_metric_callback, stmt, true, {
                         Box::pin(
                             c.fetch(query)
                                 .map_ok(Into::into)
@@ -60,7 +60,7 @@ impl<'a> TransactionStream<'a> {
                 #[cfg(feature = "sqlx-postgres")]
                 InnerConnection::Postgres(c) => {
                     let query = crate::driver::sqlx_postgres::sqlx_query(stmt);
-                    crate::metric::metric_ok!(_metric_callback, stmt, {
+                    crate::metric::metric_ok!(_metric_callback, stmt, true, {
                         Box::pin(
                             c.fetch(query)
                                 .map_ok(Into::into)
@@ -72,7 +72,7 @@ impl<'a> TransactionStream<'a> {
                 #[cfg(feature = "sqlx-sqlite")]
                 InnerConnection::Sqlite(c) => {
                     let query = crate::driver::sqlx_sqlite::sqlx_query(stmt);
-                    crate::metric::metric_ok!(_metric_callback, stmt, {
+                    crate::metric::metric_ok!(_metric_callback, stmt, true, {
                         Box::pin(
                             c.fetch(query)
                                 .map_ok(Into::into)
@@ -89,6 +89,17 @@ impl<'a> TransactionStream<'a> {
         }
         .build()
     }
+
+    /// Record the statement and the streamed rows, see [RecordingConnection](crate::RecordingConnection)
+    #[cfg(all(feature = "mock", feature = "with-json"))]
+    pub(crate) fn record(mut self, recorder: &crate::database::mock_recording::Recorder) -> Self {
+        let stmt = self.borrow_stmt().clone();
+        self.with_stream_mut(|stream| {
+            let inner = std::mem::replace(stream, Box::pin(futures::stream::empty()));
+            *stream = Box::pin(recorder.stream(stmt, inner));
+        });
+        self
+    }
 }
 
 impl<'a> Stream for TransactionStream<'a> {
@@ -5,9 +5,10 @@ use crate::{
 #[cfg(feature = "sqlx-dep")]
 use crate::{sqlx_error_to_exec_err, sqlx_error_to_query_err};
 use futures::lock::Mutex;
+use sea_query::{Alias, Iden};
 #[cfg(feature = "sqlx-dep")]
 use sqlx::{pool::PoolConnection, TransactionManager};
-use std::{future::Future, pin::Pin, sync::Arc};
+use std::{future::Future, pin::Pin, sync::Arc, time::Duration};
 use tracing::instrument;
 
 // a Transaction is just a sugar for a connection where START TRANSACTION has been executed
@@ -18,6 +19,10 @@ pub struct DatabaseTransaction {
     backend: DbBackend,
     open: bool,
     metric_callback: Option<crate::metric::Callback>,
+    statement_timeout: Option<Duration>,
+    support_returning: bool,
+    #[cfg(all(feature = "mock", feature = "with-json"))]
+    recorder: Option<crate::database::mock_recording::Recorder>,
 }
 
 impl std::fmt::Debug for DatabaseTransaction {
@@ -31,11 +36,15 @@ impl DatabaseTransaction {
     pub(crate) async fn new_mysql(
         inner: PoolConnection<sqlx::MySql>,
         metric_callback: Option<crate::metric::Callback>,
+        isolation_level: Option<IsolationLevel>,
+        access_mode: Option<AccessMode>,
     ) -> Result<DatabaseTransaction, DbErr> {
         Self::begin(
             Arc::new(Mutex::new(InnerConnection::MySql(inner))),
             DbBackend::MySql,
             metric_callback,
+            isolation_level,
+            access_mode,
         )
         .await
     }
@@ -44,11 +53,15 @@ impl DatabaseTransaction {
     pub(crate) async fn new_postgres(
         inner: PoolConnection<sqlx::Postgres>,
         metric_callback: Option<crate::metric::Callback>,
+        isolation_level: Option<IsolationLevel>,
+        access_mode: Option<AccessMode>,
     ) -> Result<DatabaseTransaction, DbErr> {
         Self::begin(
             Arc::new(Mutex::new(InnerConnection::Postgres(inner))),
             DbBackend::Postgres,
             metric_callback,
+            isolation_level,
+            access_mode,
         )
         .await
     }
@@ -57,25 +70,38 @@ impl DatabaseTransaction {
     pub(crate) async fn new_sqlite(
         inner: PoolConnection<sqlx::Sqlite>,
         metric_callback: Option<crate::metric::Callback>,
+        statement_timeout: Option<Duration>,
+        isolation_level: Option<IsolationLevel>,
+        access_mode: Option<AccessMode>,
     ) -> Result<DatabaseTransaction, DbErr> {
-        Self::begin(
+        let mut transaction = Self::begin(
             Arc::new(Mutex::new(InnerConnection::Sqlite(inner))),
             DbBackend::Sqlite,
             metric_callback,
+            isolation_level,
+            access_mode,
         )
-        .await
+        .await?;
+        // SQLite has no statement timeout on the connection, it is applied by the transaction
+        transaction.statement_timeout = statement_timeout;
+        transaction.support_returning = crate::sqlite_support_returning();
+        Ok(transaction)
     }
 
     #[cfg(feature = "mock")]
     pub(crate) async fn new_mock(
         inner: Arc<crate::MockDatabaseConnection>,
         metric_callback: Option<crate::metric::Callback>,
+        isolation_level: Option<IsolationLevel>,
+        access_mode: Option<AccessMode>,
     ) -> Result<DatabaseTransaction, DbErr> {
         let backend = inner.get_database_backend();
         Self::begin(
             Arc::new(Mutex::new(InnerConnection::Mock(inner))),
             backend,
             metric_callback,
+            isolation_level,
+            access_mode,
         )
         .await
     }
@@ -85,16 +111,35 @@ impl DatabaseTransaction {
         conn: Arc<Mutex<InnerConnection>>,
         backend: DbBackend,
         metric_callback: Option<crate::metric::Callback>,
+        isolation_level: Option<IsolationLevel>,
+        access_mode: Option<AccessMode>,
     ) -> Result<DatabaseTransaction, DbErr> {
-        let res = DatabaseTransaction {
+        if backend == DbBackend::Sqlite && access_mode == Some(AccessMode::ReadOnly) {
+            return Err(DbErr::Exec(
+                "SQLite does not support read only transactions".to_owned(),
+            ));
+        }
+        // only opened once the transaction has begun, so that a failed begin is not rolled back
+        let mut res = DatabaseTransaction {
             conn,
             backend,
-            open: true,
+            open: false,
             metric_callback,
+            statement_timeout: None,
+            support_returning: backend.support_returning(),
+            #[cfg(all(feature = "mock", feature = "with-json"))]
+            recorder: None,
         };
         match *res.conn.lock().await {
             #[cfg(feature = "sqlx-mysql")]
             InnerConnection::MySql(ref mut c) => {
+                // in MySQL, the characteristics have to be set before the transaction starts
+                if let Some(sql) = set_transaction_config_sql(isolation_level, access_mode) {
+                    sqlx::query(&sql)
+                        .execute(&mut *c)
+                        .await
+                        .map_err(sqlx_error_to_exec_err)?;
+                }
                 <sqlx::MySql as sqlx::Database>::TransactionManager::begin(c)
                     .await
                     .map_err(sqlx_error_to_query_err)?
@@ -103,22 +148,62 @@ impl DatabaseTransaction {
             InnerConnection::Postgres(ref mut c) => {
                 <sqlx::Postgres as sqlx::Database>::TransactionManager::begin(c)
                     .await
-                    .map_err(sqlx_error_to_query_err)?
+                    .map_err(sqlx_error_to_query_err)?;
+                // in Postgres, the characteristics have to be set before any query of the transaction
+                if let Some(sql) = set_transaction_config_sql(isolation_level, access_mode) {
+                    if let Err(err) = sqlx::query(&sql).execute(&mut *c).await {
+                        // the transaction has begun, roll it back before reporting the error
+                        <sqlx::Postgres as sqlx::Database>::TransactionManager::rollback(c)
+                            .await
+                            .map_err(sqlx_error_to_query_err)?;
+                        return Err(sqlx_error_to_exec_err(err));
+                    }
+                }
             }
             #[cfg(feature = "sqlx-sqlite")]
             InnerConnection::Sqlite(ref mut c) => {
                 <sqlx::Sqlite as sqlx::Database>::TransactionManager::begin(c)
                     .await
-                    .map_err(sqlx_error_to_query_err)?
+                    .map_err(sqlx_error_to_query_err)?;
+                // sqlx always starts a deferred transaction and keeps track of its depth,
+                // so the empty deferred transaction is replaced with a locking one
+                if let Some(sql) = sqlite_begin_sql(isolation_level, access_mode) {
+                    let locking_begin = async {
+                        sqlx::query("COMMIT").execute(&mut *c).await?;
+                        if let Err(err) = sqlx::query(sql).execute(&mut *c).await {
+                            // a deferred transaction takes no lock, restart it for sqlx to roll it back
+                            sqlx::query("BEGIN").execute(&mut *c).await?;
+                            return Err(err);
+                        }
+                        Ok(())
+                    };
+                    if let Err(err) = locking_begin.await {
+                        <sqlx::Sqlite as sqlx::Database>::TransactionManager::rollback(c)
+                            .await
+                            .map_err(sqlx_error_to_query_err)?;
+                        return Err(sqlx_error_to_exec_err(err));
+                    }
+                }
             }
             #[cfg(feature = "mock")]
             InnerConnection::Mock(ref mut c) => {
-                c.begin();
+                c.try_begin_with_config(isolation_level, access_mode)?;
             }
         }
+        res.open = true;
         Ok(res)
     }
 
+    /// Record the statements run in the transaction, see [RecordingConnection](crate::RecordingConnection)
+    #[cfg(all(feature = "mock", feature = "with-json"))]
+    pub(crate) fn with_recorder(
+        mut self,
+        recorder: crate::database::mock_recording::Recorder,
+    ) -> Self {
+        self.recorder = Some(recorder);
+        self
+    }
+
     /// Runs a transaction to completion returning an rolling back the transaction on
     /// encountering an error if it fails
     #[instrument(level = "trace", skip(callback))]
@@ -146,62 +231,122 @@ impl DatabaseTransaction {
     #[instrument(level = "trace")]
     pub async fn commit(mut self) -> Result<(), DbErr> {
         self.open = false;
-        match *self.conn.lock().await {
+        let res = match *self.conn.lock().await {
             #[cfg(feature = "sqlx-mysql")]
             InnerConnection::MySql(ref mut c) => {
                 <sqlx::MySql as sqlx::Database>::TransactionManager::commit(c)
                     .await
-                    .map_err(sqlx_error_to_query_err)?
+                    .map_err(sqlx_error_to_query_err)
             }
             #[cfg(feature = "sqlx-postgres")]
             InnerConnection::Postgres(ref mut c) => {
                 <sqlx::Postgres as sqlx::Database>::TransactionManager::commit(c)
                     .await
-                    .map_err(sqlx_error_to_query_err)?
+                    .map_err(sqlx_error_to_query_err)
             }
             #[cfg(feature = "sqlx-sqlite")]
             InnerConnection::Sqlite(ref mut c) => {
                 <sqlx::Sqlite as sqlx::Database>::TransactionManager::commit(c)
                     .await
-                    .map_err(sqlx_error_to_query_err)?
+                    .map_err(sqlx_error_to_query_err)
             }
             #[cfg(feature = "mock")]
-            InnerConnection::Mock(ref mut c) => {
-                c.commit();
-            }
+            InnerConnection::Mock(ref mut c) => c.try_commit(),
+        };
+        #[cfg(all(feature = "mock", feature = "with-json"))]
+        if let Some(recorder) = &self.recorder {
+            recorder.transaction("COMMIT", &res);
         }
-        Ok(())
+        res
     }
 
     /// rolls back a transaction in case error are encountered during the operation
     #[instrument(level = "trace")]
     pub async fn rollback(mut self) -> Result<(), DbErr> {
         self.open = false;
-        match *self.conn.lock().await {
+        let res = match *self.conn.lock().await {
             #[cfg(feature = "sqlx-mysql")]
             InnerConnection::MySql(ref mut c) => {
                 <sqlx::MySql as sqlx::Database>::TransactionManager::rollback(c)
                     .await
-                    .map_err(sqlx_error_to_query_err)?
+                    .map_err(sqlx_error_to_query_err)
             }
             #[cfg(feature = "sqlx-postgres")]
             InnerConnection::Postgres(ref mut c) => {
                 <sqlx::Postgres as sqlx::Database>::TransactionManager::rollback(c)
                     .await
-                    .map_err(sqlx_error_to_query_err)?
+                    .map_err(sqlx_error_to_query_err)
             }
             #[cfg(feature = "sqlx-sqlite")]
             InnerConnection::Sqlite(ref mut c) => {
                 <sqlx::Sqlite as sqlx::Database>::TransactionManager::rollback(c)
                     .await
-                    .map_err(sqlx_error_to_query_err)?
+                    .map_err(sqlx_error_to_query_err)
             }
             #[cfg(feature = "mock")]
-            InnerConnection::Mock(ref mut c) => {
-                c.rollback();
-            }
+            InnerConnection::Mock(ref mut c) => c.try_rollback(),
+        };
+        #[cfg(all(feature = "mock", feature = "with-json"))]
+        if let Some(recorder) = &self.recorder {
+            recorder.transaction("ROLLBACK", &res);
+        }
+        res
+    }
+
+    /// Create a named savepoint in the transaction
+    #[instrument(level = "trace")]
+    pub async fn savepoint(&self, name: &str) -> Result<(), DbErr> {
+        self.execute_savepoint(format!("SAVEPOINT {}", self.quote_savepoint(name)))
+            .await
+    }
+
+    /// Roll back the changes made after a named savepoint, the savepoint is kept
+    #[instrument(level = "trace")]
+    pub async fn rollback_to_savepoint(&self, name: &str) -> Result<(), DbErr> {
+        self.execute_savepoint(format!(
+            "ROLLBACK TO SAVEPOINT {}",
+            self.quote_savepoint(name)
+        ))
+        .await
+    }
+
+    /// Release a named savepoint, the changes made after it are kept
+    #[instrument(level = "trace")]
+    pub async fn release_savepoint(&self, name: &str) -> Result<(), DbErr> {
+        self.execute_savepoint(format!("RELEASE SAVEPOINT {}", self.quote_savepoint(name)))
+            .await
+    }
+
+    fn quote_savepoint(&self, name: &str) -> String {
+        let quote = match self.backend {
+            DbBackend::MySql => '`',
+            DbBackend::Postgres | DbBackend::Sqlite => '"',
+        };
+        let mut sql = String::new();
+        Alias::new(name).prepare(&mut sql, quote);
+        sql
+    }
+
+    async fn execute_savepoint(&self, sql: String) -> Result<(), DbErr> {
+        let stmt = Statement::from_string(self.backend, sql);
+        #[cfg(all(feature = "mock", feature = "with-json"))]
+        if let Some(recorder) = &self.recorder {
+            let sql = stmt.sql.clone();
+            let res = self.run_savepoint(stmt).await;
+            recorder.transaction(&sql, &res);
+            return res;
         }
-        Ok(())
+        self.run_savepoint(stmt).await
+    }
+
+    async fn run_savepoint(&self, stmt: Statement) -> Result<(), DbErr> {
+        #[cfg(feature = "mock")]
+        #[allow(irrefutable_let_patterns)]
+        if let InnerConnection::Mock(conn) = &*self.conn.lock().await {
+            conn.savepoint(stmt);
+            return Ok(());
+        }
+        self.execute_unrecorded(stmt, None).await.map(|_| ())
     }
 
     // the rollback is queued and will be performed on next async operation, like returning the connection to the pool
@@ -243,37 +388,50 @@ impl Drop for DatabaseTransaction {
     }
 }
 
-#[async_trait::async_trait]
-impl ConnectionTrait for DatabaseTransaction {
-    fn get_database_backend(&self) -> DbBackend {
-        // this way we don't need to lock
-        self.backend
-    }
-
-    #[instrument(level = "trace")]
-    async fn execute(&self, stmt: Statement) -> Result<ExecResult, DbErr> {
+// with the Mock connection only, the result of the drivers is a unit as the Mock returns early
+#[allow(clippy::let_unit_value)]
+impl DatabaseTransaction {
+    async fn execute_unrecorded(
+        &self,
+        stmt: Statement,
+        _timeout: Option<Duration>,
+    ) -> Result<ExecResult, DbErr> {
         debug_print!("{}", stmt);
 
         let _res = match &mut *self.conn.lock().await {
             #[cfg(feature = "sqlx-mysql")]
             InnerConnection::MySql(conn) => {
-                let query = crate::driver::sqlx_mysql::sqlx_query(&stmt);
-                crate::metric::metric!(self.metric_callback, &stmt, {
+                let hinted = crate::driver::sqlx_mysql::with_max_execution_time(&stmt, _timeout);
+                let query = crate::driver::sqlx_mysql::sqlx_query(&hinted);
+                crate::metric::metric!(self.metric_callback, &stmt, true, {
                     query.execute(conn).await.map(Into::into)
                 })
             }
             #[cfg(feature = "sqlx-postgres")]
             InnerConnection::Postgres(conn) => {
                 let query = crate::driver::sqlx_postgres::sqlx_query(&stmt);
-                crate::metric::metric!(self.metric_callback, &stmt, {
-                    query.execute(conn).await.map(Into::into)
+                crate::metric::metric!(self.metric_callback, &stmt, true, {
+                    crate::driver::sqlx_postgres::with_local_statement_timeout!(
+                        conn,
+                        _timeout,
+                        query.execute
+                    )
+                    .await
+                    .map(Into::into)
                 })
             }
             #[cfg(feature = "sqlx-sqlite")]
             InnerConnection::Sqlite(conn) => {
                 let query = crate::driver::sqlx_sqlite::sqlx_query(&stmt);
-                crate::metric::metric!(self.metric_callback, &stmt, {
-                    query.execute(conn).await.map(Into::into)
+                crate::metric::metric!(self.metric_callback, &stmt, true, {
+                    let interrupt = crate::driver::sqlx_sqlite::Interrupt::new(conn);
+                    crate::driver::sqlx_sqlite::with_interrupt(
+                        interrupt,
+                        _timeout.or(self.statement_timeout),
+                        query.execute(conn),
+                    )
+                    .await
+                    .map(Into::into)
                 })
             }
             #[cfg(feature = "mock")]
@@ -285,25 +443,50 @@ impl ConnectionTrait for DatabaseTransaction {
         _res.map_err(sqlx_error_to_exec_err)
     }
 
-    #[instrument(level = "trace")]
-    async fn query_one(&self, stmt: Statement) -> Result<Option<QueryResult>, DbErr> {
+    async fn query_one_unrecorded(
+        &self,
+        stmt: Statement,
+        _timeout: Option<Duration>,
+    ) -> Result<Option<QueryResult>, DbErr> {
         debug_print!("{}", stmt);
 
         let _res = match &mut *self.conn.lock().await {
             #[cfg(feature = "sqlx-mysql")]
             InnerConnection::MySql(conn) => {
-                let query = crate::driver::sqlx_mysql::sqlx_query(&stmt);
-                query.fetch_one(conn).await.map(|row| Some(row.into()))
+                let hinted = crate::driver::sqlx_mysql::with_max_execution_time(&stmt, _timeout);
+                let query = crate::driver::sqlx_mysql::sqlx_query(&hinted);
+                crate::metric::metric!(self.metric_callback, &stmt, true, {
+                    row_not_found_to_none(query.fetch_one(conn).await)
+                })
             }
             #[cfg(feature = "sqlx-postgres")]
             InnerConnection::Postgres(conn) => {
                 let query = crate::driver::sqlx_postgres::sqlx_query(&stmt);
-                query.fetch_one(conn).await.map(|row| Some(row.into()))
+                crate::metric::metric!(self.metric_callback, &stmt, true, {
+                    row_not_found_to_none(
+                        crate::driver::sqlx_postgres::with_local_statement_timeout!(
+                            conn,
+                            _timeout,
+                            query.fetch_one
+                        )
+                        .await,
+                    )
+                })
             }
             #[cfg(feature = "sqlx-sqlite")]
             InnerConnection::Sqlite(conn) => {
                 let query = crate::driver::sqlx_sqlite::sqlx_query(&stmt);
-                query.fetch_one(conn).await.map(|row| Some(row.into()))
+                crate::metric::metric!(self.metric_callback, &stmt, true, {
+                    let interrupt = crate::driver::sqlx_sqlite::Interrupt::new(conn);
+                    row_not_found_to_none(
+                        crate::driver::sqlx_sqlite::with_interrupt(
+                            interrupt,
+                            _timeout.or(self.statement_timeout),
+                            query.fetch_one(conn),
+                        )
+                        .await,
+                    )
+                })
             }
             #[cfg(feature = "mock")]
             InnerConnection::Mock(conn) => return conn.query_one(stmt),
@@ -311,41 +494,54 @@ impl ConnectionTrait for DatabaseTransaction {
             _ => unreachable!(),
         };
         #[cfg(feature = "sqlx-dep")]
-        if let Err(sqlx::Error::RowNotFound) = _res {
-            Ok(None)
-        } else {
-            _res.map_err(sqlx_error_to_query_err)
-        }
+        _res.map_err(sqlx_error_to_query_err)
     }
 
-    #[instrument(level = "trace")]
-    async fn query_all(&self, stmt: Statement) -> Result<Vec<QueryResult>, DbErr> {
+    async fn query_all_unrecorded(
+        &self,
+        stmt: Statement,
+        _timeout: Option<Duration>,
+    ) -> Result<Vec<QueryResult>, DbErr> {
         debug_print!("{}", stmt);
 
         let _res = match &mut *self.conn.lock().await {
             #[cfg(feature = "sqlx-mysql")]
             InnerConnection::MySql(conn) => {
-                let query = crate::driver::sqlx_mysql::sqlx_query(&stmt);
-                query
-                    .fetch_all(conn)
-                    .await
-                    .map(|rows| rows.into_iter().map(|r| r.into()).collect())
+                let hinted = crate::driver::sqlx_mysql::with_max_execution_time(&stmt, _timeout);
+                let query = crate::driver::sqlx_mysql::sqlx_query(&hinted);
+                crate::metric::metric!(self.metric_callback, &stmt, true, {
+                    query
+                        .fetch_all(conn)
+                        .await
+                        .map(|rows| rows.into_iter().map(|r| r.into()).collect())
+                })
             }
             #[cfg(feature = "sqlx-postgres")]
             InnerConnection::Postgres(conn) => {
                 let query = crate::driver::sqlx_postgres::sqlx_query(&stmt);
-                query
-                    .fetch_all(conn)
+                crate::metric::metric!(self.metric_callback, &stmt, true, {
+                    crate::driver::sqlx_postgres::with_local_statement_timeout!(
+                        conn,
+                        _timeout,
+                        query.fetch_all
+                    )
                     .await
                     .map(|rows| rows.into_iter().map(|r| r.into()).collect())
+                })
             }
             #[cfg(feature = "sqlx-sqlite")]
             InnerConnection::Sqlite(conn) => {
                 let query = crate::driver::sqlx_sqlite::sqlx_query(&stmt);
-                query
-                    .fetch_all(conn)
+                crate::metric::metric!(self.metric_callback, &stmt, true, {
+                    let interrupt = crate::driver::sqlx_sqlite::Interrupt::new(conn);
+                    crate::driver::sqlx_sqlite::with_interrupt(
+                        interrupt,
+                        _timeout.or(self.statement_timeout),
+                        query.fetch_all(conn),
+                    )
                     .await
                     .map(|rows| rows.into_iter().map(|r| r.into()).collect())
+                })
             }
             #[cfg(feature = "mock")]
             InnerConnection::Mock(conn) => return conn.query_all(stmt),
@@ -357,6 +553,91 @@ impl ConnectionTrait for DatabaseTransaction {
     }
 }
 
+#[async_trait::async_trait]
+impl ConnectionTrait for DatabaseTransaction {
+    fn get_database_backend(&self) -> DbBackend {
+        // this way we don't need to lock
+        self.backend
+    }
+
+    fn support_returning(&self) -> bool {
+        self.support_returning
+    }
+
+    #[instrument(level = "trace")]
+    async fn execute(&self, stmt: Statement) -> Result<ExecResult, DbErr> {
+        self.execute_with_timeout(stmt, None).await
+    }
+
+    #[instrument(level = "trace")]
+    async fn query_one(&self, stmt: Statement) -> Result<Option<QueryResult>, DbErr> {
+        self.query_one_with_timeout(stmt, None).await
+    }
+
+    #[instrument(level = "trace")]
+    async fn query_all(&self, stmt: Statement) -> Result<Vec<QueryResult>, DbErr> {
+        self.query_all_with_timeout(stmt, None).await
+    }
+
+    #[instrument(level = "trace")]
+    async fn execute_with_timeout(
+        &self,
+        stmt: Statement,
+        timeout: Option<Duration>,
+    ) -> Result<ExecResult, DbErr> {
+        #[cfg(all(feature = "mock", feature = "with-json"))]
+        if let Some(recorder) = &self.recorder {
+            let res = self.execute_unrecorded(stmt.clone(), timeout).await;
+            recorder.exec(stmt, &res);
+            return res;
+        }
+        self.execute_unrecorded(stmt, timeout).await
+    }
+
+    #[instrument(level = "trace")]
+    async fn query_one_with_timeout(
+        &self,
+        stmt: Statement,
+        timeout: Option<Duration>,
+    ) -> Result<Option<QueryResult>, DbErr> {
+        #[cfg(all(feature = "mock", feature = "with-json"))]
+        if let Some(recorder) = &self.recorder {
+            let res = self.query_one_unrecorded(stmt.clone(), timeout).await;
+            recorder.query_one(stmt, &res);
+            return res;
+        }
+        self.query_one_unrecorded(stmt, timeout).await
+    }
+
+    #[instrument(level = "trace")]
+    async fn query_all_with_timeout(
+        &self,
+        stmt: Statement,
+        timeout: Option<Duration>,
+    ) -> Result<Vec<QueryResult>, DbErr> {
+        #[cfg(all(feature = "mock", feature = "with-json"))]
+        if let Some(recorder) = &self.recorder {
+            let res = self.query_all_unrecorded(stmt.clone(), timeout).await;
+            recorder.query_all(stmt, &res);
+            return res;
+        }
+        self.query_all_unrecorded(stmt, timeout).await
+    }
+}
+
+/// A query for one row that returns no row is not a failure
+#[cfg(feature = "sqlx-dep")]
+fn row_not_found_to_none<R>(res: Result<R, sqlx::Error>) -> Result<Option<QueryResult>, sqlx::Error>
+where
+    R: Into<QueryResult>,
+{
+    match res {
+        Ok(row) => Ok(Some(row.into())),
+        Err(sqlx::Error::RowNotFound) => Ok(None),
+        Err(err) => Err(err),
+    }
+}
+
 #[async_trait::async_trait]
 impl<'a> StreamTrait<'a> for DatabaseTransaction {
     type Stream = TransactionStream<'a>;
@@ -368,11 +649,12 @@ impl<'a> StreamTrait<'a> for DatabaseTransaction {
     ) -> Pin<Box<dyn Future<Output = Result<Self::Stream, DbErr>> + 'a + Send>> {
         Box::pin(async move {
             let conn = self.conn.lock().await;
-            Ok(crate::TransactionStream::build(
-                conn,
-                stmt,
-                self.metric_callback.clone(),
-            ))
+            let stream = crate::TransactionStream::build(conn, stmt, self.metric_callback.clone());
+            #[cfg(all(feature = "mock", feature = "with-json"))]
+            if let Some(recorder) = &self.recorder {
+                return Ok(stream.record(recorder));
+            }
+            Ok(stream)
         })
     }
 }
@@ -381,12 +663,42 @@ impl<'a> StreamTrait<'a> for DatabaseTransaction {
 impl TransactionTrait for DatabaseTransaction {
     #[instrument(level = "trace")]
     async fn begin(&self) -> Result<DatabaseTransaction, DbErr> {
-        DatabaseTransaction::begin(
+        let res = DatabaseTransaction::begin(
             Arc::clone(&self.conn),
             self.backend,
             self.metric_callback.clone(),
+            None,
+            None,
         )
-        .await
+        .await;
+        #[cfg(all(feature = "mock", feature = "with-json"))]
+        if let Some(recorder) = &self.recorder {
+            recorder.transaction("BEGIN", &res.as_ref().map(|_| ()).map_err(Clone::clone));
+        }
+        let mut transaction = res?;
+        transaction.statement_timeout = self.statement_timeout;
+        transaction.support_returning = self.support_returning;
+        #[cfg(all(feature = "mock", feature = "with-json"))]
+        {
+            transaction.recorder = self.recorder.clone();
+        }
+        Ok(transaction)
+    }
+
+    /// The isolation level and access mode can only be set on the outermost transaction,
+    /// a nested transaction with either of them returns an error
+    #[instrument(level = "trace")]
+    async fn begin_with_config(
+        &self,
+        isolation_level: Option<IsolationLevel>,
+        access_mode: Option<AccessMode>,
+    ) -> Result<DatabaseTransaction, DbErr> {
+        if isolation_level.is_some() || access_mode.is_some() {
+            return Err(DbErr::Exec(
+                "Isolation level and access mode cannot be set on a nested transaction".to_owned(),
+            ));
+        }
+        self.begin().await
     }
 
     /// Execute the function inside a transaction.
@@ -404,6 +716,30 @@ impl TransactionTrait for DatabaseTransaction {
         let transaction = self.begin().await.map_err(TransactionError::Connection)?;
         transaction.run(_callback).await
     }
+
+    /// Execute the function inside a transaction.
+    /// The isolation level and access mode can only be set on the outermost transaction.
+    #[instrument(level = "trace", skip(_callback))]
+    async fn transaction_with_config<F, T, E>(
+        &self,
+        _callback: F,
+        isolation_level: Option<IsolationLevel>,
+        access_mode: Option<AccessMode>,
+    ) -> Result<T, TransactionError<E>>
+    where
+        F: for<'c> FnOnce(
+                &'c DatabaseTransaction,
+            ) -> Pin<Box<dyn Future<Output = Result<T, E>> + Send + 'c>>
+            + Send,
+        T: Send,
+        E: std::error::Error + Send,
+    {
+        let transaction = self
+            .begin_with_config(isolation_level, access_mode)
+            .await
+            .map_err(TransactionError::Connection)?;
+        transaction.run(_callback).await
+    }
 }
 
 /// Defines errors for handling transaction failures
@@ -431,3 +767,81 @@ where
 }
 
 impl<E> std::error::Error for TransactionError<E> where E: std::error::Error {}
+
+/// Isolation level of a transaction
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IsolationLevel {
+    /// Statements can see uncommitted changes of other transactions
+    ReadUncommitted,
+    /// Statements can only see changes committed before they began
+    ReadCommitted,
+    /// Statements can only see changes committed before the transaction began
+    RepeatableRead,
+    /// Transactions behave as if they were executed one after another
+    Serializable,
+}
+
+impl std::fmt::Display for IsolationLevel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            IsolationLevel::ReadUncommitted => write!(f, "READ UNCOMMITTED"),
+            IsolationLevel::ReadCommitted => write!(f, "READ COMMITTED"),
+            IsolationLevel::RepeatableRead => write!(f, "REPEATABLE READ"),
+            IsolationLevel::Serializable => write!(f, "SERIALIZABLE"),
+        }
+    }
+}
+
+/// Access mode of a transaction
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AccessMode {
+    /// Data cannot be modified in the transaction
+    ReadOnly,
+    /// Data can be read and modified in the transaction
+    ReadWrite,
+}
+
+impl std::fmt::Display for AccessMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AccessMode::ReadOnly => write!(f, "READ ONLY"),
+            AccessMode::ReadWrite => write!(f, "READ WRITE"),
+        }
+    }
+}
+
+/// SQLite transactions are always serializable, instead the locks are acquired upfront
+/// when an isolation level or the read write access mode is given: `EXCLUSIVE` for a
+/// serializable transaction and `IMMEDIATE` otherwise, preventing `SQLITE_BUSY` when a
+/// deferred transaction upgrades to a write transaction.
+#[cfg(any(feature = "mock", feature = "sqlx-sqlite"))]
+pub(crate) fn sqlite_begin_sql(
+    isolation_level: Option<IsolationLevel>,
+    access_mode: Option<AccessMode>,
+) -> Option<&'static str> {
+    match (isolation_level, access_mode) {
+        (Some(IsolationLevel::Serializable), _) => Some("BEGIN EXCLUSIVE"),
+        (Some(_), _) | (_, Some(AccessMode::ReadWrite)) => Some("BEGIN IMMEDIATE"),
+        _ => None,
+    }
+}
+
+/// The `SET TRANSACTION` statement of MySQL and Postgres, if any characteristic is given
+#[cfg(any(feature = "mock", feature = "sqlx-mysql", feature = "sqlx-postgres"))]
+pub(crate) fn set_transaction_config_sql(
+    isolation_level: Option<IsolationLevel>,
+    access_mode: Option<AccessMode>,
+) -> Option<String> {
+    let mut modes = Vec::new();
+    if let Some(isolation_level) = isolation_level {
+        modes.push(format!("ISOLATION LEVEL {}", isolation_level));
+    }
+    if let Some(access_mode) = access_mode {
+        modes.push(access_mode.to_string());
+    }
+    if modes.is_empty() {
+        None
+    } else {
+        Some(format!("SET TRANSACTION {}", modes.join(", ")))
+    }
+}
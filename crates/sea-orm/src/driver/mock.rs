@@ -1,13 +1,13 @@
 use crate::{
-    debug_print, error::*, DatabaseConnection, DbBackend, ExecResult, MockDatabase, QueryResult,
-    Statement, Transaction,
+    debug_print, error::*, AccessMode, DatabaseConnection, DbBackend, ExecResult, IsolationLevel,
+    MockDatabase, QueryResult, Statement, Transaction,
 };
 use futures::Stream;
 use std::{
     fmt::Debug,
     pin::Pin,
     sync::{
-        atomic::{AtomicUsize, Ordering},
+        atomic::{AtomicBool, AtomicUsize, Ordering},
         Arc, Mutex,
     },
 };
@@ -18,11 +18,23 @@ use tracing::instrument;
 pub struct MockDatabaseConnector;
 
 /// Defines a connection for the [MockDatabase]
-#[derive(Debug)]
 pub struct MockDatabaseConnection {
     execute_counter: AtomicUsize,
     query_counter: AtomicUsize,
     mocker: Mutex<Box<dyn MockDatabaseTrait>>,
+    closed: AtomicBool,
+    metric_callback: Mutex<Option<crate::metric::Callback>>,
+}
+
+impl Debug for MockDatabaseConnection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MockDatabaseConnection")
+            .field("execute_counter", &self.execute_counter)
+            .field("query_counter", &self.query_counter)
+            .field("mocker", &self.mocker)
+            .field("closed", &self.closed)
+            .finish()
+    }
 }
 
 /// A Trait for any type wanting to perform operations on the [MockDatabase]
@@ -36,17 +48,72 @@ pub trait MockDatabaseTrait: Send + Debug {
     /// Create a transaction that can be committed atomically
     fn begin(&mut self);
 
+    /// Create a transaction with an isolation level and access mode
+    fn begin_with_config(
+        &mut self,
+        isolation_level: Option<IsolationLevel>,
+        access_mode: Option<AccessMode>,
+    ) {
+        let _ = (isolation_level, access_mode);
+        self.begin()
+    }
+
+    /// Record a `SAVEPOINT`, `ROLLBACK TO SAVEPOINT` or `RELEASE SAVEPOINT` statement
+    /// of a named savepoint in the open transaction
+    fn savepoint(&mut self, stmt: Statement) {
+        let _ = stmt;
+    }
+
     /// Commit a successful transaction atomically into the [MockDatabase]
     fn commit(&mut self);
 
     /// Roll back a transaction since errors were encountered
     fn rollback(&mut self);
 
+    /// Create a transaction, which may fail with an error injected into the [MockDatabase]
+    fn try_begin(&mut self) -> Result<(), DbErr> {
+        self.begin();
+        Ok(())
+    }
+
+    /// Create a transaction with an isolation level and access mode, which may fail with an
+    /// error injected into the [MockDatabase]
+    fn try_begin_with_config(
+        &mut self,
+        isolation_level: Option<IsolationLevel>,
+        access_mode: Option<AccessMode>,
+    ) -> Result<(), DbErr> {
+        self.begin_with_config(isolation_level, access_mode);
+        Ok(())
+    }
+
+    /// Commit a transaction, which may fail with an error injected into the [MockDatabase]
+    fn try_commit(&mut self) -> Result<(), DbErr> {
+        self.commit();
+        Ok(())
+    }
+
+    /// Roll back a transaction, which may fail with an error injected into the [MockDatabase]
+    fn try_rollback(&mut self) -> Result<(), DbErr> {
+        self.rollback();
+        Ok(())
+    }
+
     /// Get all logs from a [MockDatabase] and return a [Transaction]
     fn drain_transaction_log(&mut self) -> Vec<Transaction>;
 
     /// Get the backend being used in the [MockDatabase]
     fn get_database_backend(&self) -> DbBackend;
+
+    /// Check if a transaction is open, as reported in [metric::Info](crate::metric::Info)
+    fn is_in_transaction(&self) -> bool {
+        false
+    }
+
+    /// Check that the expected statements have been executed
+    fn verify(&self) -> Result<(), DbErr> {
+        Ok(())
+    }
 }
 
 impl MockDatabaseConnector {
@@ -106,9 +173,42 @@ impl MockDatabaseConnection {
             execute_counter: AtomicUsize::new(0),
             query_counter: AtomicUsize::new(0),
             mocker: Mutex::new(Box::new(m)),
+            closed: AtomicBool::new(false),
+            metric_callback: Mutex::new(None),
         }
     }
 
+    pub(crate) fn set_metric_callback(&self, callback: crate::metric::Callback) {
+        *self.metric_callback.lock().unwrap() = Some(callback);
+    }
+
+    /// Run a statement on the mocker, reporting it to the metric callback and its span
+    fn run<T, F>(&self, statement: Statement, f: F) -> Result<T, DbErr>
+    where
+        F: FnOnce(&mut dyn MockDatabaseTrait, Statement) -> Result<T, DbErr>,
+        Result<T, DbErr>: crate::metric::MetricResult,
+    {
+        let callback = self.metric_callback.lock().unwrap().clone();
+        let mut mocker = self.mocker.lock().unwrap();
+        let in_transaction = mocker.is_in_transaction();
+        let span = crate::metric::statement_span(&statement, in_transaction);
+        if callback.is_none() && span.is_disabled() {
+            return f(mocker.as_mut(), statement);
+        }
+        let stmt = statement.clone();
+        let start = std::time::SystemTime::now();
+        let res = span.in_scope(|| f(mocker.as_mut(), statement));
+        crate::metric::report(
+            callback.as_ref(),
+            &span,
+            &stmt,
+            start.elapsed().unwrap_or_default(),
+            in_transaction,
+            &res,
+        );
+        res
+    }
+
     pub(crate) fn get_mocker_mutex(&self) -> &Mutex<Box<dyn MockDatabaseTrait>> {
         &self.mocker
     }
@@ -122,25 +222,35 @@ impl MockDatabaseConnection {
     #[instrument(level = "trace")]
     pub fn execute(&self, statement: Statement) -> Result<ExecResult, DbErr> {
         debug_print!("{}", statement);
+        self.check_open()?;
         let counter = self.execute_counter.fetch_add(1, Ordering::SeqCst);
-        self.mocker.lock().unwrap().execute(counter, statement)
+        self.run(statement, |mocker, statement| {
+            mocker.execute(counter, statement)
+        })
     }
 
     /// Return one [QueryResult] if the query was successful
     #[instrument(level = "trace")]
     pub fn query_one(&self, statement: Statement) -> Result<Option<QueryResult>, DbErr> {
         debug_print!("{}", statement);
+        self.check_open()?;
         let counter = self.query_counter.fetch_add(1, Ordering::SeqCst);
-        let result = self.mocker.lock().unwrap().query(counter, statement)?;
-        Ok(result.into_iter().next())
+        self.run(statement, |mocker, statement| {
+            mocker
+                .query(counter, statement)
+                .map(|rows| rows.into_iter().next())
+        })
     }
 
     /// Return all [QueryResult]s if the query was successful
     #[instrument(level = "trace")]
     pub fn query_all(&self, statement: Statement) -> Result<Vec<QueryResult>, DbErr> {
         debug_print!("{}", statement);
+        self.check_open()?;
         let counter = self.query_counter.fetch_add(1, Ordering::SeqCst);
-        self.mocker.lock().unwrap().query(counter, statement)
+        self.run(statement, |mocker, statement| {
+            mocker.query(counter, statement)
+        })
     }
 
     /// Return [QueryResult]s  from a multi-query operation
@@ -161,6 +271,26 @@ impl MockDatabaseConnection {
         self.mocker.lock().unwrap().begin()
     }
 
+    /// Create a statement block of SQL statements with an isolation level and access mode
+    #[instrument(level = "trace")]
+    pub fn begin_with_config(
+        &self,
+        isolation_level: Option<IsolationLevel>,
+        access_mode: Option<AccessMode>,
+    ) {
+        self.mocker
+            .lock()
+            .unwrap()
+            .begin_with_config(isolation_level, access_mode)
+    }
+
+    /// Create, roll back to or release a named savepoint in the open transaction
+    #[instrument(level = "trace")]
+    pub fn savepoint(&self, statement: Statement) {
+        debug_print!("{}", statement);
+        self.mocker.lock().unwrap().savepoint(statement)
+    }
+
     /// Commit a transaction atomically to the database
     #[instrument(level = "trace")]
     pub fn commit(&self) {
@@ -172,4 +302,66 @@ impl MockDatabaseConnection {
     pub fn rollback(&self) {
         self.mocker.lock().unwrap().rollback()
     }
+
+    /// Same as [MockDatabaseConnection::begin], failing with an error injected into the mocker
+    #[instrument(level = "trace")]
+    pub fn try_begin(&self) -> Result<(), DbErr> {
+        self.mocker.lock().unwrap().try_begin()
+    }
+
+    /// Same as [MockDatabaseConnection::begin_with_config], failing with an error injected into
+    /// the mocker
+    #[instrument(level = "trace")]
+    pub fn try_begin_with_config(
+        &self,
+        isolation_level: Option<IsolationLevel>,
+        access_mode: Option<AccessMode>,
+    ) -> Result<(), DbErr> {
+        self.mocker
+            .lock()
+            .unwrap()
+            .try_begin_with_config(isolation_level, access_mode)
+    }
+
+    /// Same as [MockDatabaseConnection::commit], failing with an error injected into the mocker
+    #[instrument(level = "trace")]
+    pub fn try_commit(&self) -> Result<(), DbErr> {
+        self.mocker.lock().unwrap().try_commit()
+    }
+
+    /// Same as [MockDatabaseConnection::rollback], failing with an error injected into the mocker
+    #[instrument(level = "trace")]
+    pub fn try_rollback(&self) -> Result<(), DbErr> {
+        self.mocker.lock().unwrap().try_rollback()
+    }
+
+    /// Check that the expected statements have been executed, see [MockDatabase::verify]
+    pub fn verify(&self) -> Result<(), DbErr> {
+        self.mocker.lock().unwrap().verify()
+    }
+
+    /// Check if the connection has not been closed
+    #[instrument(level = "trace")]
+    pub fn ping(&self) -> Result<(), DbErr> {
+        self.check_open()
+    }
+
+    /// Close the connection, the statements executed afterwards fail
+    #[instrument(level = "trace")]
+    pub fn close(&self) {
+        self.closed.store(true, Ordering::SeqCst);
+    }
+
+    /// Check if the connection has been closed
+    pub fn is_closed(&self) -> bool {
+        self.closed.load(Ordering::SeqCst)
+    }
+
+    fn check_open(&self) -> Result<(), DbErr> {
+        if self.is_closed() {
+            Err(DbErr::Conn("Connection is closed".to_owned()))
+        } else {
+            Ok(())
+        }
+    }
 }
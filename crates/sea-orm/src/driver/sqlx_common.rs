@@ -1,16 +1,143 @@
-use crate::DbErr;
+use crate::{DbErr, DriverErr, DriverErrKind, PoolStats};
+use std::sync::{
+    atomic::{AtomicU32, Ordering},
+    Arc,
+};
 
 /// Converts an [sqlx::error] execution error to a [DbErr]
 pub fn sqlx_error_to_exec_err(err: sqlx::Error) -> DbErr {
-    DbErr::Exec(err.to_string())
+    match sqlx_error_to_driver_err(err) {
+        Ok(err) => DbErr::Driver(Box::new(err)),
+        Err(err) => DbErr::Exec(err.to_string()),
+    }
 }
 
 /// Converts an [sqlx::error] query error to a [DbErr]
 pub fn sqlx_error_to_query_err(err: sqlx::Error) -> DbErr {
-    DbErr::Query(err.to_string())
+    match sqlx_error_to_driver_err(err) {
+        Ok(err) => DbErr::Driver(Box::new(err)),
+        Err(err) => DbErr::Query(err.to_string()),
+    }
 }
 
 /// Converts an [sqlx::error] connection error to a [DbErr]
 pub fn sqlx_error_to_conn_err(err: sqlx::Error) -> DbErr {
     DbErr::Conn(err.to_string())
 }
+
+/// Classify the errors reported by the database and the errors of the connection,
+/// the errors without a [DriverErrKind] are given back
+fn sqlx_error_to_driver_err(err: sqlx::Error) -> Result<DriverErr, sqlx::Error> {
+    match classify_sqlx_error(&err) {
+        Some(driver_err) if driver_err.kind.is_some() => Ok(DriverErr {
+            source: Some(Arc::new(err)),
+            ..driver_err
+        }),
+        _ => Err(err),
+    }
+}
+
+/// Get the kind of an [sqlx::Error], if it is classified
+pub(crate) fn sqlx_error_kind(err: &sqlx::Error) -> Option<DriverErrKind> {
+    classify_sqlx_error(err).and_then(|err| err.kind)
+}
+
+fn classify_sqlx_error(err: &sqlx::Error) -> Option<DriverErr> {
+    match err {
+        sqlx::Error::Database(db_err) => Some(database_error_to_driver_err(db_err.as_ref())),
+        sqlx::Error::Io(io_err) if io_err.kind() == std::io::ErrorKind::TimedOut => {
+            Some(DriverErr {
+                kind: Some(DriverErrKind::Timeout),
+                message: err.to_string(),
+                ..Default::default()
+            })
+        }
+        sqlx::Error::Io(_) | sqlx::Error::Tls(_) | sqlx::Error::PoolClosed => Some(DriverErr {
+            kind: Some(DriverErrKind::ConnectionLost),
+            message: err.to_string(),
+            ..Default::default()
+        }),
+        sqlx::Error::PoolTimedOut => Some(DriverErr {
+            kind: Some(DriverErrKind::Timeout),
+            message: err.to_string(),
+            ..Default::default()
+        }),
+        _ => None,
+    }
+}
+
+fn database_error_to_driver_err(err: &dyn sqlx::error::DatabaseError) -> DriverErr {
+    #[cfg(feature = "sqlx-mysql")]
+    if let Some(err) = err.try_downcast_ref::<sqlx::mysql::MySqlDatabaseError>() {
+        return DriverErr::from_mysql(err.number(), err.code(), err.message());
+    }
+    #[cfg(feature = "sqlx-postgres")]
+    if let Some(err) = err.try_downcast_ref::<sqlx::postgres::PgDatabaseError>() {
+        return DriverErr {
+            constraint: err.constraint().map(ToOwned::to_owned),
+            table: err.table().map(ToOwned::to_owned),
+            column: err.column().map(ToOwned::to_owned),
+            ..DriverErr::from_postgres(err.code(), err.message())
+        };
+    }
+    #[cfg(feature = "sqlx-sqlite")]
+    if err
+        .try_downcast_ref::<sqlx::sqlite::SqliteError>()
+        .is_some()
+    {
+        return DriverErr::from_sqlite(err.code().as_deref(), err.message());
+    }
+    DriverErr {
+        code: err.code().map(|code| code.into_owned()),
+        message: err.message().to_owned(),
+        ..Default::default()
+    }
+}
+
+/// Counts the tasks waiting for a connection from a pool
+#[derive(Debug, Clone, Default)]
+pub(crate) struct WaitCounter(Arc<AtomicU32>);
+
+struct WaitGuard<'a>(&'a AtomicU32);
+
+impl Drop for WaitGuard<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+impl WaitCounter {
+    /// Acquire a connection from the pool, counting the task as waiting until it is acquired
+    pub(crate) async fn acquire<DB>(
+        &self,
+        pool: &sqlx::Pool<DB>,
+    ) -> Result<sqlx::pool::PoolConnection<DB>, sqlx::Error>
+    where
+        DB: sqlx::Database,
+    {
+        self.0.fetch_add(1, Ordering::Relaxed);
+        // decrement even if the future is dropped before a connection is acquired
+        let _guard = WaitGuard(&self.0);
+        pool.acquire().await
+    }
+
+    /// Get the number of tasks waiting for a connection
+    pub(crate) fn get(&self) -> u32 {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// Get the statistics of a pool
+pub(crate) fn pool_stats<DB>(pool: &sqlx::Pool<DB>, waiting: &WaitCounter) -> PoolStats
+where
+    DB: sqlx::Database,
+{
+    let size = pool.size();
+    let idle = pool.num_idle() as u32;
+    PoolStats {
+        size,
+        idle,
+        in_use: size.saturating_sub(idle),
+        waiting: waiting.get(),
+    }
+}
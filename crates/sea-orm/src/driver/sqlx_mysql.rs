@@ -1,4 +1,4 @@
-use std::{future::Future, pin::Pin, sync::Arc};
+use std::{borrow::Cow, future::Future, pin::Pin, sync::Arc, time::Duration};
 
 use sqlx::{
     mysql::{MySqlArguments, MySqlConnectOptions, MySqlQueryResult, MySqlRow},
@@ -10,8 +10,9 @@ use sea_query_driver_mysql::bind_query;
 use tracing::instrument;
 
 use crate::{
-    debug_print, error::*, executor::*, ConnectOptions, DatabaseConnection, DatabaseTransaction,
-    QueryStream, Statement, TransactionError,
+    debug_print, error::*, executor::*, run_after_connect, AccessMode, ConnectOptions,
+    DatabaseConnection, DatabaseTransaction, DbBackend, InitConnection, InnerInitConnection,
+    IsolationLevel, PoolStats, QueryStream, Statement, TransactionError,
 };
 
 use super::sqlx_common::*;
@@ -25,6 +26,7 @@ pub struct SqlxMySqlConnector;
 pub struct SqlxMySqlPoolConnection {
     pool: MySqlPool,
     metric_callback: Option<crate::metric::Callback>,
+    waiting: WaitCounter,
 }
 
 impl std::fmt::Debug for SqlxMySqlPoolConnection {
@@ -50,11 +52,26 @@ impl SqlxMySqlConnector {
             use sqlx::ConnectOptions;
             opt.disable_statement_logging();
         }
-        match options.pool_options().connect_with(opt).await {
+        let after_connect = options.after_connect_steps(DbBackend::MySql);
+        let mut pool_options = options.pool_options();
+        if !after_connect.is_empty() {
+            let after_connect = Arc::new(after_connect);
+            pool_options = pool_options.after_connect(move |conn| {
+                let after_connect = Arc::clone(&after_connect);
+                Box::pin(async move {
+                    let conn = InitConnection::new(InnerInitConnection::MySql(conn));
+                    run_after_connect(conn, &after_connect)
+                        .await
+                        .map_err(|err| sqlx::Error::Configuration(Box::new(err)))
+                })
+            });
+        }
+        match pool_options.connect_with(opt).await {
             Ok(pool) => Ok(DatabaseConnection::SqlxMySqlPoolConnection(
                 SqlxMySqlPoolConnection {
                     pool,
                     metric_callback: None,
+                    waiting: WaitCounter::default(),
                 },
             )),
             Err(e) => Err(sqlx_error_to_conn_err(e)),
@@ -68,6 +85,7 @@ impl SqlxMySqlConnector {
         DatabaseConnection::SqlxMySqlPoolConnection(SqlxMySqlPoolConnection {
             pool,
             metric_callback: None,
+            waiting: WaitCounter::default(),
         })
     }
 }
@@ -76,64 +94,97 @@ impl SqlxMySqlPoolConnection {
     /// Execute a [Statement] on a MySQL backend
     #[instrument(level = "trace")]
     pub async fn execute(&self, stmt: Statement) -> Result<ExecResult, DbErr> {
+        self.execute_with_timeout(stmt, None).await
+    }
+
+    /// Same as [SqlxMySqlPoolConnection::execute], with a timeout overriding the statement timeout of the connection.
+    /// The timeout is only applied to `SELECT` statements
+    #[instrument(level = "trace")]
+    pub async fn execute_with_timeout(
+        &self,
+        stmt: Statement,
+        timeout: Option<Duration>,
+    ) -> Result<ExecResult, DbErr> {
         debug_print!("{}", stmt);
 
-        let query = sqlx_query(&stmt);
-        if let Ok(conn) = &mut self.pool.acquire().await {
-            crate::metric::metric!(self.metric_callback, &stmt, {
-                match query.execute(conn).await {
-                    Ok(res) => Ok(res.into()),
-                    Err(err) => Err(sqlx_error_to_exec_err(err)),
-                }
-            })
-        } else {
-            Err(DbErr::Exec(
-                "Failed to acquire connection from pool.".to_owned(),
-            ))
-        }
+        let hinted = with_max_execution_time(&stmt, timeout);
+        let query = sqlx_query(&hinted);
+        let conn = &mut self
+            .waiting
+            .acquire(&self.pool)
+            .await
+            .map_err(sqlx_error_to_exec_err)?;
+        crate::metric::metric!(self.metric_callback, &stmt, false, {
+            match query.execute(conn).await {
+                Ok(res) => Ok(res.into()),
+                Err(err) => Err(sqlx_error_to_exec_err(err)),
+            }
+        })
     }
 
     /// Get one result from a SQL query. Returns [Option::None] if no match was found
     #[instrument(level = "trace")]
     pub async fn query_one(&self, stmt: Statement) -> Result<Option<QueryResult>, DbErr> {
+        self.query_one_with_timeout(stmt, None).await
+    }
+
+    /// Same as [SqlxMySqlPoolConnection::query_one], with a timeout overriding the statement timeout of the connection.
+    /// The timeout is only applied to `SELECT` statements
+    #[instrument(level = "trace")]
+    pub async fn query_one_with_timeout(
+        &self,
+        stmt: Statement,
+        timeout: Option<Duration>,
+    ) -> Result<Option<QueryResult>, DbErr> {
         debug_print!("{}", stmt);
 
-        let query = sqlx_query(&stmt);
-        if let Ok(conn) = &mut self.pool.acquire().await {
-            crate::metric::metric!(self.metric_callback, &stmt, {
-                match query.fetch_one(conn).await {
-                    Ok(row) => Ok(Some(row.into())),
-                    Err(err) => match err {
-                        sqlx::Error::RowNotFound => Ok(None),
-                        _ => Err(DbErr::Query(err.to_string())),
-                    },
-                }
-            })
-        } else {
-            Err(DbErr::Query(
-                "Failed to acquire connection from pool.".to_owned(),
-            ))
-        }
+        let hinted = with_max_execution_time(&stmt, timeout);
+        let query = sqlx_query(&hinted);
+        let conn = &mut self
+            .waiting
+            .acquire(&self.pool)
+            .await
+            .map_err(sqlx_error_to_query_err)?;
+        crate::metric::metric!(self.metric_callback, &stmt, false, {
+            match query.fetch_one(conn).await {
+                Ok(row) => Ok(Some(row.into())),
+                Err(err) => match err {
+                    sqlx::Error::RowNotFound => Ok(None),
+                    _ => Err(sqlx_error_to_query_err(err)),
+                },
+            }
+        })
     }
 
     /// Get the results of a query returning them as a Vec<[QueryResult]>
     #[instrument(level = "trace")]
     pub async fn query_all(&self, stmt: Statement) -> Result<Vec<QueryResult>, DbErr> {
+        self.query_all_with_timeout(stmt, None).await
+    }
+
+    /// Same as [SqlxMySqlPoolConnection::query_all], with a timeout overriding the statement timeout of the connection.
+    /// The timeout is only applied to `SELECT` statements
+    #[instrument(level = "trace")]
+    pub async fn query_all_with_timeout(
+        &self,
+        stmt: Statement,
+        timeout: Option<Duration>,
+    ) -> Result<Vec<QueryResult>, DbErr> {
         debug_print!("{}", stmt);
 
-        let query = sqlx_query(&stmt);
-        if let Ok(conn) = &mut self.pool.acquire().await {
-            crate::metric::metric!(self.metric_callback, &stmt, {
-                match query.fetch_all(conn).await {
-                    Ok(rows) => Ok(rows.into_iter().map(|r| r.into()).collect()),
-                    Err(err) => Err(sqlx_error_to_query_err(err)),
-                }
-            })
-        } else {
-            Err(DbErr::Query(
-                "Failed to acquire connection from pool.".to_owned(),
-            ))
-        }
+        let hinted = with_max_execution_time(&stmt, timeout);
+        let query = sqlx_query(&hinted);
+        let conn = &mut self
+            .waiting
+            .acquire(&self.pool)
+            .await
+            .map_err(sqlx_error_to_query_err)?;
+        crate::metric::metric!(self.metric_callback, &stmt, false, {
+            match query.fetch_all(conn).await {
+                Ok(rows) => Ok(rows.into_iter().map(|r| r.into()).collect()),
+                Err(err) => Err(sqlx_error_to_query_err(err)),
+            }
+        })
     }
 
     /// Stream the results of executing a SQL query
@@ -141,29 +192,43 @@ impl SqlxMySqlPoolConnection {
     pub async fn stream(&self, stmt: Statement) -> Result<QueryStream, DbErr> {
         debug_print!("{}", stmt);
 
-        if let Ok(conn) = self.pool.acquire().await {
-            Ok(QueryStream::from((
-                conn,
-                stmt,
-                self.metric_callback.clone(),
-            )))
-        } else {
-            Err(DbErr::Query(
-                "Failed to acquire connection from pool.".to_owned(),
-            ))
-        }
+        let conn = self
+            .waiting
+            .acquire(&self.pool)
+            .await
+            .map_err(sqlx_error_to_query_err)?;
+        Ok(QueryStream::from((
+            conn,
+            stmt,
+            self.metric_callback.clone(),
+        )))
     }
 
     /// Bundle a set of SQL statements that execute together.
     #[instrument(level = "trace")]
     pub async fn begin(&self) -> Result<DatabaseTransaction, DbErr> {
-        if let Ok(conn) = self.pool.acquire().await {
-            DatabaseTransaction::new_mysql(conn, self.metric_callback.clone()).await
-        } else {
-            Err(DbErr::Query(
-                "Failed to acquire connection from pool.".to_owned(),
-            ))
-        }
+        self.begin_with_config(None, None).await
+    }
+
+    /// Bundle a set of SQL statements that execute together, with an optional isolation level and access mode.
+    #[instrument(level = "trace")]
+    pub async fn begin_with_config(
+        &self,
+        isolation_level: Option<IsolationLevel>,
+        access_mode: Option<AccessMode>,
+    ) -> Result<DatabaseTransaction, DbErr> {
+        let conn = self
+            .waiting
+            .acquire(&self.pool)
+            .await
+            .map_err(sqlx_error_to_query_err)?;
+        DatabaseTransaction::new_mysql(
+            conn,
+            self.metric_callback.clone(),
+            isolation_level,
+            access_mode,
+        )
+        .await
     }
 
     /// Create a MySQL transaction
@@ -177,18 +242,63 @@ impl SqlxMySqlPoolConnection {
         T: Send,
         E: std::error::Error + Send,
     {
-        if let Ok(conn) = self.pool.acquire().await {
-            let transaction = DatabaseTransaction::new_mysql(conn, self.metric_callback.clone())
+        self.transaction_with_config(callback, None, None).await
+    }
+
+    /// Create a MySQL transaction with an optional isolation level and access mode
+    #[instrument(level = "trace", skip(callback))]
+    pub async fn transaction_with_config<F, T, E>(
+        &self,
+        callback: F,
+        isolation_level: Option<IsolationLevel>,
+        access_mode: Option<AccessMode>,
+    ) -> Result<T, TransactionError<E>>
+    where
+        F: for<'b> FnOnce(
+                &'b DatabaseTransaction,
+            ) -> Pin<Box<dyn Future<Output = Result<T, E>> + Send + 'b>>
+            + Send,
+        T: Send,
+        E: std::error::Error + Send,
+    {
+        let conn = self
+            .waiting
+            .acquire(&self.pool)
+            .await
+            .map_err(|e| TransactionError::Connection(sqlx_error_to_query_err(e)))?;
+        let transaction = DatabaseTransaction::new_mysql(
+            conn,
+            self.metric_callback.clone(),
+            isolation_level,
+            access_mode,
+        )
+        .await
+        .map_err(|e| TransactionError::Connection(e))?;
+        transaction.run(callback).await
+    }
+
+    /// Check if the database can be reached, by acquiring a connection and pinging it
+    #[instrument(level = "trace")]
+    pub async fn ping(&self) -> Result<(), DbErr> {
+        match self.waiting.acquire(&self.pool).await {
+            Ok(mut conn) => sqlx::Connection::ping(&mut *conn)
                 .await
-                .map_err(|e| TransactionError::Connection(e))?;
-            transaction.run(callback).await
-        } else {
-            Err(TransactionError::Connection(DbErr::Query(
-                "Failed to acquire connection from pool.".to_owned(),
-            )))
+                .map_err(sqlx_error_to_conn_err),
+            Err(err) => Err(sqlx_error_to_conn_err(err)),
         }
     }
 
+    /// Close the pool, waiting for the connections in use to be returned to the pool
+    #[instrument(level = "trace")]
+    pub async fn close(&self) {
+        self.pool.close().await
+    }
+
+    /// Get the statistics of the pool
+    pub fn pool_stats(&self) -> PoolStats {
+        pool_stats(&self.pool, &self.waiting)
+    }
+
     pub(crate) fn set_metric_callback<F>(&mut self, callback: F)
     where
         F: Fn(&crate::metric::Info<'_>) + Send + Sync + 'static,
@@ -213,6 +323,31 @@ impl From<MySqlQueryResult> for ExecResult {
     }
 }
 
+/// Add a `MAX_EXECUTION_TIME` optimizer hint to a `SELECT` statement with a timeout,
+/// MySQL ignores the hint on other statements
+pub(crate) fn with_max_execution_time(
+    stmt: &Statement,
+    timeout: Option<Duration>,
+) -> Cow<'_, Statement> {
+    let timeout = match timeout {
+        Some(timeout) => timeout,
+        None => return Cow::Borrowed(stmt),
+    };
+    let sql = stmt.sql.trim_start();
+    match sql.get(..6) {
+        Some(keyword) if keyword.eq_ignore_ascii_case("SELECT") => Cow::Owned(Statement {
+            sql: format!(
+                "SELECT /*+ MAX_EXECUTION_TIME({}) */{}",
+                timeout.as_millis(),
+                &sql[6..]
+            ),
+            values: stmt.values.clone(),
+            db_backend: stmt.db_backend,
+        }),
+        _ => Cow::Borrowed(stmt),
+    }
+}
+
 pub(crate) fn sqlx_query(stmt: &Statement) -> sqlx::query::Query<'_, MySql, MySqlArguments> {
     let mut query = sqlx::query(&stmt.sql);
     if let Some(values) = &stmt.values {
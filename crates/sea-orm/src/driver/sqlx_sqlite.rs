@@ -1,7 +1,9 @@
-use std::{future::Future, pin::Pin, sync::Arc};
+use std::{future::Future, pin::Pin, sync::Arc, time::Duration};
 
 use sqlx::{
-    sqlite::{SqliteArguments, SqliteConnectOptions, SqliteQueryResult, SqliteRow},
+    sqlite::{
+        SqliteArguments, SqliteConnectOptions, SqliteConnection, SqliteQueryResult, SqliteRow,
+    },
     Sqlite, SqlitePool,
 };
 
@@ -10,8 +12,10 @@ use sea_query_driver_sqlite::bind_query;
 use tracing::instrument;
 
 use crate::{
-    debug_print, error::*, executor::*, ConnectOptions, DatabaseConnection, DatabaseTransaction,
-    QueryStream, Statement, TransactionError,
+    debug_print, error::*, executor::*, run_after_connect, AccessMode, ConnectOptions,
+    DatabaseConnection, DatabaseTransaction, DbBackend, InitConnection, InnerInitConnection,
+    IsolationLevel, PoolStats, QueryStream, ReplicatedConnection, SqliteJournalMode, SqliteOptions,
+    SqliteSynchronous, Statement, TransactionError,
 };
 
 use super::sqlx_common::*;
@@ -25,6 +29,8 @@ pub struct SqlxSqliteConnector;
 pub struct SqlxSqlitePoolConnection {
     pool: SqlitePool,
     metric_callback: Option<crate::metric::Callback>,
+    waiting: WaitCounter,
+    statement_timeout: Option<Duration>,
 }
 
 impl std::fmt::Debug for SqlxSqlitePoolConnection {
@@ -42,6 +48,42 @@ impl SqlxSqliteConnector {
     /// Add configuration options for the SQLite database
     #[instrument(level = "trace")]
     pub async fn connect(options: ConnectOptions) -> Result<DatabaseConnection, DbErr> {
+        if !options.sqlite.split_writer {
+            return Self::connect_pool(options, false).await;
+        }
+        if options.sqlite.journal_mode != Some(SqliteJournalMode::Wal) {
+            return Err(DbErr::Conn(
+                "Splitting the writer of a SQLite pool requires the WAL journal mode".to_owned(),
+            ));
+        }
+        if is_in_memory(&options.url) {
+            return Err(DbErr::Conn(
+                "Splitting the writer of a SQLite pool requires a database file".to_owned(),
+            ));
+        }
+        let mut writer = options.clone();
+        writer.sqlite.split_writer = false;
+        writer.max_connections(1);
+        if writer.get_min_connections().is_some() {
+            writer.min_connections(1);
+        }
+        let mut reader = options;
+        reader.sqlite.split_writer = false;
+        let selection = reader.get_replica_selection();
+        // The writer connects first, as the readers can not change the journal mode
+        let writer = Self::connect_pool(writer, false).await?;
+        let reader = Self::connect_pool(reader, true).await?;
+        Ok(ReplicatedConnection::connect(
+            writer,
+            vec![reader],
+            selection,
+        ))
+    }
+
+    async fn connect_pool(
+        options: ConnectOptions,
+        read_only: bool,
+    ) -> Result<DatabaseConnection, DbErr> {
         let mut options = options;
         let mut opt = options
             .url
@@ -50,6 +92,7 @@ impl SqlxSqliteConnector {
         if options.sqlcipher_key.is_some() {
             opt = opt.pragma("key", options.sqlcipher_key.clone().unwrap());
         }
+        opt = sqlite_connect_options(opt, &options.sqlite, read_only);
         if !options.sqlx_logging {
             use sqlx::ConnectOptions;
             opt.disable_statement_logging();
@@ -57,11 +100,28 @@ impl SqlxSqliteConnector {
         if options.get_max_connections().is_none() {
             options.max_connections(1);
         }
-        match options.pool_options().connect_with(opt).await {
+        let after_connect = options.after_connect_steps(DbBackend::Sqlite);
+        let statement_timeout = options.statement_timeout;
+        let mut pool_options = options.pool_options();
+        if !after_connect.is_empty() {
+            let after_connect = Arc::new(after_connect);
+            pool_options = pool_options.after_connect(move |conn| {
+                let after_connect = Arc::clone(&after_connect);
+                Box::pin(async move {
+                    let conn = InitConnection::new(InnerInitConnection::Sqlite(conn));
+                    run_after_connect(conn, &after_connect)
+                        .await
+                        .map_err(|err| sqlx::Error::Configuration(Box::new(err)))
+                })
+            });
+        }
+        match pool_options.connect_with(opt).await {
             Ok(pool) => Ok(DatabaseConnection::SqlxSqlitePoolConnection(
                 SqlxSqlitePoolConnection {
                     pool,
                     metric_callback: None,
+                    waiting: WaitCounter::default(),
+                    statement_timeout,
                 },
             )),
             Err(e) => Err(sqlx_error_to_conn_err(e)),
@@ -75,6 +135,8 @@ impl SqlxSqliteConnector {
         DatabaseConnection::SqlxSqlitePoolConnection(SqlxSqlitePoolConnection {
             pool,
             metric_callback: None,
+            waiting: WaitCounter::default(),
+            statement_timeout: None,
         })
     }
 }
@@ -83,64 +145,97 @@ impl SqlxSqlitePoolConnection {
     /// Execute a [Statement] on a SQLite backend
     #[instrument(level = "trace")]
     pub async fn execute(&self, stmt: Statement) -> Result<ExecResult, DbErr> {
+        self.execute_with_timeout(stmt, None).await
+    }
+
+    /// Same as [SqlxSqlitePoolConnection::execute], with a timeout overriding the statement timeout of the connection
+    #[instrument(level = "trace")]
+    pub async fn execute_with_timeout(
+        &self,
+        stmt: Statement,
+        timeout: Option<Duration>,
+    ) -> Result<ExecResult, DbErr> {
         debug_print!("{}", stmt);
 
         let query = sqlx_query(&stmt);
-        if let Ok(conn) = &mut self.pool.acquire().await {
-            crate::metric::metric!(self.metric_callback, &stmt, {
-                match query.execute(conn).await {
-                    Ok(res) => Ok(res.into()),
-                    Err(err) => Err(sqlx_error_to_exec_err(err)),
-                }
-            })
-        } else {
-            Err(DbErr::Exec(
-                "Failed to acquire connection from pool.".to_owned(),
-            ))
-        }
+        let conn = &mut self
+            .waiting
+            .acquire(&self.pool)
+            .await
+            .map_err(sqlx_error_to_exec_err)?;
+        crate::metric::metric!(self.metric_callback, &stmt, false, {
+            let interrupt = Interrupt::new(conn);
+            let timeout = timeout.or(self.statement_timeout);
+            match with_interrupt(interrupt, timeout, query.execute(conn)).await {
+                Ok(res) => Ok(res.into()),
+                Err(err) => Err(sqlx_error_to_exec_err(err)),
+            }
+        })
     }
 
     /// Get one result from a SQL query. Returns [Option::None] if no match was found
     #[instrument(level = "trace")]
     pub async fn query_one(&self, stmt: Statement) -> Result<Option<QueryResult>, DbErr> {
+        self.query_one_with_timeout(stmt, None).await
+    }
+
+    /// Same as [SqlxSqlitePoolConnection::query_one], with a timeout overriding the statement timeout of the connection
+    #[instrument(level = "trace")]
+    pub async fn query_one_with_timeout(
+        &self,
+        stmt: Statement,
+        timeout: Option<Duration>,
+    ) -> Result<Option<QueryResult>, DbErr> {
         debug_print!("{}", stmt);
 
         let query = sqlx_query(&stmt);
-        if let Ok(conn) = &mut self.pool.acquire().await {
-            crate::metric::metric!(self.metric_callback, &stmt, {
-                match query.fetch_one(conn).await {
-                    Ok(row) => Ok(Some(row.into())),
-                    Err(err) => match err {
-                        sqlx::Error::RowNotFound => Ok(None),
-                        _ => Err(DbErr::Query(err.to_string())),
-                    },
-                }
-            })
-        } else {
-            Err(DbErr::Query(
-                "Failed to acquire connection from pool.".to_owned(),
-            ))
-        }
+        let conn = &mut self
+            .waiting
+            .acquire(&self.pool)
+            .await
+            .map_err(sqlx_error_to_query_err)?;
+        crate::metric::metric!(self.metric_callback, &stmt, false, {
+            let interrupt = Interrupt::new(conn);
+            let timeout = timeout.or(self.statement_timeout);
+            match with_interrupt(interrupt, timeout, query.fetch_one(conn)).await {
+                Ok(row) => Ok(Some(row.into())),
+                Err(err) => match err {
+                    sqlx::Error::RowNotFound => Ok(None),
+                    _ => Err(sqlx_error_to_query_err(err)),
+                },
+            }
+        })
     }
 
     /// Get the results of a query returning them as a Vec<[QueryResult]>
     #[instrument(level = "trace")]
     pub async fn query_all(&self, stmt: Statement) -> Result<Vec<QueryResult>, DbErr> {
+        self.query_all_with_timeout(stmt, None).await
+    }
+
+    /// Same as [SqlxSqlitePoolConnection::query_all], with a timeout overriding the statement timeout of the connection
+    #[instrument(level = "trace")]
+    pub async fn query_all_with_timeout(
+        &self,
+        stmt: Statement,
+        timeout: Option<Duration>,
+    ) -> Result<Vec<QueryResult>, DbErr> {
         debug_print!("{}", stmt);
 
         let query = sqlx_query(&stmt);
-        if let Ok(conn) = &mut self.pool.acquire().await {
-            crate::metric::metric!(self.metric_callback, &stmt, {
-                match query.fetch_all(conn).await {
-                    Ok(rows) => Ok(rows.into_iter().map(|r| r.into()).collect()),
-                    Err(err) => Err(sqlx_error_to_query_err(err)),
-                }
-            })
-        } else {
-            Err(DbErr::Query(
-                "Failed to acquire connection from pool.".to_owned(),
-            ))
-        }
+        let conn = &mut self
+            .waiting
+            .acquire(&self.pool)
+            .await
+            .map_err(sqlx_error_to_query_err)?;
+        crate::metric::metric!(self.metric_callback, &stmt, false, {
+            let interrupt = Interrupt::new(conn);
+            let timeout = timeout.or(self.statement_timeout);
+            match with_interrupt(interrupt, timeout, query.fetch_all(conn)).await {
+                Ok(rows) => Ok(rows.into_iter().map(|r| r.into()).collect()),
+                Err(err) => Err(sqlx_error_to_query_err(err)),
+            }
+        })
     }
 
     /// Stream the results of executing a SQL query
@@ -148,29 +243,44 @@ impl SqlxSqlitePoolConnection {
     pub async fn stream(&self, stmt: Statement) -> Result<QueryStream, DbErr> {
         debug_print!("{}", stmt);
 
-        if let Ok(conn) = self.pool.acquire().await {
-            Ok(QueryStream::from((
-                conn,
-                stmt,
-                self.metric_callback.clone(),
-            )))
-        } else {
-            Err(DbErr::Query(
-                "Failed to acquire connection from pool.".to_owned(),
-            ))
-        }
+        let conn = self
+            .waiting
+            .acquire(&self.pool)
+            .await
+            .map_err(sqlx_error_to_query_err)?;
+        Ok(QueryStream::from((
+            conn,
+            stmt,
+            self.metric_callback.clone(),
+        )))
     }
 
     /// Bundle a set of SQL statements that execute together.
     #[instrument(level = "trace")]
     pub async fn begin(&self) -> Result<DatabaseTransaction, DbErr> {
-        if let Ok(conn) = self.pool.acquire().await {
-            DatabaseTransaction::new_sqlite(conn, self.metric_callback.clone()).await
-        } else {
-            Err(DbErr::Query(
-                "Failed to acquire connection from pool.".to_owned(),
-            ))
-        }
+        self.begin_with_config(None, None).await
+    }
+
+    /// Bundle a set of SQL statements that execute together, with an optional isolation level and access mode.
+    #[instrument(level = "trace")]
+    pub async fn begin_with_config(
+        &self,
+        isolation_level: Option<IsolationLevel>,
+        access_mode: Option<AccessMode>,
+    ) -> Result<DatabaseTransaction, DbErr> {
+        let conn = self
+            .waiting
+            .acquire(&self.pool)
+            .await
+            .map_err(sqlx_error_to_query_err)?;
+        DatabaseTransaction::new_sqlite(
+            conn,
+            self.metric_callback.clone(),
+            self.statement_timeout,
+            isolation_level,
+            access_mode,
+        )
+        .await
     }
 
     /// Create a MySQL transaction
@@ -184,18 +294,64 @@ impl SqlxSqlitePoolConnection {
         T: Send,
         E: std::error::Error + Send,
     {
-        if let Ok(conn) = self.pool.acquire().await {
-            let transaction = DatabaseTransaction::new_sqlite(conn, self.metric_callback.clone())
+        self.transaction_with_config(callback, None, None).await
+    }
+
+    /// Create a MySQL transaction with an optional isolation level and access mode
+    #[instrument(level = "trace", skip(callback))]
+    pub async fn transaction_with_config<F, T, E>(
+        &self,
+        callback: F,
+        isolation_level: Option<IsolationLevel>,
+        access_mode: Option<AccessMode>,
+    ) -> Result<T, TransactionError<E>>
+    where
+        F: for<'b> FnOnce(
+                &'b DatabaseTransaction,
+            ) -> Pin<Box<dyn Future<Output = Result<T, E>> + Send + 'b>>
+            + Send,
+        T: Send,
+        E: std::error::Error + Send,
+    {
+        let conn = self
+            .waiting
+            .acquire(&self.pool)
+            .await
+            .map_err(|e| TransactionError::Connection(sqlx_error_to_query_err(e)))?;
+        let transaction = DatabaseTransaction::new_sqlite(
+            conn,
+            self.metric_callback.clone(),
+            self.statement_timeout,
+            isolation_level,
+            access_mode,
+        )
+        .await
+        .map_err(|e| TransactionError::Connection(e))?;
+        transaction.run(callback).await
+    }
+
+    /// Check if the database can be reached, by acquiring a connection and pinging it
+    #[instrument(level = "trace")]
+    pub async fn ping(&self) -> Result<(), DbErr> {
+        match self.waiting.acquire(&self.pool).await {
+            Ok(mut conn) => sqlx::Connection::ping(&mut *conn)
                 .await
-                .map_err(|e| TransactionError::Connection(e))?;
-            transaction.run(callback).await
-        } else {
-            Err(TransactionError::Connection(DbErr::Query(
-                "Failed to acquire connection from pool.".to_owned(),
-            )))
+                .map_err(sqlx_error_to_conn_err),
+            Err(err) => Err(sqlx_error_to_conn_err(err)),
         }
     }
 
+    /// Close the pool, waiting for the connections in use to be returned to the pool
+    #[instrument(level = "trace")]
+    pub async fn close(&self) {
+        self.pool.close().await
+    }
+
+    /// Get the statistics of the pool
+    pub fn pool_stats(&self) -> PoolStats {
+        pool_stats(&self.pool, &self.waiting)
+    }
+
     pub(crate) fn set_metric_callback<F>(&mut self, callback: F)
     where
         F: Fn(&crate::metric::Info<'_>) + Send + Sync + 'static,
@@ -204,6 +360,57 @@ impl SqlxSqlitePoolConnection {
     }
 }
 
+fn sqlite_connect_options(
+    mut opt: SqliteConnectOptions,
+    sqlite: &SqliteOptions,
+    read_only: bool,
+) -> SqliteConnectOptions {
+    if let Some(journal_mode) = sqlite.journal_mode {
+        opt = opt.journal_mode(match journal_mode {
+            SqliteJournalMode::Delete => sqlx::sqlite::SqliteJournalMode::Delete,
+            SqliteJournalMode::Truncate => sqlx::sqlite::SqliteJournalMode::Truncate,
+            SqliteJournalMode::Persist => sqlx::sqlite::SqliteJournalMode::Persist,
+            SqliteJournalMode::Memory => sqlx::sqlite::SqliteJournalMode::Memory,
+            SqliteJournalMode::Wal => sqlx::sqlite::SqliteJournalMode::Wal,
+            SqliteJournalMode::Off => sqlx::sqlite::SqliteJournalMode::Off,
+        });
+    }
+    if let Some(synchronous) = sqlite.synchronous {
+        opt = opt.synchronous(match synchronous {
+            SqliteSynchronous::Off => sqlx::sqlite::SqliteSynchronous::Off,
+            SqliteSynchronous::Normal => sqlx::sqlite::SqliteSynchronous::Normal,
+            SqliteSynchronous::Full => sqlx::sqlite::SqliteSynchronous::Full,
+            SqliteSynchronous::Extra => sqlx::sqlite::SqliteSynchronous::Extra,
+        });
+    }
+    if let Some(busy_timeout) = sqlite.busy_timeout {
+        opt = opt.busy_timeout(busy_timeout);
+    }
+    if let Some(foreign_keys) = sqlite.foreign_keys {
+        opt = opt.foreign_keys(foreign_keys);
+    }
+    if let Some(create_if_missing) = sqlite.create_if_missing {
+        opt = opt.create_if_missing(create_if_missing);
+    }
+    if let Some(shared_cache) = sqlite.shared_cache {
+        opt = opt.shared_cache(shared_cache);
+    }
+    if let Some(cache_size) = sqlite.cache_size {
+        opt = opt.pragma("cache_size", cache_size.to_string());
+    }
+    if read_only {
+        opt = opt.read_only(true);
+    }
+    opt
+}
+
+/// Check if a SQLite URI opens an in-memory database, as `sqlite::memory:` or with `mode=memory`
+fn is_in_memory(url: &str) -> bool {
+    let database = url.trim_start_matches("sqlite:").trim_start_matches("//");
+    let (database, params) = database.split_once('?').unwrap_or((database, ""));
+    database == ":memory:" || params.split('&').any(|param| param == "mode=memory")
+}
+
 impl From<SqliteRow> for QueryResult {
     fn from(row: SqliteRow) -> QueryResult {
         QueryResult {
@@ -220,6 +427,78 @@ impl From<SqliteQueryResult> for ExecResult {
     }
 }
 
+/// Whether the linked SQLite library supports `RETURNING`, which was added in SQLite 3.35
+pub(crate) fn sqlite_support_returning() -> bool {
+    unsafe { libsqlite3_sys::sqlite3_libversion_number() >= 3_035_000 }
+}
+
+/// The handle to interrupt the statement running on a SQLite connection
+#[derive(Clone, Copy)]
+pub(crate) struct Interrupt(*mut libsqlite3_sys::sqlite3);
+
+// SAFETY: `sqlite3_interrupt` can be called from any thread, the handle is only used
+// while the connection it belongs to is borrowed by the running statement
+unsafe impl Send for Interrupt {}
+
+impl Interrupt {
+    pub(crate) fn new(conn: &mut SqliteConnection) -> Self {
+        // the handle is not used to make any other call than `sqlite3_interrupt`
+        #[allow(deprecated)]
+        Self(conn.as_raw_handle())
+    }
+
+    fn interrupt(self) {
+        // SAFETY: see `Send` above
+        unsafe { libsqlite3_sys::sqlite3_interrupt(self.0) }
+    }
+}
+
+/// Interrupts the statement if the future running it is dropped before completion
+struct InterruptOnDrop(Option<Interrupt>);
+
+impl Drop for InterruptOnDrop {
+    fn drop(&mut self) {
+        if let Some(interrupt) = self.0.take() {
+            interrupt.interrupt();
+        }
+    }
+}
+
+/// Run a query with a timeout if set. The statement is interrupted when the timeout expires,
+/// failing with an error of kind [DriverErrKind::Timeout], or when the future is dropped
+pub(crate) async fn with_interrupt<F, T>(
+    interrupt: Interrupt,
+    timeout: Option<Duration>,
+    query: F,
+) -> Result<T, sqlx::Error>
+where
+    F: Future<Output = Result<T, sqlx::Error>>,
+{
+    // The query is dropped before the statement is interrupted, so that the worker of the
+    // connection stops sending its results instead of running the statement again
+    let mut on_drop = InterruptOnDrop(Some(interrupt));
+    let query = Box::pin(query);
+    let res = match timeout {
+        Some(timeout) => {
+            match futures::future::select(query, Box::pin(crate::sleep(timeout))).await {
+                futures::future::Either::Left((res, _)) => res,
+                futures::future::Either::Right(((), query)) => {
+                    drop(query);
+                    Err(sqlx::Error::Io(std::io::Error::new(
+                        std::io::ErrorKind::TimedOut,
+                        "statement timeout expired",
+                    )))
+                }
+            }
+        }
+        None => query.await,
+    };
+    if res.is_ok() {
+        on_drop.0 = None;
+    }
+    res
+}
+
 pub(crate) fn sqlx_query(stmt: &Statement) -> sqlx::query::Query<'_, Sqlite, SqliteArguments> {
     let mut query = sqlx::query(&stmt.sql);
     if let Some(values) = &stmt.values {
@@ -227,3 +506,56 @@ pub(crate) fn sqlx_query(stmt: &Statement) -> sqlx::query::Query<'_, Sqlite, Sql
     }
     query
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn sqlite_connect_options_1() {
+        let base: SqliteConnectOptions = "sqlite://edge.db".parse().unwrap();
+        let sqlite = SqliteOptions {
+            journal_mode: Some(SqliteJournalMode::Wal),
+            synchronous: Some(SqliteSynchronous::Normal),
+            busy_timeout: Some(Duration::from_secs(5)),
+            foreign_keys: Some(false),
+            create_if_missing: Some(true),
+            shared_cache: Some(true),
+            cache_size: Some(-2000),
+            split_writer: true,
+        };
+
+        assert_eq!(
+            format!("{:?}", sqlite_connect_options(base.clone(), &sqlite, true)),
+            format!(
+                "{:?}",
+                base.clone()
+                    .journal_mode(sqlx::sqlite::SqliteJournalMode::Wal)
+                    .synchronous(sqlx::sqlite::SqliteSynchronous::Normal)
+                    .busy_timeout(Duration::from_secs(5))
+                    .foreign_keys(false)
+                    .create_if_missing(true)
+                    .shared_cache(true)
+                    .pragma("cache_size", "-2000")
+                    .read_only(true)
+            )
+        );
+        assert_eq!(
+            format!(
+                "{:?}",
+                sqlite_connect_options(base.clone(), &SqliteOptions::default(), false)
+            ),
+            format!("{:?}", base)
+        );
+    }
+
+    #[test]
+    fn is_in_memory_1() {
+        assert!(is_in_memory("sqlite::memory:"));
+        assert!(is_in_memory("sqlite://:memory:"));
+        assert!(is_in_memory("sqlite://edge.db?mode=memory&cache=shared"));
+        assert!(!is_in_memory("sqlite://edge.db"));
+        assert!(!is_in_memory("sqlite://edge.db?mode=rwc"));
+    }
+}
@@ -0,0 +1,723 @@
+use crate::{
+    ActiveModelBehavior, ActiveModelTrait, ColumnTrait, ConnectionTrait, DbBackend, DbErr,
+    EntityName, EntityTrait, ExecResult, IdenStatic, Identity, IntoActiveModel, Iterable,
+    ModelTrait, PrimaryKeyToColumn, PrimaryKeyTrait, QueryResult, Related, RelationTrait,
+    Statement, Value,
+};
+use std::{
+    str::FromStr,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+};
+
+/// A fake value of a type, for the fields of the models built by a [Factory]
+pub trait FakeValue: Sized {
+    /// The fake value of the column for the `seq`-th model built by a factory, starting from 1
+    fn fake_value(column: &str, seq: u64) -> Self;
+}
+
+/// Builds an [ActiveModel](ActiveModelTrait) with a fake value for each column,
+/// implemented by deriving `DeriveFactory` on the `Model`
+pub trait ActiveModelFactory: ActiveModelTrait + ActiveModelBehavior + Send + 'static
+where
+    <Self::Entity as EntityTrait>::Model: IntoActiveModel<Self>,
+{
+    /// The fake value of the column for the `seq`-th model built by a factory
+    fn fake_value(c: <Self::Entity as EntityTrait>::Column, seq: u64) -> Value;
+
+    /// Create a [Factory] of this ActiveModel
+    fn factory() -> Factory<Self> {
+        Factory::new()
+    }
+}
+
+type ValueFn = Arc<dyn Fn(u64) -> Value + Send + Sync>;
+
+/// Builds and inserts models with fake values, which can be overridden per column.
+///
+/// The columns of a `belongs_to` relation are set to the primary key of a parent row,
+/// created with the factory of the parent given to [Factory::belongs_to].
+/// Otherwise the columns are left to the database if they are nullable, and must be set if they are not.
+///
+/// ```
+/// # use sea_orm::{error::*, tests_cfg::*, *};
+/// #
+/// # let _: Result<(), DbErr> = smol::block_on(async {
+/// #
+/// # let db = MockDatabase::new(DbBackend::Postgres)
+/// #     .append_query_results(vec![
+/// #         vec![cake::Model { id: 1, name: "name 1".to_owned() }],
+/// #     ])
+/// #     .append_query_results(vec![
+/// #         vec![fruit::Model { id: 1, name: "Fruit 1".to_owned(), cake_id: Some(1) }],
+/// #     ])
+/// #     .into_connection();
+/// #
+/// let fruit = fruit::ActiveModel::factory()
+///     .sequence(fruit::Column::Name, |seq| format!("Fruit {}", seq))
+///     .belongs_to(cake::ActiveModel::factory())
+///     .create(&db)
+///     .await?;
+///
+/// assert_eq!(fruit.cake_id, Some(1));
+/// #
+/// # Ok(())
+/// # });
+/// ```
+pub struct Factory<A>
+where
+    A: ActiveModelFactory,
+    <A::Entity as EntityTrait>::Model: IntoActiveModel<A>,
+{
+    overrides: Vec<(<A::Entity as EntityTrait>::Column, ValueFn)>,
+    parents: Vec<Box<dyn ParentFactory>>,
+    error: Option<DbErr>,
+    seq: AtomicU64,
+}
+
+impl<A> std::fmt::Debug for Factory<A>
+where
+    A: ActiveModelFactory,
+    <A::Entity as EntityTrait>::Model: IntoActiveModel<A>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Factory")
+            .field("entity", &A::Entity::default().table_name())
+            .field(
+                "overrides",
+                &self
+                    .overrides
+                    .iter()
+                    .map(|(col, _)| col.as_str())
+                    .collect::<Vec<_>>(),
+            )
+            .field(
+                "parents",
+                &self
+                    .parents
+                    .iter()
+                    .map(|parent| parent.table_name())
+                    .collect::<Vec<_>>(),
+            )
+            .field("seq", &self.seq)
+            .finish()
+    }
+}
+
+impl<A> Default for Factory<A>
+where
+    A: ActiveModelFactory,
+    <A::Entity as EntityTrait>::Model: IntoActiveModel<A>,
+{
+    fn default() -> Self {
+        Self {
+            overrides: Vec::new(),
+            parents: Vec::new(),
+            error: None,
+            seq: AtomicU64::new(0),
+        }
+    }
+}
+
+impl<A> Factory<A>
+where
+    A: ActiveModelFactory,
+    <A::Entity as EntityTrait>::Model: IntoActiveModel<A>,
+{
+    /// Create a factory with the fake values of the ActiveModel
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the column to the value in every model
+    pub fn set<V>(self, col: <A::Entity as EntityTrait>::Column, value: V) -> Self
+    where
+        V: Into<Value> + Clone + Send + Sync + 'static,
+    {
+        self.sequence(col, move |_| value.clone())
+    }
+
+    /// Set the column to the value computed from the sequence number of the model, starting from 1,
+    /// e.g. for unique columns
+    pub fn sequence<F, V>(mut self, col: <A::Entity as EntityTrait>::Column, f: F) -> Self
+    where
+        F: Fn(u64) -> V + Send + Sync + 'static,
+        V: Into<Value>,
+    {
+        self.overrides.retain(|(c, _)| c.as_str() != col.as_str());
+        self.overrides
+            .push((col, Arc::new(move |seq| f(seq).into())));
+        self
+    }
+
+    /// Create a parent row with the factory for the `belongs_to` relation to its entity,
+    /// unless the columns of the relation are set.
+    ///
+    /// The entity must reference the parent with its own columns, creating the models fails
+    /// if the parent is a child of the entity or is related to it through another entity
+    pub fn belongs_to<P>(mut self, parent: Factory<P>) -> Self
+    where
+        P: ActiveModelFactory,
+        <P::Entity as EntityTrait>::Model: IntoActiveModel<P>,
+        A::Entity: Related<P::Entity>,
+    {
+        let table_name = A::Entity::default().table_name().to_owned();
+        let parent_name = P::Entity::default().table_name().to_owned();
+        if <A::Entity as Related<P::Entity>>::via().is_some() {
+            self.error.get_or_insert(DbErr::Custom(format!(
+                "`{}` is related to `{}` through another entity, `Factory::belongs_to` only takes the factory of a parent referenced by the columns of `{}`",
+                table_name, parent_name, table_name
+            )));
+            return self;
+        }
+        let rel = <A::Entity as Related<P::Entity>>::to();
+        if rel.is_owner {
+            self.error.get_or_insert(DbErr::Custom(format!(
+                "`{}` does not belong to `{}`, `Factory::belongs_to` only takes the factory of a parent referenced by the columns of `{}`",
+                table_name, parent_name, table_name
+            )));
+            return self;
+        }
+        self.parents.push(Box::new(Parent {
+            factory: parent,
+            from_cols: identity_names(&rel.from_col),
+            to_cols: identity_names(&rel.to_col),
+        }));
+        self
+    }
+
+    /// Build an ActiveModel with the next sequence number.
+    /// An auto increment primary key is left to the database, and the columns of `belongs_to` relations are not set
+    pub fn build(&self) -> A {
+        let seq = self.seq.fetch_add(1, Ordering::SeqCst) + 1;
+        let relation_cols = self.relation_cols();
+        let auto_increment_cols: Vec<String> =
+            if <<A::Entity as EntityTrait>::PrimaryKey as PrimaryKeyTrait>::auto_increment() {
+                <A::Entity as EntityTrait>::PrimaryKey::iter()
+                    .map(|key| key.into_column().as_str().to_owned())
+                    .collect()
+            } else {
+                Vec::new()
+            };
+
+        let mut am = A::default();
+        for col in <A::Entity as EntityTrait>::Column::iter() {
+            if let Some((_, f)) = self
+                .overrides
+                .iter()
+                .find(|(c, _)| c.as_str() == col.as_str())
+            {
+                am.set(col, f(seq));
+            } else if !auto_increment_cols
+                .iter()
+                .chain(relation_cols.iter())
+                .any(|name| name == col.as_str())
+            {
+                am.set(col, A::fake_value(col, seq));
+            }
+        }
+        am
+    }
+
+    /// Build an ActiveModel and insert it, after creating its parent rows
+    pub async fn create<C>(&self, db: &C) -> Result<<A::Entity as EntityTrait>::Model, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        self.create_in(&DynConnection(db)).await
+    }
+
+    /// Build and insert `n` models, each with its own parent rows
+    pub async fn create_many<C>(
+        &self,
+        n: usize,
+        db: &C,
+    ) -> Result<Vec<<A::Entity as EntityTrait>::Model>, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        let db = DynConnection(db);
+        let mut models = Vec::with_capacity(n);
+        for _ in 0..n {
+            models.push(self.create_in(&db).await?);
+        }
+        Ok(models)
+    }
+
+    async fn create_in(
+        &self,
+        db: &DynConnection<'_>,
+    ) -> Result<<A::Entity as EntityTrait>::Model, DbErr> {
+        if let Some(err) = &self.error {
+            return Err(err.clone());
+        }
+        let mut am = self.build();
+        let table_name = A::Entity::default().table_name().to_owned();
+
+        for rel in <A::Entity as EntityTrait>::Relation::iter().map(|rel| rel.def()) {
+            if rel.is_owner {
+                continue;
+            }
+            let from_cols = identity_names(&rel.from_col);
+            if from_cols.iter().any(|name| self.is_overridden(name))
+                || self
+                    .parents
+                    .iter()
+                    .any(|parent| parent.child_cols() == from_cols)
+            {
+                continue;
+            }
+            for name in from_cols.iter() {
+                let col = column::<A>(name)?;
+                if !col.def().null {
+                    return Err(DbErr::Custom(format!(
+                        "The column `{}` of `{}` belongs to a parent row, set it or give the factory of the parent to `Factory::belongs_to`",
+                        name, table_name
+                    )));
+                }
+            }
+        }
+
+        for parent in self.parents.iter() {
+            if parent
+                .child_cols()
+                .iter()
+                .any(|name| self.is_overridden(name))
+            {
+                continue;
+            }
+            let values = parent.create(db).await?;
+            for (name, value) in parent.child_cols().iter().zip(values) {
+                am.set(column::<A>(name)?, value);
+            }
+        }
+
+        am.insert(db).await
+    }
+
+    fn is_overridden(&self, name: &str) -> bool {
+        self.overrides.iter().any(|(col, _)| col.as_str() == name)
+    }
+
+    /// The columns of the `belongs_to` relations
+    fn relation_cols(&self) -> Vec<String> {
+        <A::Entity as EntityTrait>::Relation::iter()
+            .map(|rel| rel.def())
+            .filter(|rel| !rel.is_owner)
+            .flat_map(|rel| identity_names(&rel.from_col))
+            .collect()
+    }
+}
+
+fn column<A>(name: &str) -> Result<<A::Entity as EntityTrait>::Column, DbErr>
+where
+    A: ActiveModelTrait,
+{
+    <A::Entity as EntityTrait>::Column::from_str(name).map_err(|_| {
+        DbErr::Custom(format!(
+            "`{}` has no column `{}`",
+            A::Entity::default().table_name(),
+            name
+        ))
+    })
+}
+
+fn identity_names(identity: &Identity) -> Vec<String> {
+    match identity {
+        Identity::Unary(a) => vec![a.to_string()],
+        Identity::Binary(a, b) => vec![a.to_string(), b.to_string()],
+        Identity::Ternary(a, b, c) => vec![a.to_string(), b.to_string(), c.to_string()],
+    }
+}
+
+/// Creates the parent rows of a factory, regardless of the type of the parent
+#[async_trait::async_trait]
+trait ParentFactory: Send + Sync {
+    fn table_name(&self) -> String;
+
+    /// The columns of the child referencing the parent
+    fn child_cols(&self) -> &[String];
+
+    /// Create a parent row, returning the values of the columns referenced by the child
+    async fn create(&self, db: &DynConnection<'_>) -> Result<Vec<Value>, DbErr>;
+}
+
+struct Parent<P>
+where
+    P: ActiveModelFactory,
+    <P::Entity as EntityTrait>::Model: IntoActiveModel<P>,
+{
+    factory: Factory<P>,
+    from_cols: Vec<String>,
+    to_cols: Vec<String>,
+}
+
+#[async_trait::async_trait]
+impl<P> ParentFactory for Parent<P>
+where
+    P: ActiveModelFactory,
+    <P::Entity as EntityTrait>::Model: IntoActiveModel<P>,
+{
+    fn table_name(&self) -> String {
+        P::Entity::default().table_name().to_owned()
+    }
+
+    fn child_cols(&self) -> &[String] {
+        &self.from_cols
+    }
+
+    async fn create(&self, db: &DynConnection<'_>) -> Result<Vec<Value>, DbErr> {
+        let model = self.factory.create_in(db).await?;
+        self.to_cols
+            .iter()
+            .map(|name| Ok(model.get(column::<P>(name)?)))
+            .collect()
+    }
+}
+
+/// A connection of any type, for the parent factories
+struct DynConnection<'a>(&'a dyn ConnectionTrait);
+
+#[async_trait::async_trait]
+impl ConnectionTrait for DynConnection<'_> {
+    fn get_database_backend(&self) -> DbBackend {
+        self.0.get_database_backend()
+    }
+
+    async fn execute(&self, stmt: Statement) -> Result<ExecResult, DbErr> {
+        self.0.execute(stmt).await
+    }
+
+    async fn query_one(&self, stmt: Statement) -> Result<Option<QueryResult>, DbErr> {
+        self.0.query_one(stmt).await
+    }
+
+    async fn query_all(&self, stmt: Statement) -> Result<Vec<QueryResult>, DbErr> {
+        self.0.query_all(stmt).await
+    }
+
+    fn support_returning(&self) -> bool {
+        self.0.support_returning()
+    }
+
+    fn is_mock_connection(&self) -> bool {
+        self.0.is_mock_connection()
+    }
+}
+
+macro_rules! fake_number {
+    ( $( $type: ty ),* ) => {
+        $(
+            impl FakeValue for $type {
+                fn fake_value(_: &str, seq: u64) -> Self {
+                    seq as $type
+                }
+            }
+        )*
+    };
+}
+
+fake_number!(i8, i16, i32, i64, u8, u16, u32, u64, f32, f64);
+
+impl FakeValue for bool {
+    fn fake_value(_: &str, seq: u64) -> Self {
+        seq % 2 == 1
+    }
+}
+
+impl FakeValue for char {
+    fn fake_value(_: &str, seq: u64) -> Self {
+        (b'a' + (seq.saturating_sub(1) % 26) as u8) as char
+    }
+}
+
+impl FakeValue for String {
+    fn fake_value(column: &str, seq: u64) -> Self {
+        format!("{} {}", column, seq)
+    }
+}
+
+impl FakeValue for Vec<u8> {
+    fn fake_value(column: &str, seq: u64) -> Self {
+        String::fake_value(column, seq).into_bytes()
+    }
+}
+
+impl<T> FakeValue for Option<T>
+where
+    T: FakeValue,
+{
+    fn fake_value(column: &str, seq: u64) -> Self {
+        Some(T::fake_value(column, seq))
+    }
+}
+
+impl<T> FakeValue for T
+where
+    T: crate::ActiveEnum,
+{
+    /// The variants in turn, panics if the enum has no variants
+    fn fake_value(_: &str, seq: u64) -> Self {
+        let variants: Vec<T> = T::iter().collect();
+        if variants.is_empty() {
+            panic!(
+                "`{}` has no variants to fake a value from",
+                std::any::type_name::<T>()
+            );
+        }
+        let i = seq.saturating_sub(1) as usize % variants.len();
+        variants.into_iter().nth(i).unwrap()
+    }
+}
+
+#[cfg(feature = "with-json")]
+impl FakeValue for serde_json::Value {
+    fn fake_value(column: &str, seq: u64) -> Self {
+        serde_json::json!({ column: seq })
+    }
+}
+
+#[cfg(feature = "with-chrono")]
+impl FakeValue for chrono::NaiveDate {
+    /// A day from 2022-01-01
+    fn fake_value(_: &str, seq: u64) -> Self {
+        chrono::NaiveDate::from_ymd_opt(2022, 1, 1).unwrap()
+            + chrono::Duration::days(seq as i64 - 1)
+    }
+}
+
+#[cfg(feature = "with-chrono")]
+impl FakeValue for chrono::NaiveTime {
+    /// A minute from midnight
+    fn fake_value(_: &str, seq: u64) -> Self {
+        chrono::NaiveTime::from_hms_opt(0, 0, 0).unwrap()
+            + chrono::Duration::minutes((seq as i64 - 1) % (24 * 60))
+    }
+}
+
+#[cfg(feature = "with-chrono")]
+impl FakeValue for chrono::NaiveDateTime {
+    fn fake_value(column: &str, seq: u64) -> Self {
+        chrono::NaiveDate::fake_value(column, seq)
+            .and_time(chrono::NaiveTime::fake_value(column, seq))
+    }
+}
+
+#[cfg(feature = "with-chrono")]
+impl FakeValue for chrono::DateTime<chrono::Utc> {
+    fn fake_value(column: &str, seq: u64) -> Self {
+        chrono::TimeZone::from_utc_datetime(
+            &chrono::Utc,
+            &chrono::NaiveDateTime::fake_value(column, seq),
+        )
+    }
+}
+
+#[cfg(feature = "with-chrono")]
+impl FakeValue for chrono::DateTime<chrono::Local> {
+    fn fake_value(column: &str, seq: u64) -> Self {
+        chrono::DateTime::<chrono::Utc>::fake_value(column, seq).with_timezone(&chrono::Local)
+    }
+}
+
+#[cfg(feature = "with-chrono")]
+impl FakeValue for chrono::DateTime<chrono::FixedOffset> {
+    fn fake_value(column: &str, seq: u64) -> Self {
+        chrono::DateTime::<chrono::Utc>::fake_value(column, seq).into()
+    }
+}
+
+#[cfg(feature = "with-time")]
+impl FakeValue for time::Date {
+    /// A day from 2022-01-01
+    fn fake_value(_: &str, seq: u64) -> Self {
+        time::date!(2022 - 01 - 01) + time::Duration::days(seq as i64 - 1)
+    }
+}
+
+#[cfg(feature = "with-time")]
+impl FakeValue for time::Time {
+    /// A minute from midnight
+    fn fake_value(_: &str, seq: u64) -> Self {
+        time::time!(0:00) + time::Duration::minutes((seq as i64 - 1) % (24 * 60))
+    }
+}
+
+#[cfg(feature = "with-time")]
+impl FakeValue for time::PrimitiveDateTime {
+    fn fake_value(column: &str, seq: u64) -> Self {
+        time::Date::fake_value(column, seq).with_time(time::Time::fake_value(column, seq))
+    }
+}
+
+#[cfg(feature = "with-time")]
+impl FakeValue for time::OffsetDateTime {
+    fn fake_value(column: &str, seq: u64) -> Self {
+        time::PrimitiveDateTime::fake_value(column, seq).assume_utc()
+    }
+}
+
+#[cfg(feature = "with-uuid")]
+impl FakeValue for uuid::Uuid {
+    /// A random UUID
+    fn fake_value(_: &str, _: u64) -> Self {
+        uuid::Uuid::new_v4()
+    }
+}
+
+#[cfg(feature = "with-rust_decimal")]
+impl FakeValue for rust_decimal::Decimal {
+    fn fake_value(_: &str, seq: u64) -> Self {
+        rust_decimal::Decimal::from(seq)
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "mock")]
+mod tests {
+    use crate::tests_cfg::*;
+    use crate::{
+        ActiveModelFactory, ActiveValue, DbBackend, DbErr, FakeValue, MockDatabase, Statement,
+        Transaction,
+    };
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn build() {
+        let factory = cake::ActiveModel::factory();
+        assert_eq!(
+            factory.build(),
+            cake::ActiveModel {
+                id: ActiveValue::NotSet,
+                name: ActiveValue::Set("name 1".to_owned()),
+            }
+        );
+
+        let factory = fruit::ActiveModel::factory()
+            .sequence(fruit::Column::Name, |seq| format!("Fruit {}", seq))
+            .set(fruit::Column::CakeId, 3);
+        assert_eq!(
+            (factory.build(), factory.build()),
+            (
+                fruit::ActiveModel {
+                    id: ActiveValue::NotSet,
+                    name: ActiveValue::Set("Fruit 1".to_owned()),
+                    cake_id: ActiveValue::Set(Some(3)),
+                },
+                fruit::ActiveModel {
+                    id: ActiveValue::NotSet,
+                    name: ActiveValue::Set("Fruit 2".to_owned()),
+                    cake_id: ActiveValue::Set(Some(3)),
+                }
+            )
+        );
+    }
+
+    #[test]
+    fn fake_active_enum() {
+        use crate as sea_orm;
+        use crate::entity::prelude::*;
+
+        #[derive(Debug, Clone, PartialEq, EnumIter, DeriveActiveEnum)]
+        #[sea_orm(rs_type = "String", db_type = "String(Some(1))")]
+        enum Category {
+            #[sea_orm(string_value = "B")]
+            Big,
+            #[sea_orm(string_value = "S")]
+            Small,
+        }
+
+        assert_eq!(
+            (1..=3)
+                .map(|seq| Category::fake_value("category", seq))
+                .collect::<Vec<_>>(),
+            vec![Category::Big, Category::Small, Category::Big]
+        );
+        assert_eq!(Category::fake_value("category", 0), Category::Big);
+        assert_eq!(char::fake_value("grade", 0), 'a');
+    }
+
+    #[smol_potat::test]
+    async fn create_with_parents() -> Result<(), DbErr> {
+        let db = MockDatabase::new(DbBackend::Postgres)
+            .append_query_results(vec![vec![cake::Model {
+                id: 7,
+                name: "name 1".to_owned(),
+            }]])
+            .append_query_results(vec![vec![fruit::Model {
+                id: 1,
+                name: "name 1".to_owned(),
+                cake_id: Some(7),
+            }]])
+            .append_query_results(vec![vec![cake::Model {
+                id: 8,
+                name: "name 2".to_owned(),
+            }]])
+            .append_query_results(vec![vec![fruit::Model {
+                id: 2,
+                name: "name 2".to_owned(),
+                cake_id: Some(8),
+            }]])
+            .into_connection();
+
+        let fruits = fruit::ActiveModel::factory()
+            .belongs_to(cake::ActiveModel::factory())
+            .create_many(2, &db)
+            .await?;
+        assert_eq!(
+            fruits.iter().map(|fruit| fruit.cake_id).collect::<Vec<_>>(),
+            vec![Some(7), Some(8)]
+        );
+
+        let insert_cake = |name: &str| {
+            Statement::from_sql_and_values(
+                DbBackend::Postgres,
+                r#"INSERT INTO "cake" ("name") VALUES ($1) RETURNING "id", "name""#,
+                vec![name.into()],
+            )
+        };
+        let insert_fruit = |name: &str, cake_id: i32| {
+            Statement::from_sql_and_values(
+                DbBackend::Postgres,
+                r#"INSERT INTO "fruit" ("name", "cake_id") VALUES ($1, $2) RETURNING "id", "name", "cake_id""#,
+                vec![name.into(), cake_id.into()],
+            )
+        };
+        assert_eq!(
+            db.into_transaction_log(),
+            vec![
+                Transaction::one(insert_cake("name 1")),
+                Transaction::one(insert_fruit("name 1", 7)),
+                Transaction::one(insert_cake("name 2")),
+                Transaction::one(insert_fruit("name 2", 8)),
+            ]
+        );
+
+        let db = MockDatabase::new(DbBackend::Postgres).into_connection();
+        assert_eq!(
+            cake_filling::ActiveModel::factory().create(&db).await,
+            Err(DbErr::Custom(
+                "The column `cake_id` of `cake_filling` belongs to a parent row, set it or give the factory of the parent to `Factory::belongs_to`".to_owned()
+            ))
+        );
+
+        Ok(())
+    }
+
+    #[smol_potat::test]
+    async fn belongs_to_child() {
+        let db = MockDatabase::new(DbBackend::Postgres).into_connection();
+        assert_eq!(
+            cake::ActiveModel::factory()
+                .belongs_to(fruit::ActiveModel::factory())
+                .create(&db)
+                .await,
+            Err(DbErr::Custom(
+                "`cake` does not belong to `fruit`, `Factory::belongs_to` only takes the factory of a parent referenced by the columns of `cake`".to_owned()
+            ))
+        );
+        assert!(db.into_transaction_log().is_empty());
+    }
+}
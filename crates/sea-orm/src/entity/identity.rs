@@ -1,5 +1,5 @@
 use crate::{ColumnTrait, EntityTrait, IdenStatic};
-use sea_query::{Alias, DynIden, Iden, IntoIden, SeaRc};
+use sea_query::{Alias, DynIden, Iden, IntoIden, SeaRc, Value, ValueTuple};
 use std::fmt;
 
 /// Defines an operation for an Entity
@@ -32,6 +32,33 @@ impl Iden for Identity {
     }
 }
 
+impl Identity {
+    /// Get the columns of this [Identity] in order
+    pub(crate) fn columns(&self) -> Vec<DynIden> {
+        match self {
+            Identity::Unary(iden) => vec![SeaRc::clone(iden)],
+            Identity::Binary(iden1, iden2) => vec![SeaRc::clone(iden1), SeaRc::clone(iden2)],
+            Identity::Ternary(iden1, iden2, iden3) => vec![
+                SeaRc::clone(iden1),
+                SeaRc::clone(iden2),
+                SeaRc::clone(iden3),
+            ],
+        }
+    }
+}
+
+/// Flatten a [ValueTuple] into the values of each column
+pub(crate) fn value_tuple_to_vec(value_tuple: ValueTuple) -> Vec<Value> {
+    match value_tuple {
+        ValueTuple::One(v1) => vec![v1],
+        ValueTuple::Two(v1, v2) => vec![v1, v2],
+        ValueTuple::Three(v1, v2, v3) => vec![v1, v2, v3],
+        ValueTuple::Four(v1, v2, v3, v4) => vec![v1, v2, v3, v4],
+        ValueTuple::Five(v1, v2, v3, v4, v5) => vec![v1, v2, v3, v4, v5],
+        ValueTuple::Six(v1, v2, v3, v4, v5, v6) => vec![v1, v2, v3, v4, v5, v6],
+    }
+}
+
 /// Performs a conversion into an [Identity]
 pub trait IntoIdentity {
     /// Method to perform the conversion
@@ -99,9 +99,11 @@ mod active_enum;
 mod active_model;
 mod base_entity;
 mod column;
+mod factory;
 mod identity;
 mod link;
 mod model;
+mod partial_model;
 /// Re-export common types from the entity
 pub mod prelude;
 mod primary_key;
@@ -111,9 +113,11 @@ pub use active_enum::*;
 pub use active_model::*;
 pub use base_entity::*;
 pub use column::*;
+pub use factory::*;
 pub use identity::*;
 pub use link::*;
 pub use model::*;
+pub use partial_model::*;
 // pub use prelude::*;
 pub use primary_key::*;
 pub use relation::*;
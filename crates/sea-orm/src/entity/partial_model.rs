@@ -0,0 +1,111 @@
+use crate::{FromQueryResult, QuerySelect};
+
+/// A Trait for a partial Model, which selects only a subset of the columns of an Entity.
+/// It can be derived with `DerivePartialModel`.
+pub trait PartialModelTrait: FromQueryResult {
+    /// Select the columns this partial Model needs
+    fn select_cols<S: QuerySelect>(select: S) -> S {
+        Self::select_cols_nested(select, "")
+    }
+
+    /// Select the columns this partial Model needs, with the column aliases prefixed by `pre`
+    fn select_cols_nested<S: QuerySelect>(select: S, pre: &str) -> S;
+}
+
+#[cfg(test)]
+#[cfg(feature = "macros")]
+mod tests {
+    use crate as sea_orm;
+    use crate::tests_cfg::cake;
+    use crate::{entity::*, DbBackend, DerivePartialModel};
+    use pretty_assertions::assert_eq;
+    use sea_query::Expr;
+
+    #[derive(Debug, PartialEq, DerivePartialModel)]
+    #[sea_orm(entity = "cake::Entity")]
+    struct CakeName {
+        #[sea_orm(from_col = "name")]
+        cake_name: String,
+    }
+
+    #[derive(Debug, PartialEq, DerivePartialModel)]
+    #[sea_orm(entity = "cake::Entity")]
+    struct CakeSummary {
+        id: i32,
+        #[sea_orm(from_expr = "Expr::cust(\"1\")")]
+        one: i32,
+        #[sea_orm(nested)]
+        cake: CakeName,
+    }
+
+    #[test]
+    fn partial_model_1() {
+        assert_eq!(
+            DbBackend::MySql
+                .build(&cake::Entity::find().into_partial_model::<CakeName>().query)
+                .to_string(),
+            "SELECT `cake`.`name` AS `cake_name` FROM `cake`"
+        );
+    }
+
+    #[test]
+    fn partial_model_2() {
+        assert_eq!(
+            DbBackend::MySql
+                .build(
+                    &cake::Entity::find()
+                        .into_partial_model::<CakeSummary>()
+                        .query
+                )
+                .to_string(),
+            [
+                "SELECT `cake`.`id` AS `id`, 1 AS `one`,",
+                "`cake`.`name` AS `cake_cake_name` FROM `cake`",
+            ]
+            .join(" ")
+        );
+    }
+
+    #[smol_potat::test]
+    #[cfg(feature = "mock")]
+    async fn partial_model_3() -> Result<(), crate::DbErr> {
+        use crate::{MockDatabase, Transaction};
+
+        let db = MockDatabase::new(DbBackend::Postgres)
+            .append_query_results(vec![vec![maplit::btreemap! {
+                "id" => Into::<Value>::into(1),
+                "one" => Into::<Value>::into(1),
+                "cake_cake_name" => Into::<Value>::into("Cheese Cake"),
+            }]])
+            .into_connection();
+
+        assert_eq!(
+            cake::Entity::find()
+                .into_partial_model::<CakeSummary>()
+                .all(&db)
+                .await?,
+            vec![CakeSummary {
+                id: 1,
+                one: 1,
+                cake: CakeName {
+                    cake_name: "Cheese Cake".to_owned(),
+                },
+            }]
+        );
+
+        assert_eq!(
+            db.into_transaction_log(),
+            vec![Transaction::from_sql_and_values(
+                DbBackend::Postgres,
+                [
+                    r#"SELECT "cake"."id" AS "id", 1 AS "one","#,
+                    r#""cake"."name" AS "cake_cake_name" FROM "cake""#,
+                ]
+                .join(" ")
+                .as_str(),
+                vec![]
+            )]
+        );
+        Ok(())
+    }
+}
@@ -1,15 +1,15 @@
 pub use crate::{
-    error::*, ActiveEnum, ActiveModelBehavior, ActiveModelTrait, ColumnDef, ColumnTrait,
-    ColumnType, DatabaseConnection, DbConn, EntityName, EntityTrait, EnumIter, ForeignKeyAction,
-    Iden, IdenStatic, Linked, ModelTrait, PaginatorTrait, PrimaryKeyToColumn, PrimaryKeyTrait,
-    QueryFilter, QueryResult, Related, RelationDef, RelationTrait, Select, Value,
+    error::*, ActiveEnum, ActiveModelBehavior, ActiveModelFactory, ActiveModelTrait, ColumnDef,
+    ColumnTrait, ColumnType, DatabaseConnection, DbConn, EntityName, EntityTrait, EnumIter,
+    ForeignKeyAction, Iden, IdenStatic, Linked, ModelTrait, PaginatorTrait, PrimaryKeyToColumn,
+    PrimaryKeyTrait, QueryFilter, QueryResult, Related, RelationDef, RelationTrait, Select, Value,
 };
 
 #[cfg(feature = "macros")]
 pub use crate::{
     DeriveActiveEnum, DeriveActiveModel, DeriveActiveModelBehavior, DeriveColumn,
-    DeriveCustomColumn, DeriveEntity, DeriveEntityModel, DeriveIntoActiveModel, DeriveModel,
-    DerivePrimaryKey, DeriveRelation,
+    DeriveCustomColumn, DeriveEntity, DeriveEntityModel, DeriveFactory, DeriveIntoActiveModel,
+    DeriveModel, DerivePartialModel, DerivePrimaryKey, DeriveRelation,
 };
 
 #[cfg(feature = "with-json")]
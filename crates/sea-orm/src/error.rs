@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 /// An error from unsuccessful database operations
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub enum DbErr {
@@ -7,8 +9,12 @@ pub enum DbErr {
     Exec(String),
     /// An error occurred while performing a query
     Query(String),
+    /// An error reported by the database driver while executing a statement, classified by its [DriverErrKind]
+    Driver(Box<DriverErr>),
     /// The record was not found in the database
     RecordNotFound(String),
+    /// None of the records were inserted, e.g. because of `ON CONFLICT DO NOTHING`
+    RecordNotInserted,
     /// A custom error
     Custom(String),
     /// Error occurred while parsing value as target type
@@ -19,7 +25,41 @@ pub enum DbErr {
     Migration(String),
 }
 
-impl std::error::Error for DbErr {}
+impl std::error::Error for DbErr {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Driver(err) => Some(err.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+impl DbErr {
+    /// Get the error reported by the database driver, if any
+    pub fn driver_err(&self) -> Option<&DriverErr> {
+        match self {
+            Self::Driver(err) => Some(err.as_ref()),
+            _ => None,
+        }
+    }
+
+    /// Get the classification of the error reported by the database driver, if known
+    pub fn kind(&self) -> Option<DriverErrKind> {
+        self.driver_err().and_then(|err| err.kind)
+    }
+
+    /// Check if the error is caused by a concurrent transaction, so the transaction can be retried
+    pub fn is_retryable(&self) -> bool {
+        matches!(
+            self.kind(),
+            Some(
+                DriverErrKind::Deadlock
+                    | DriverErrKind::SerializationFailure
+                    | DriverErrKind::LockTimeout
+            )
+        )
+    }
+}
 
 impl std::fmt::Display for DbErr {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
@@ -27,7 +67,12 @@ impl std::fmt::Display for DbErr {
             Self::Conn(s) => write!(f, "Connection Error: {}", s),
             Self::Exec(s) => write!(f, "Execution Error: {}", s),
             Self::Query(s) => write!(f, "Query Error: {}", s),
+            Self::Driver(e) => write!(f, "Driver Error: {}", e),
             Self::RecordNotFound(s) => write!(f, "RecordNotFound Error: {}", s),
+            Self::RecordNotInserted => write!(
+                f,
+                "RecordNotInserted Error: None of the records are inserted"
+            ),
             Self::Custom(s) => write!(f, "Custom Error: {}", s),
             Self::Type(s) => write!(f, "Type Error: {}", s),
             Self::Json(s) => write!(f, "Json Error: {}", s),
@@ -36,6 +81,219 @@ impl std::fmt::Display for DbErr {
     }
 }
 
+/// The classification of an error reported by the database driver
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DriverErrKind {
+    /// A unique constraint or the primary key is violated
+    UniqueViolation,
+    /// A foreign key constraint is violated
+    ForeignKeyViolation,
+    /// A NULL value is given to a NOT NULL column
+    NotNullViolation,
+    /// A check constraint is violated
+    CheckViolation,
+    /// The transaction is aborted to resolve a deadlock
+    Deadlock,
+    /// The transaction cannot be serialized with a concurrent transaction
+    SerializationFailure,
+    /// A lock cannot be acquired in time, or the database is locked
+    LockTimeout,
+    /// The connection to the database is lost
+    ConnectionLost,
+    /// The statement or the connection acquisition timed out
+    Timeout,
+}
+
+/// An error reported by the database driver, with the details reported by the backend
+#[derive(Debug, Clone, Default)]
+pub struct DriverErr {
+    /// The classification of the error, if known
+    pub kind: Option<DriverErrKind>,
+    /// The SQLSTATE code, reported by MySQL and Postgres
+    pub code: Option<String>,
+    /// The vendor code, i.e. the error number of MySQL or the extended result code of SQLite
+    pub vendor_code: Option<i32>,
+    /// The error message
+    pub message: String,
+    /// The name of the violated constraint
+    pub constraint: Option<String>,
+    /// The name of the table related to the error
+    pub table: Option<String>,
+    /// The name of the column related to the error
+    pub column: Option<String>,
+    /// The error returned by the driver
+    pub source: Option<Arc<dyn std::error::Error + Send + Sync>>,
+}
+
+impl PartialEq for DriverErr {
+    fn eq(&self, other: &Self) -> bool {
+        self.kind == other.kind
+            && self.code == other.code
+            && self.vendor_code == other.vendor_code
+            && self.message == other.message
+            && self.constraint == other.constraint
+            && self.table == other.table
+            && self.column == other.column
+    }
+}
+
+impl Eq for DriverErr {}
+
+impl std::error::Error for DriverErr {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.source
+            .as_ref()
+            .map(|err| err.as_ref() as &(dyn std::error::Error + 'static))
+    }
+}
+
+impl std::fmt::Display for DriverErr {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match &self.code {
+            Some(code) => write!(f, "{} ({})", self.message, code),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+#[cfg(any(feature = "sqlx-dep", test))]
+impl DriverErr {
+    #[cfg(any(feature = "sqlx-postgres", test))]
+    /// Classify an error reported by Postgres, the names are reported by Postgres as fields of the error
+    pub(crate) fn from_postgres(code: &str, message: &str) -> Self {
+        let kind = match code {
+            "23505" => Some(DriverErrKind::UniqueViolation),
+            "23503" => Some(DriverErrKind::ForeignKeyViolation),
+            "23502" => Some(DriverErrKind::NotNullViolation),
+            "23514" => Some(DriverErrKind::CheckViolation),
+            "40P01" => Some(DriverErrKind::Deadlock),
+            "40001" => Some(DriverErrKind::SerializationFailure),
+            "55P03" => Some(DriverErrKind::LockTimeout),
+            "57014" => Some(DriverErrKind::Timeout),
+            "57P01" | "57P02" | "57P03" => Some(DriverErrKind::ConnectionLost),
+            _ if code.starts_with("08") => Some(DriverErrKind::ConnectionLost),
+            _ => None,
+        };
+        Self {
+            kind,
+            code: Some(code.to_owned()),
+            message: message.to_owned(),
+            ..Default::default()
+        }
+    }
+
+    #[cfg(any(feature = "sqlx-mysql", test))]
+    /// Classify an error reported by MySQL, the names are parsed from the error message
+    pub(crate) fn from_mysql(number: u16, code: Option<&str>, message: &str) -> Self {
+        let mut err = Self {
+            code: code.map(ToOwned::to_owned),
+            vendor_code: Some(number.into()),
+            message: message.to_owned(),
+            ..Default::default()
+        };
+        match number {
+            // Duplicate entry '1' for key 'PRIMARY', the key is prefixed by the table since MySQL 8
+            1062 | 1586 => {
+                err.kind = Some(DriverErrKind::UniqueViolation);
+                if let Some(key) = between(message, "for key '", "'") {
+                    match key.rsplit_once('.') {
+                        Some((table, key)) => {
+                            err.table = Some(table.to_owned());
+                            err.constraint = Some(key.to_owned());
+                        }
+                        None => err.constraint = Some(key.to_owned()),
+                    }
+                }
+            }
+            // Cannot add or update a child row: a foreign key constraint fails
+            // (`db`.`fruit`, CONSTRAINT `fk-fruit-cake` FOREIGN KEY (`cake_id`) REFERENCES ...)
+            1216 | 1217 | 1451 | 1452 => {
+                err.kind = Some(DriverErrKind::ForeignKeyViolation);
+                err.table = between(message, "fails (", ", CONSTRAINT")
+                    .and_then(|table| table.rsplit('.').next())
+                    .map(|table| table.trim_matches('`').to_owned());
+                err.constraint = between(message, "CONSTRAINT `", "`").map(ToOwned::to_owned);
+                err.column = between(message, "FOREIGN KEY (`", "`").map(ToOwned::to_owned);
+            }
+            // Column 'name' cannot be null / Field 'name' doesn't have a default value
+            1048 | 1364 => {
+                err.kind = Some(DriverErrKind::NotNullViolation);
+                err.column = between(message, "'", "'").map(ToOwned::to_owned);
+            }
+            // Check constraint 'name' is violated.
+            3819 => {
+                err.kind = Some(DriverErrKind::CheckViolation);
+                err.constraint = between(message, "'", "'").map(ToOwned::to_owned);
+            }
+            1213 => err.kind = Some(DriverErrKind::Deadlock),
+            1205 => err.kind = Some(DriverErrKind::LockTimeout),
+            3024 => err.kind = Some(DriverErrKind::Timeout),
+            2006 | 2013 => err.kind = Some(DriverErrKind::ConnectionLost),
+            _ => {}
+        }
+        err
+    }
+
+    #[cfg(any(feature = "sqlx-sqlite", test))]
+    /// Classify an error reported by SQLite, the names are parsed from the error message
+    pub(crate) fn from_sqlite(code: Option<&str>, message: &str) -> Self {
+        let mut err = Self {
+            vendor_code: code.and_then(|code| code.parse().ok()),
+            message: message.to_owned(),
+            ..Default::default()
+        };
+        let table_column = |prefix: &str| {
+            message
+                .strip_prefix(prefix)
+                .and_then(|names| names.split(", ").next())
+                .and_then(|name| name.split_once('.'))
+                .map(|(table, column)| (Some(table.to_owned()), Some(column.to_owned())))
+                .unwrap_or_default()
+        };
+        match err.vendor_code {
+            // UNIQUE constraint failed: cake.name
+            Some(2067) | Some(1555) => {
+                err.kind = Some(DriverErrKind::UniqueViolation);
+                let (table, column) = table_column("UNIQUE constraint failed: ");
+                err.table = table;
+                err.column = column;
+            }
+            Some(787) => err.kind = Some(DriverErrKind::ForeignKeyViolation),
+            // NOT NULL constraint failed: cake.name
+            Some(1299) => {
+                err.kind = Some(DriverErrKind::NotNullViolation);
+                let (table, column) = table_column("NOT NULL constraint failed: ");
+                err.table = table;
+                err.column = column;
+            }
+            // CHECK constraint failed: name
+            Some(275) => {
+                err.kind = Some(DriverErrKind::CheckViolation);
+                err.constraint = message
+                    .strip_prefix("CHECK constraint failed: ")
+                    .map(ToOwned::to_owned);
+            }
+            // SQLITE_INTERRUPT, the statement is interrupted when its timeout expires
+            Some(9) => err.kind = Some(DriverErrKind::Timeout),
+            // the primary result code is the least significant byte of the extended result code,
+            // SQLITE_BUSY and SQLITE_LOCKED
+            Some(code) if matches!(code & 0xff, 5 | 6) => {
+                err.kind = Some(DriverErrKind::LockTimeout)
+            }
+            _ => {}
+        }
+        err
+    }
+}
+
+/// Get the text between the first `start` and the following `end`
+#[cfg(any(feature = "sqlx-mysql", test))]
+fn between<'a>(message: &'a str, start: &str, end: &str) -> Option<&'a str> {
+    let (_, rest) = message.split_once(start)?;
+    let (text, _) = rest.split_once(end)?;
+    Some(text)
+}
+
 /// An error from a failed column operation when trying to convert the column to a string
 #[derive(Debug, Clone)]
 pub struct ColumnFromStrErr(pub String);
@@ -47,3 +305,118 @@ impl std::fmt::Display for ColumnFromStrErr {
         write!(f, "{}", self.0.as_str())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn driver_err_postgres() {
+        let err = DriverErr::from_postgres(
+            "23505",
+            r#"duplicate key value violates unique constraint "cake_name_key""#,
+        );
+        assert_eq!(err.kind, Some(DriverErrKind::UniqueViolation));
+        assert_eq!(err.code.as_deref(), Some("23505"));
+
+        assert_eq!(
+            DriverErr::from_postgres("40001", "could not serialize access").kind,
+            Some(DriverErrKind::SerializationFailure)
+        );
+        assert_eq!(
+            DriverErr::from_postgres("08006", "connection failure").kind,
+            Some(DriverErrKind::ConnectionLost)
+        );
+        assert_eq!(DriverErr::from_postgres("42601", "syntax error").kind, None);
+    }
+
+    #[test]
+    fn driver_err_mysql() {
+        assert_eq!(
+            DriverErr::from_mysql(
+                1062,
+                Some("23000"),
+                "Duplicate entry 'Apple Pie' for key 'cake.name'"
+            ),
+            DriverErr {
+                kind: Some(DriverErrKind::UniqueViolation),
+                code: Some("23000".to_owned()),
+                vendor_code: Some(1062),
+                message: "Duplicate entry 'Apple Pie' for key 'cake.name'".to_owned(),
+                constraint: Some("name".to_owned()),
+                table: Some("cake".to_owned()),
+                ..Default::default()
+            }
+        );
+
+        let message = "Cannot add or update a child row: a foreign key constraint fails (`sea`.`fruit`, CONSTRAINT `fk-fruit-cake` FOREIGN KEY (`cake_id`) REFERENCES `cake` (`id`))";
+        let err = DriverErr::from_mysql(1452, Some("23000"), message);
+        assert_eq!(err.kind, Some(DriverErrKind::ForeignKeyViolation));
+        assert_eq!(err.table.as_deref(), Some("fruit"));
+        assert_eq!(err.constraint.as_deref(), Some("fk-fruit-cake"));
+        assert_eq!(err.column.as_deref(), Some("cake_id"));
+
+        let err = DriverErr::from_mysql(1048, Some("23000"), "Column 'name' cannot be null");
+        assert_eq!(err.kind, Some(DriverErrKind::NotNullViolation));
+        assert_eq!(err.column.as_deref(), Some("name"));
+
+        let err = DriverErr::from_mysql(
+            3819,
+            Some("HY000"),
+            "Check constraint 'price_positive' is violated.",
+        );
+        assert_eq!(err.kind, Some(DriverErrKind::CheckViolation));
+        assert_eq!(err.constraint.as_deref(), Some("price_positive"));
+
+        assert!(DbErr::Driver(Box::new(DriverErr::from_mysql(
+            1213,
+            Some("40001"),
+            "Deadlock found when trying to get lock; try restarting transaction"
+        )))
+        .is_retryable());
+    }
+
+    #[test]
+    fn retryable() {
+        assert!(DbErr::Driver(Box::new(DriverErr::from_postgres(
+            "40001",
+            "could not serialize access due to concurrent update"
+        )))
+        .is_retryable());
+        assert!(!DbErr::Exec("syntax error".to_owned()).is_retryable());
+        assert!(!DbErr::Driver(Box::new(DriverErr::from_postgres(
+            "23505",
+            "duplicate key value violates unique constraint"
+        )))
+        .is_retryable());
+    }
+
+    #[test]
+    fn driver_err_sqlite() {
+        let err = DriverErr::from_sqlite(Some("2067"), "UNIQUE constraint failed: cake.name");
+        assert_eq!(err.kind, Some(DriverErrKind::UniqueViolation));
+        assert_eq!(err.vendor_code, Some(2067));
+        assert_eq!(err.table.as_deref(), Some("cake"));
+        assert_eq!(err.column.as_deref(), Some("name"));
+
+        let err = DriverErr::from_sqlite(Some("1299"), "NOT NULL constraint failed: fruit.name");
+        assert_eq!(err.kind, Some(DriverErrKind::NotNullViolation));
+        assert_eq!(err.table.as_deref(), Some("fruit"));
+        assert_eq!(err.column.as_deref(), Some("name"));
+
+        let err = DriverErr::from_sqlite(Some("787"), "FOREIGN KEY constraint failed");
+        assert_eq!(err.kind, Some(DriverErrKind::ForeignKeyViolation));
+
+        let err = DriverErr::from_sqlite(Some("275"), "CHECK constraint failed: price_positive");
+        assert_eq!(err.kind, Some(DriverErrKind::CheckViolation));
+        assert_eq!(err.constraint.as_deref(), Some("price_positive"));
+
+        let err = DriverErr::from_sqlite(Some("517"), "database is locked");
+        assert_eq!(err.kind, Some(DriverErrKind::LockTimeout));
+        assert!(DbErr::Driver(Box::new(err)).is_retryable());
+
+        let err = DriverErr::from_sqlite(Some("9"), "interrupted");
+        assert_eq!(err.kind, Some(DriverErrKind::Timeout));
+    }
+}
@@ -0,0 +1,401 @@
+use crate::{
+    error::*, ColumnTrait, ConnectionTrait, DbBackend, EntityTrait, IntoSimpleExpr, QuerySelect,
+    QueryTrait, Select, TryGetable,
+};
+use sea_query::{Alias, ColumnType, Expr, Func, Order, SelectStatement, SimpleExpr, ValueType};
+
+const AGGREGATE_INPUT: &str = "aggregate_input";
+const AGGREGATE_VALUE: &str = "aggregate_value";
+const GROUP_KEY: &str = "group_key";
+const GROUP_COUNT: &str = "group_count";
+const SUB_QUERY: &str = "sub_query";
+
+/// Aggregate functions on a [Select]. Each of them returns `None` when there are no rows
+/// to aggregate, or when all the aggregated values are NULL. The rows are aggregated
+/// regardless of the order, limit and offset of the [Select].
+///
+/// The SQL type of an aggregate depends on the database backend, e.g. `SUM` of an integer
+/// column yields `BIGINT` on Postgres and `DECIMAL` on MySQL, so the aggregate is cast to
+/// the SQL type of `T` where needed: integers are cast to `BIGINT` on Postgres (`INTEGER`
+/// for `i32` and `SMALLINT` for `i16`), `SIGNED` or `UNSIGNED` on MySQL and `INTEGER` on SQLite,
+/// floats to `DOUBLE PRECISION` (or `REAL`) on Postgres, `DOUBLE` (or `FLOAT`) on MySQL 8.0.17
+/// or later and `REAL` on SQLite, and `Decimal` to `NUMERIC` on Postgres.
+impl<E> Select<E>
+where
+    E: EntityTrait,
+{
+    /// Get the sum of an expression
+    ///
+    /// ```
+    /// # use sea_orm::{error::*, tests_cfg::*, *};
+    /// #
+    /// # #[smol_potat::main]
+    /// # #[cfg(feature = "mock")]
+    /// # pub async fn main() -> Result<(), DbErr> {
+    /// #
+    /// # let db = MockDatabase::new(DbBackend::Postgres)
+    /// #     .append_query_results(vec![
+    /// #         vec![maplit::btreemap! {
+    /// #             "aggregate_value" => Into::<Value>::into(6i64),
+    /// #         }],
+    /// #         vec![maplit::btreemap! {
+    /// #             "aggregate_value" => Value::BigInt(None),
+    /// #         }],
+    /// #     ])
+    /// #     .into_connection();
+    /// #
+    /// use sea_orm::{entity::*, query::*, tests_cfg::fruit};
+    ///
+    /// let sum: Option<i64> = fruit::Entity::find().sum(fruit::Column::Id, &db).await?;
+    /// assert_eq!(sum, Some(6));
+    ///
+    /// let sum: Option<i64> = fruit::Entity::find()
+    ///     .filter(fruit::Column::Id.gt(100))
+    ///     .sum(fruit::Column::Id, &db)
+    ///     .await?;
+    /// assert_eq!(sum, None);
+    ///
+    /// assert_eq!(
+    ///     db.into_transaction_log(),
+    ///     vec![
+    ///         Transaction::from_sql_and_values(
+    ///             DbBackend::Postgres,
+    ///             [
+    ///                 r#"SELECT CAST(SUM("aggregate_input") AS BIGINT) AS "aggregate_value""#,
+    ///                 r#"FROM (SELECT "fruit"."id" AS "aggregate_input" FROM "fruit") AS "sub_query""#,
+    ///             ]
+    ///             .join(" ")
+    ///             .as_str(),
+    ///             vec![]
+    ///         ),
+    ///         Transaction::from_sql_and_values(
+    ///             DbBackend::Postgres,
+    ///             [
+    ///                 r#"SELECT CAST(SUM("aggregate_input") AS BIGINT) AS "aggregate_value""#,
+    ///                 r#"FROM (SELECT "fruit"."id" AS "aggregate_input" FROM "fruit""#,
+    ///                 r#"WHERE "fruit"."id" > $1) AS "sub_query""#,
+    ///             ]
+    ///             .join(" ")
+    ///             .as_str(),
+    ///             vec![100i32.into()]
+    ///         ),
+    ///     ]
+    /// );
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn sum<T, X, C>(self, expr: X, db: &C) -> Result<Option<T>, DbErr>
+    where
+        T: TryGetable + ValueType,
+        X: IntoSimpleExpr,
+        C: ConnectionTrait,
+    {
+        self.aggregate(Func::sum::<SimpleExpr>, expr.into_simple_expr(), db)
+            .await
+    }
+
+    /// Get the average of an expression
+    pub async fn avg<T, X, C>(self, expr: X, db: &C) -> Result<Option<T>, DbErr>
+    where
+        T: TryGetable + ValueType,
+        X: IntoSimpleExpr,
+        C: ConnectionTrait,
+    {
+        self.aggregate(Func::avg::<SimpleExpr>, expr.into_simple_expr(), db)
+            .await
+    }
+
+    /// Get the minimum of an expression
+    pub async fn min<T, X, C>(self, expr: X, db: &C) -> Result<Option<T>, DbErr>
+    where
+        T: TryGetable + ValueType,
+        X: IntoSimpleExpr,
+        C: ConnectionTrait,
+    {
+        self.aggregate(Func::min::<SimpleExpr>, expr.into_simple_expr(), db)
+            .await
+    }
+
+    /// Get the maximum of an expression
+    pub async fn max<T, X, C>(self, expr: X, db: &C) -> Result<Option<T>, DbErr>
+    where
+        T: TryGetable + ValueType,
+        X: IntoSimpleExpr,
+        C: ConnectionTrait,
+    {
+        self.aggregate(Func::max::<SimpleExpr>, expr.into_simple_expr(), db)
+            .await
+    }
+
+    /// Count the rows in each group of a column, ordered by the column
+    ///
+    /// ```
+    /// # use sea_orm::{error::*, tests_cfg::*, *};
+    /// #
+    /// # #[smol_potat::main]
+    /// # #[cfg(feature = "mock")]
+    /// # pub async fn main() -> Result<(), DbErr> {
+    /// #
+    /// # let db = MockDatabase::new(DbBackend::Postgres)
+    /// #     .append_query_results(vec![vec![
+    /// #         maplit::btreemap! {
+    /// #             "group_key" => Value::Int(None),
+    /// #             "group_count" => Into::<Value>::into(1i64),
+    /// #         },
+    /// #         maplit::btreemap! {
+    /// #             "group_key" => Into::<Value>::into(1),
+    /// #             "group_count" => Into::<Value>::into(3i64),
+    /// #         },
+    /// #     ]])
+    /// #     .into_connection();
+    /// #
+    /// use sea_orm::{entity::*, query::*, tests_cfg::fruit};
+    ///
+    /// let counts: Vec<(Option<i32>, i64)> = fruit::Entity::find()
+    ///     .group_count(fruit::Column::CakeId, &db)
+    ///     .await?;
+    ///
+    /// assert_eq!(counts, vec![(None, 1), (Some(1), 3)]);
+    ///
+    /// assert_eq!(
+    ///     db.into_transaction_log(),
+    ///     vec![Transaction::from_sql_and_values(
+    ///         DbBackend::Postgres,
+    ///         [
+    ///             r#"SELECT "group_key", COUNT(*) AS "group_count""#,
+    ///             r#"FROM (SELECT "fruit"."cake_id" AS "group_key" FROM "fruit") AS "sub_query""#,
+    ///             r#"GROUP BY "group_key" ORDER BY "group_key" ASC"#,
+    ///         ]
+    ///         .join(" ")
+    ///         .as_str(),
+    ///         vec![]
+    ///     )]
+    /// );
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn group_count<K, X, C>(self, col: X, db: &C) -> Result<Vec<(K, i64)>, DbErr>
+    where
+        K: TryGetable,
+        X: ColumnTrait,
+        C: ConnectionTrait,
+    {
+        let query = SelectStatement::new()
+            .column(Alias::new(GROUP_KEY))
+            .expr_as(Func::count(Expr::asterisk()), Alias::new(GROUP_COUNT))
+            .from_subquery(self.aggregated_rows(col, GROUP_KEY), Alias::new(SUB_QUERY))
+            .group_by_col(Alias::new(GROUP_KEY))
+            .order_by(Alias::new(GROUP_KEY), Order::Asc)
+            .to_owned();
+        let builder = db.get_database_backend();
+        let rows = db.query_all(builder.build(&query)).await?;
+        rows.iter()
+            .map(|row| Ok((row.try_get("", GROUP_KEY)?, row.try_get("", GROUP_COUNT)?)))
+            .collect()
+    }
+
+    async fn aggregate<T, C>(
+        self,
+        func: fn(SimpleExpr) -> SimpleExpr,
+        expr: SimpleExpr,
+        db: &C,
+    ) -> Result<Option<T>, DbErr>
+    where
+        T: TryGetable + ValueType,
+        C: ConnectionTrait,
+    {
+        let builder = db.get_database_backend();
+        let mut aggregate = func(Expr::col(Alias::new(AGGREGATE_INPUT)).into());
+        if let Some(type_name) = cast_type::<T>(builder) {
+            aggregate = aggregate.cast_as(Alias::new(type_name));
+        }
+        let query = SelectStatement::new()
+            .expr_as(aggregate, Alias::new(AGGREGATE_VALUE))
+            .from_subquery(
+                self.aggregated_rows(expr, AGGREGATE_INPUT),
+                Alias::new(SUB_QUERY),
+            )
+            .to_owned();
+        match db.query_one(builder.build(&query)).await? {
+            Some(row) => row.try_get("", AGGREGATE_VALUE),
+            None => Ok(None),
+        }
+    }
+
+    /// Select the aggregated expression of every row, dropping the limit and offset,
+    /// while the order has no effect in a subquery
+    fn aggregated_rows<X>(self, expr: X, alias: &str) -> SelectStatement
+    where
+        X: IntoSimpleExpr,
+    {
+        self.select_only()
+            .column_as(expr, alias)
+            .into_query()
+            .reset_limit()
+            .reset_offset()
+            .to_owned()
+    }
+}
+
+/// The SQL type to cast an aggregate to, so that it can be decoded as `T` on the database backend
+fn cast_type<T>(db_backend: DbBackend) -> Option<&'static str>
+where
+    T: ValueType,
+{
+    use ColumnType::*;
+    match (db_backend, T::column_type()) {
+        (DbBackend::Postgres, SmallInteger(_)) => Some("SMALLINT"),
+        (DbBackend::Postgres, Integer(_)) => Some("INTEGER"),
+        (DbBackend::Postgres, BigInteger(_)) => Some("BIGINT"),
+        (DbBackend::Postgres, Float(_)) => Some("REAL"),
+        (DbBackend::Postgres, Double(_)) => Some("DOUBLE PRECISION"),
+        (DbBackend::Postgres, Decimal(_)) => Some("NUMERIC"),
+        (DbBackend::MySql, TinyInteger(_) | SmallInteger(_) | Integer(_) | BigInteger(_)) => {
+            Some("SIGNED")
+        }
+        (DbBackend::MySql, TinyUnsigned(_) | SmallUnsigned(_) | Unsigned(_) | BigUnsigned(_)) => {
+            Some("UNSIGNED")
+        }
+        (DbBackend::MySql, Float(_)) => Some("FLOAT"),
+        (DbBackend::MySql, Double(_)) => Some("DOUBLE"),
+        (
+            DbBackend::Sqlite,
+            TinyInteger(_) | SmallInteger(_) | Integer(_) | BigInteger(_) | TinyUnsigned(_)
+            | SmallUnsigned(_) | Unsigned(_) | BigUnsigned(_),
+        ) => Some("INTEGER"),
+        (DbBackend::Sqlite, Float(_) | Double(_)) => Some("REAL"),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "mock")]
+mod tests {
+    use crate::{
+        entity::prelude::*, tests_cfg::*, DbBackend, MockDatabase, QueryFilter, QueryOrder,
+        QuerySelect, Transaction,
+    };
+    use pretty_assertions::assert_eq;
+
+    #[smol_potat::test]
+    async fn aggregate_1() -> Result<(), DbErr> {
+        let db = MockDatabase::new(DbBackend::MySql)
+            .append_query_results(vec![
+                vec![maplit::btreemap! {
+                    "aggregate_value" => Into::<Value>::into(1),
+                }],
+                vec![maplit::btreemap! {
+                    "aggregate_value" => Into::<Value>::into(4),
+                }],
+                vec![maplit::btreemap! {
+                    "aggregate_value" => Into::<Value>::into(2.5f64),
+                }],
+            ])
+            .into_connection();
+
+        assert_eq!(
+            fruit::Entity::find().min(fruit::Column::Id, &db).await?,
+            Some(1)
+        );
+        assert_eq!(
+            fruit::Entity::find().max(fruit::Column::Id, &db).await?,
+            Some(4)
+        );
+        assert_eq!(
+            fruit::Entity::find().avg(fruit::Column::Id, &db).await?,
+            Some(2.5f64)
+        );
+
+        assert_eq!(
+            db.into_transaction_log(),
+            vec![
+                Transaction::from_sql_and_values(
+                    DbBackend::MySql,
+                    [
+                        "SELECT CAST(MIN(`aggregate_input`) AS SIGNED) AS `aggregate_value`",
+                        "FROM (SELECT `fruit`.`id` AS `aggregate_input` FROM `fruit`) AS `sub_query`",
+                    ]
+                    .join(" ")
+                    .as_str(),
+                    vec![]
+                ),
+                Transaction::from_sql_and_values(
+                    DbBackend::MySql,
+                    [
+                        "SELECT CAST(MAX(`aggregate_input`) AS SIGNED) AS `aggregate_value`",
+                        "FROM (SELECT `fruit`.`id` AS `aggregate_input` FROM `fruit`) AS `sub_query`",
+                    ]
+                    .join(" ")
+                    .as_str(),
+                    vec![]
+                ),
+                Transaction::from_sql_and_values(
+                    DbBackend::MySql,
+                    [
+                        "SELECT CAST(AVG(`aggregate_input`) AS DOUBLE) AS `aggregate_value`",
+                        "FROM (SELECT `fruit`.`id` AS `aggregate_input` FROM `fruit`) AS `sub_query`",
+                    ]
+                    .join(" ")
+                    .as_str(),
+                    vec![]
+                ),
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[smol_potat::test]
+    async fn aggregate_2() -> Result<(), DbErr> {
+        let db = MockDatabase::new(DbBackend::Sqlite)
+            .append_query_results(vec![Vec::<fruit::Model>::new(), Vec::new()])
+            .into_connection();
+
+        let sum: Option<i64> = fruit::Entity::find()
+            .filter(fruit::Column::CakeId.eq(1))
+            .sum(fruit::Column::Id, &db)
+            .await?;
+        assert_eq!(sum, None);
+
+        // the order is replaced, and the limit dropped
+        let counts: Vec<(Option<i32>, i64)> = fruit::Entity::find()
+            .order_by_desc(fruit::Column::Name)
+            .limit(2)
+            .group_count(fruit::Column::CakeId, &db)
+            .await?;
+        assert_eq!(counts, vec![]);
+
+        assert_eq!(
+            db.into_transaction_log(),
+            vec![
+                Transaction::from_sql_and_values(
+                    DbBackend::Sqlite,
+                    [
+                        r#"SELECT CAST(SUM("aggregate_input") AS INTEGER) AS "aggregate_value""#,
+                        r#"FROM (SELECT "fruit"."id" AS "aggregate_input" FROM "fruit""#,
+                        r#"WHERE "fruit"."cake_id" = ?) AS "sub_query""#,
+                    ]
+                    .join(" ")
+                    .as_str(),
+                    vec![1i32.into()]
+                ),
+                Transaction::from_sql_and_values(
+                    DbBackend::Sqlite,
+                    [
+                        r#"SELECT "group_key", COUNT(*) AS "group_count""#,
+                        r#"FROM (SELECT "fruit"."cake_id" AS "group_key" FROM "fruit""#,
+                        r#"ORDER BY "fruit"."name" DESC) AS "sub_query""#,
+                        r#"GROUP BY "group_key" ORDER BY "group_key" ASC"#,
+                    ]
+                    .join(" ")
+                    .as_str(),
+                    vec![]
+                ),
+            ]
+        );
+
+        Ok(())
+    }
+}
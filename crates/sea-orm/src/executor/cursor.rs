@@ -0,0 +1,843 @@
+use crate::{
+    error::*, value_tuple_to_vec, ConnectionTrait, EntityTrait, FromQueryResult, IntoIdentity,
+    QueryResult, Select, SelectModel, Selector, SelectorTrait,
+};
+use sea_query::{
+    Condition, DynIden, Expr, IntoValueTuple, Order, SeaRc, SelectStatement, SimpleExpr, Value,
+};
+use std::{marker::PhantomData, time::Duration};
+
+#[cfg(feature = "with-json")]
+use crate::JsonValue;
+
+/// Keyset (cursor) based pagination of a result from a query operation.
+///
+/// Rows are ordered by the cursor columns and filtered by comparing against
+/// the column values of a boundary row, so deep pages are as cheap as the first
+/// one and concurrently inserted rows are neither skipped nor repeated.
+/// The cursor columns must uniquely identify a row and must not be nullable,
+/// and the underlying query should not carry an `ORDER BY` clause of its own.
+#[derive(Clone, Debug)]
+pub struct Cursor<S>
+where
+    S: SelectorTrait,
+{
+    pub(crate) query: SelectStatement,
+    pub(crate) table: Option<DynIden>,
+    pub(crate) order_columns: Vec<DynIden>,
+    pub(crate) desc: bool,
+    pub(crate) after: Option<Vec<Value>>,
+    pub(crate) before: Option<Vec<Value>>,
+    pub(crate) first: Option<u64>,
+    pub(crate) last: Option<u64>,
+    pub(crate) timeout: Option<Duration>,
+    pub(crate) selector: PhantomData<S>,
+}
+
+/// A page of items fetched by a [Cursor], along with the opaque cursors of its boundaries
+#[derive(Clone, Debug, PartialEq)]
+pub struct CursorPage<T> {
+    /// The items of this page, in cursor order
+    pub items: Vec<T>,
+    /// Cursor of the first item, pass it to [Cursor::before_cursor] to fetch the previous page
+    pub start_cursor: Option<String>,
+    /// Cursor of the last item, pass it to [Cursor::after_cursor] to fetch the next page
+    pub end_cursor: Option<String>,
+    /// Whether there are more items before this page
+    pub has_previous_page: bool,
+    /// Whether there are more items after this page
+    pub has_next_page: bool,
+}
+
+impl<S> Cursor<S>
+where
+    S: SelectorTrait,
+{
+    pub(crate) fn new<C>(query: SelectStatement, table: Option<DynIden>, order_columns: C) -> Self
+    where
+        C: IntoIdentity,
+    {
+        Self {
+            query,
+            table,
+            order_columns: order_columns.into_identity().columns(),
+            desc: false,
+            after: None,
+            before: None,
+            first: None,
+            last: None,
+            timeout: None,
+            selector: PhantomData,
+        }
+    }
+
+    /// Order by the cursor columns in descending order
+    pub fn desc(&mut self) -> &mut Self {
+        self.desc = true;
+        self
+    }
+
+    /// Only return items after the row with the given cursor column values
+    pub fn after<V>(&mut self, values: V) -> &mut Self
+    where
+        V: IntoValueTuple,
+    {
+        self.after = Some(value_tuple_to_vec(values.into_value_tuple()));
+        self
+    }
+
+    /// Only return items before the row with the given cursor column values
+    pub fn before<V>(&mut self, values: V) -> &mut Self
+    where
+        V: IntoValueTuple,
+    {
+        self.before = Some(value_tuple_to_vec(values.into_value_tuple()));
+        self
+    }
+
+    /// Only return items after the given opaque cursor, as returned in [CursorPage::end_cursor]
+    pub fn after_cursor(&mut self, cursor: &str) -> Result<&mut Self, DbErr> {
+        self.after = Some(decode_cursor(cursor)?);
+        Ok(self)
+    }
+
+    /// Only return items before the given opaque cursor, as returned in [CursorPage::start_cursor]
+    pub fn before_cursor(&mut self, cursor: &str) -> Result<&mut Self, DbErr> {
+        self.before = Some(decode_cursor(cursor)?);
+        Ok(self)
+    }
+
+    /// Limit the result to the first `num` items
+    pub fn first(&mut self, num: u64) -> &mut Self {
+        self.first = Some(num);
+        self.last = None;
+        self
+    }
+
+    /// Limit the result to the last `num` items
+    pub fn last(&mut self, num: u64) -> &mut Self {
+        self.last = Some(num);
+        self.first = None;
+        self
+    }
+
+    /// Set the maximum execution time of the query, overriding the statement timeout of the connection
+    pub fn timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Return a [Cursor] that converts the result into the given Model
+    pub fn into_model<M>(self) -> Cursor<SelectModel<M>>
+    where
+        M: FromQueryResult,
+    {
+        Cursor {
+            query: self.query,
+            table: self.table,
+            order_columns: self.order_columns,
+            desc: self.desc,
+            after: self.after,
+            before: self.before,
+            first: self.first,
+            last: self.last,
+            timeout: self.timeout,
+            selector: PhantomData,
+        }
+    }
+
+    /// Return a [Cursor] that converts the result into [JsonValue]
+    #[cfg(feature = "with-json")]
+    pub fn into_json(self) -> Cursor<SelectModel<JsonValue>> {
+        self.into_model()
+    }
+
+    /// Fetch the items of the current window
+    pub async fn all<C>(&self, db: &C) -> Result<Vec<S::Item>, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        let (rows, _) = self.fetch_rows(db, self.first.or(self.last)).await?;
+        rows.into_iter().map(S::from_raw_query_result).collect()
+    }
+
+    /// Fetch the items of the current window together with the cursors of its boundaries.
+    /// The cursor values are read from the result by column name, so the cursor columns
+    /// must be selected, under their own names, by whatever the items are converted into.
+    pub async fn fetch_page<C>(&self, db: &C) -> Result<CursorPage<S::Item>, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        let limit = self.first.or(self.last);
+        let (rows, has_more) = self
+            .fetch_rows(db, limit.map(|num| num.saturating_add(1)))
+            .await?;
+        let start_cursor = match rows.first() {
+            Some(row) => Some(encode_cursor(&self.cursor_values(row)?)?),
+            None => None,
+        };
+        let end_cursor = match rows.last() {
+            Some(row) => Some(encode_cursor(&self.cursor_values(row)?)?),
+            None => None,
+        };
+        let (has_previous_page, has_next_page) = if self.last.is_some() {
+            (has_more, self.before.is_some())
+        } else {
+            (self.after.is_some(), has_more)
+        };
+        Ok(CursorPage {
+            items: rows
+                .into_iter()
+                .map(S::from_raw_query_result)
+                .collect::<Result<_, _>>()?,
+            start_cursor,
+            end_cursor,
+            has_previous_page,
+            has_next_page,
+        })
+    }
+
+    async fn fetch_rows<C>(
+        &self,
+        db: &C,
+        limit: Option<u64>,
+    ) -> Result<(Vec<QueryResult>, bool), DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        let mut query = self.query.clone();
+        if let Some(values) = &self.after {
+            query.cond_where(self.filter(values, !self.desc)?);
+        }
+        if let Some(values) = &self.before {
+            query.cond_where(self.filter(values, self.desc)?);
+        }
+        // fetching the last items is done by reversing the order, then reversing the result back
+        let reverse = self.last.is_some();
+        let order = if self.desc != reverse {
+            Order::Desc
+        } else {
+            Order::Asc
+        };
+        for col in self.order_columns.iter() {
+            query.order_by_expr(self.column_expr(col), order.clone());
+        }
+        if let Some(limit) = limit {
+            query.limit(limit);
+        }
+        let builder = db.get_database_backend();
+        let stmt = builder.build(&query);
+        let mut rows = db.query_all_with_timeout(stmt, self.timeout).await?;
+        let window = self.first.or(self.last).map(|num| num as usize);
+        let has_more = matches!(window, Some(num) if rows.len() > num);
+        if let Some(num) = window {
+            rows.truncate(num);
+        }
+        if reverse {
+            rows.reverse();
+        }
+        Ok((rows, has_more))
+    }
+
+    fn cursor_values(&self, row: &QueryResult) -> Result<Vec<Value>, DbErr> {
+        self.order_columns
+            .iter()
+            .map(|col| cursor_value(row, &col.to_string()))
+            .collect()
+    }
+
+    fn column_expr(&self, col: &DynIden) -> SimpleExpr {
+        match &self.table {
+            Some(table) => Expr::tbl(SeaRc::clone(table), SeaRc::clone(col)).into(),
+            None => Expr::col(SeaRc::clone(col)).into(),
+        }
+    }
+
+    /// Expand the row value comparison `(c1, c2, c3) > (v1, v2, v3)` into
+    /// `c1 > v1 OR (c1 = v1 AND c2 > v2) OR (c1 = v1 AND c2 = v2 AND c3 > v3)`,
+    /// which has the same meaning on every backend
+    fn filter(&self, values: &[Value], greater: bool) -> Result<Condition, DbErr> {
+        if values.len() != self.order_columns.len() {
+            return Err(DbErr::Custom(format!(
+                "Expected {} cursor values, got {}",
+                self.order_columns.len(),
+                values.len()
+            )));
+        }
+        let mut cond = Condition::any();
+        for (i, (col, value)) in self.order_columns.iter().zip(values.iter()).enumerate() {
+            let mut all = Condition::all();
+            for (prev_col, prev_value) in self.order_columns.iter().zip(values.iter()).take(i) {
+                all = all.add(Expr::expr(self.column_expr(prev_col)).eq(prev_value.clone()));
+            }
+            let expr = Expr::expr(self.column_expr(col));
+            all = all.add(if greater {
+                expr.gt(value.clone())
+            } else {
+                expr.lt(value.clone())
+            });
+            cond = cond.add(all);
+        }
+        Ok(cond)
+    }
+}
+
+impl<E, M> Select<E>
+where
+    E: EntityTrait<Model = M>,
+    M: FromQueryResult + Sized + Send + Sync,
+{
+    /// Convert into a [Cursor] ordered by the given columns
+    ///
+    /// ```
+    /// # use sea_orm::{error::*, tests_cfg::*, *};
+    /// #
+    /// # #[smol_potat::main]
+    /// # #[cfg(feature = "mock")]
+    /// # pub async fn main() -> Result<(), DbErr> {
+    /// #
+    /// # let db = MockDatabase::new(DbBackend::Postgres)
+    /// #     .append_query_results(vec![vec![
+    /// #         cake::Model {
+    /// #             id: 11,
+    /// #             name: "Cheese Cake".to_owned(),
+    /// #         },
+    /// #         cake::Model {
+    /// #             id: 12,
+    /// #             name: "Chocolate Cake".to_owned(),
+    /// #         },
+    /// #     ]])
+    /// #     .into_connection();
+    /// #
+    /// use sea_orm::{entity::*, query::*, tests_cfg::cake};
+    ///
+    /// let page = cake::Entity::find()
+    ///     .cursor_by(cake::Column::Id)
+    ///     .after(10)
+    ///     .first(2)
+    ///     .fetch_page(&db)
+    ///     .await?;
+    ///
+    /// assert_eq!(page.items.len(), 2);
+    /// assert!(page.has_previous_page);
+    /// assert!(!page.has_next_page);
+    ///
+    /// assert_eq!(
+    ///     db.into_transaction_log(),
+    ///     vec![Transaction::from_sql_and_values(
+    ///         DbBackend::Postgres,
+    ///         [
+    ///             r#"SELECT "cake"."id", "cake"."name" FROM "cake""#,
+    ///             r#"WHERE "cake"."id" > $1"#,
+    ///             r#"ORDER BY "cake"."id" ASC LIMIT $2"#,
+    ///         ]
+    ///         .join(" ")
+    ///         .as_str(),
+    ///         vec![10_i32.into(), 3_u64.into()]
+    ///     )]
+    /// );
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn cursor_by<C>(self, order_columns: C) -> Cursor<SelectModel<M>>
+    where
+        C: IntoIdentity,
+    {
+        let table: DynIden = SeaRc::new(E::default());
+        Cursor::new(self.query, Some(table), order_columns)
+    }
+}
+
+impl<S> Selector<S>
+where
+    S: SelectorTrait,
+{
+    /// Convert into a [Cursor] ordered by the given columns.
+    /// The columns are not qualified by table name, so they must be unambiguous.
+    pub fn cursor_by<C>(self, order_columns: C) -> Cursor<S>
+    where
+        C: IntoIdentity,
+    {
+        Cursor::new(self.query, None, order_columns)
+    }
+}
+
+/// Read a cursor column from a raw row by its column name. A row does not know which
+/// [Value] variant a column maps to, so the column is decoded as each supported type in
+/// turn, relying on the driver to reject a type that does not match the column.
+fn cursor_value(row: &QueryResult, col: &str) -> Result<Value, DbErr> {
+    #[cfg(feature = "mock")]
+    #[allow(irrefutable_let_patterns)]
+    {
+        if let crate::QueryResultRow::Mock(row) = &row.row {
+            return row.get(col).cloned().ok_or_else(|| {
+                DbErr::Custom(format!("Cursor column `{}` not found in the result", col))
+            });
+        }
+    }
+    macro_rules! probe {
+        ( $( $type: ty ),* ) => {
+            $(
+                if let Ok(value) = row.try_get::<$type>("", col) {
+                    return Ok(value.into());
+                }
+            )*
+        };
+    }
+    probe!(i64, i32, i16);
+    #[cfg(feature = "sqlx-mysql")]
+    #[allow(irrefutable_let_patterns)]
+    {
+        if let crate::QueryResultRow::SqlxMySql(_) = &row.row {
+            probe!(u64);
+        }
+    }
+    probe!(f64, f32, bool);
+    #[cfg(feature = "with-rust_decimal")]
+    probe!(rust_decimal::Decimal);
+    #[cfg(feature = "with-uuid")]
+    probe!(uuid::Uuid);
+    #[cfg(feature = "with-chrono")]
+    probe!(
+        chrono::NaiveDateTime,
+        chrono::DateTime<chrono::Utc>,
+        chrono::NaiveDate,
+        chrono::NaiveTime
+    );
+    #[cfg(feature = "with-time")]
+    {
+        // sqlx-sqlite cannot decode the `time` types
+        #[cfg(feature = "sqlx-sqlite")]
+        let supported = !matches!(row.row, crate::QueryResultRow::SqlxSqlite(_));
+        #[cfg(not(feature = "sqlx-sqlite"))]
+        let supported = true;
+        if supported {
+            probe!(
+                time::PrimitiveDateTime,
+                time::OffsetDateTime,
+                time::Date,
+                time::Time
+            );
+        }
+    }
+    probe!(String, Vec<u8>);
+    Err(DbErr::Custom(format!(
+        "Cursor column `{}` not found in the result or of an unsupported type",
+        col
+    )))
+}
+
+/// Encode cursor values as `tag:len:payload` entries, hex encoded to keep the cursor opaque
+fn encode_cursor(values: &[Value]) -> Result<String, DbErr> {
+    let mut bytes = Vec::new();
+    for value in values {
+        let (tag, payload): (&str, Vec<u8>) = match value {
+            Value::Bool(Some(v)) => ("b", v.to_string().into_bytes()),
+            Value::TinyInt(Some(v)) => ("i8", v.to_string().into_bytes()),
+            Value::SmallInt(Some(v)) => ("i16", v.to_string().into_bytes()),
+            Value::Int(Some(v)) => ("i32", v.to_string().into_bytes()),
+            Value::BigInt(Some(v)) => ("i64", v.to_string().into_bytes()),
+            Value::TinyUnsigned(Some(v)) => ("u8", v.to_string().into_bytes()),
+            Value::SmallUnsigned(Some(v)) => ("u16", v.to_string().into_bytes()),
+            Value::Unsigned(Some(v)) => ("u32", v.to_string().into_bytes()),
+            Value::BigUnsigned(Some(v)) => ("u64", v.to_string().into_bytes()),
+            Value::Float(Some(v)) => ("f32", v.to_string().into_bytes()),
+            Value::Double(Some(v)) => ("f64", v.to_string().into_bytes()),
+            Value::String(Some(v)) => ("s", v.as_bytes().to_vec()),
+            Value::Bytes(Some(v)) => ("x", v.as_ref().clone()),
+            #[cfg(feature = "with-chrono")]
+            Value::ChronoDate(Some(v)) => ("cd", v.to_string().into_bytes()),
+            #[cfg(feature = "with-chrono")]
+            Value::ChronoTime(Some(v)) => ("ct", v.to_string().into_bytes()),
+            #[cfg(feature = "with-chrono")]
+            Value::ChronoDateTime(Some(v)) => (
+                "cdt",
+                v.format("%Y-%m-%dT%H:%M:%S%.f").to_string().into_bytes(),
+            ),
+            #[cfg(feature = "with-chrono")]
+            Value::ChronoDateTimeUtc(Some(v)) => ("cdtu", v.to_rfc3339().into_bytes()),
+            #[cfg(feature = "with-chrono")]
+            Value::ChronoDateTimeLocal(Some(v)) => ("cdtl", v.to_rfc3339().into_bytes()),
+            #[cfg(feature = "with-chrono")]
+            Value::ChronoDateTimeWithTimeZone(Some(v)) => ("cdtz", v.to_rfc3339().into_bytes()),
+            #[cfg(feature = "with-time")]
+            Value::TimeDate(Some(v)) => ("td", v.format("%Y-%m-%d").into_bytes()),
+            #[cfg(feature = "with-time")]
+            Value::TimeTime(Some(v)) => ("tt", v.format("%H:%M:%S.%N").into_bytes()),
+            #[cfg(feature = "with-time")]
+            Value::TimeDateTime(Some(v)) => ("tdt", v.format("%Y-%m-%dT%H:%M:%S.%N").into_bytes()),
+            #[cfg(feature = "with-time")]
+            Value::TimeDateTimeWithTimeZone(Some(v)) => {
+                ("tdtz", v.format("%Y-%m-%dT%H:%M:%S.%N%z").into_bytes())
+            }
+            #[cfg(feature = "with-uuid")]
+            Value::Uuid(Some(v)) => ("uuid", v.to_string().into_bytes()),
+            #[cfg(feature = "with-rust_decimal")]
+            Value::Decimal(Some(v)) => ("dec", v.to_string().into_bytes()),
+            _ => {
+                return Err(DbErr::Custom(format!(
+                    "Unsupported cursor value: {:?}",
+                    value
+                )))
+            }
+        };
+        bytes.extend_from_slice(format!("{}:{}:", tag, payload.len()).as_bytes());
+        bytes.extend(payload);
+    }
+    Ok(bytes.iter().map(|b| format!("{:02x}", b)).collect())
+}
+
+fn decode_cursor(cursor: &str) -> Result<Vec<Value>, DbErr> {
+    let invalid = || DbErr::Custom(format!("Invalid cursor: {}", cursor));
+    let bytes = (0..cursor.len())
+        .step_by(2)
+        .map(|i| {
+            cursor
+                .get(i..i + 2)
+                .and_then(|s| u8::from_str_radix(s, 16).ok())
+        })
+        .collect::<Option<Vec<u8>>>()
+        .ok_or_else(invalid)?;
+
+    let mut values = Vec::new();
+    let mut rest = bytes.as_slice();
+    while !rest.is_empty() {
+        let (tag, remain) = split_at_colon(rest).ok_or_else(invalid)?;
+        let (len, remain) = split_at_colon(remain).ok_or_else(invalid)?;
+        let len: usize = std::str::from_utf8(len)
+            .ok()
+            .and_then(|len| len.parse().ok())
+            .ok_or_else(invalid)?;
+        if remain.len() < len {
+            return Err(invalid());
+        }
+        let (payload, remain) = remain.split_at(len);
+        rest = remain;
+
+        if tag == b"x" {
+            values.push(Value::Bytes(Some(Box::new(payload.to_vec()))));
+            continue;
+        }
+        let s = std::str::from_utf8(payload).map_err(|_| invalid())?;
+        macro_rules! parse {
+            ( $variant: ident, $type: ty ) => {
+                Value::$variant(Some(s.parse::<$type>().map_err(|_| invalid())?.into()))
+            };
+        }
+        let value = match tag {
+            b"b" => parse!(Bool, bool),
+            b"i8" => parse!(TinyInt, i8),
+            b"i16" => parse!(SmallInt, i16),
+            b"i32" => parse!(Int, i32),
+            b"i64" => parse!(BigInt, i64),
+            b"u8" => parse!(TinyUnsigned, u8),
+            b"u16" => parse!(SmallUnsigned, u16),
+            b"u32" => parse!(Unsigned, u32),
+            b"u64" => parse!(BigUnsigned, u64),
+            b"f32" => parse!(Float, f32),
+            b"f64" => parse!(Double, f64),
+            b"s" => Value::String(Some(Box::new(s.to_owned()))),
+            #[cfg(feature = "with-chrono")]
+            b"cd" => parse!(ChronoDate, chrono::NaiveDate),
+            #[cfg(feature = "with-chrono")]
+            b"ct" => parse!(ChronoTime, chrono::NaiveTime),
+            #[cfg(feature = "with-chrono")]
+            b"cdt" => Value::ChronoDateTime(Some(Box::new(
+                chrono::NaiveDateTime::parse_from_str(s, "%Y-%m-%dT%H:%M:%S%.f")
+                    .map_err(|_| invalid())?,
+            ))),
+            #[cfg(feature = "with-chrono")]
+            b"cdtu" => Value::ChronoDateTimeUtc(Some(Box::new(
+                chrono::DateTime::parse_from_rfc3339(s)
+                    .map_err(|_| invalid())?
+                    .with_timezone(&chrono::Utc),
+            ))),
+            #[cfg(feature = "with-chrono")]
+            b"cdtl" => Value::ChronoDateTimeLocal(Some(Box::new(
+                chrono::DateTime::parse_from_rfc3339(s)
+                    .map_err(|_| invalid())?
+                    .with_timezone(&chrono::Local),
+            ))),
+            #[cfg(feature = "with-chrono")]
+            b"cdtz" => Value::ChronoDateTimeWithTimeZone(Some(Box::new(
+                chrono::DateTime::parse_from_rfc3339(s).map_err(|_| invalid())?,
+            ))),
+            #[cfg(feature = "with-time")]
+            b"td" => Value::TimeDate(Some(Box::new(
+                time::Date::parse(s, "%Y-%m-%d").map_err(|_| invalid())?,
+            ))),
+            #[cfg(feature = "with-time")]
+            b"tt" => Value::TimeTime(Some(Box::new(
+                time::Time::parse(s, "%H:%M:%S.%N").map_err(|_| invalid())?,
+            ))),
+            #[cfg(feature = "with-time")]
+            b"tdt" => Value::TimeDateTime(Some(Box::new(
+                time::PrimitiveDateTime::parse(s, "%Y-%m-%dT%H:%M:%S.%N").map_err(|_| invalid())?,
+            ))),
+            #[cfg(feature = "with-time")]
+            b"tdtz" => Value::TimeDateTimeWithTimeZone(Some(Box::new(
+                time::OffsetDateTime::parse(s, "%Y-%m-%dT%H:%M:%S.%N%z").map_err(|_| invalid())?,
+            ))),
+            #[cfg(feature = "with-uuid")]
+            b"uuid" => parse!(Uuid, uuid::Uuid),
+            #[cfg(feature = "with-rust_decimal")]
+            b"dec" => parse!(Decimal, rust_decimal::Decimal),
+            _ => return Err(invalid()),
+        };
+        values.push(value);
+    }
+    Ok(values)
+}
+
+fn split_at_colon(bytes: &[u8]) -> Option<(&[u8], &[u8])> {
+    let pos = bytes.iter().position(|b| *b == b':')?;
+    Some((&bytes[..pos], &bytes[pos + 1..]))
+}
+
+#[cfg(test)]
+#[cfg(feature = "mock")]
+mod tests {
+    use super::*;
+    use crate::entity::prelude::*;
+    use crate::{tests_cfg::*, DbBackend, MockDatabase, Transaction};
+    use pretty_assertions::assert_eq;
+
+    fn fruits(ids: &[i32]) -> Vec<fruit::Model> {
+        ids.iter()
+            .map(|id| fruit::Model {
+                id: *id,
+                name: format!("Fruit {}", id),
+                cake_id: Some(1),
+            })
+            .collect()
+    }
+
+    #[smol_potat::test]
+    async fn first_after() -> Result<(), DbErr> {
+        let db = MockDatabase::new(DbBackend::Postgres)
+            .append_query_results(vec![fruits(&[11, 12, 13])])
+            .into_connection();
+
+        let page = fruit::Entity::find()
+            .cursor_by(fruit::Column::Id)
+            .after(10)
+            .first(2)
+            .fetch_page(&db)
+            .await?;
+
+        assert_eq!(page.items, fruits(&[11, 12]));
+        assert!(page.has_previous_page);
+        assert!(page.has_next_page);
+        assert_eq!(
+            decode_cursor(page.end_cursor.as_ref().unwrap())?,
+            vec![12.into()]
+        );
+
+        assert_eq!(
+            db.into_transaction_log(),
+            vec![Transaction::from_sql_and_values(
+                DbBackend::Postgres,
+                [
+                    r#"SELECT "fruit"."id", "fruit"."name", "fruit"."cake_id" FROM "fruit""#,
+                    r#"WHERE "fruit"."id" > $1"#,
+                    r#"ORDER BY "fruit"."id" ASC LIMIT $2"#,
+                ]
+                .join(" ")
+                .as_str(),
+                vec![10_i32.into(), 3_u64.into()]
+            )]
+        );
+        Ok(())
+    }
+
+    #[smol_potat::test]
+    async fn last_before() -> Result<(), DbErr> {
+        let db = MockDatabase::new(DbBackend::Postgres)
+            .append_query_results(vec![fruits(&[19, 18])])
+            .into_connection();
+
+        let page = fruit::Entity::find()
+            .cursor_by(fruit::Column::Id)
+            .before(20)
+            .last(2)
+            .fetch_page(&db)
+            .await?;
+
+        assert_eq!(page.items, fruits(&[18, 19]));
+        assert!(!page.has_previous_page);
+        assert!(page.has_next_page);
+
+        assert_eq!(
+            db.into_transaction_log(),
+            vec![Transaction::from_sql_and_values(
+                DbBackend::Postgres,
+                [
+                    r#"SELECT "fruit"."id", "fruit"."name", "fruit"."cake_id" FROM "fruit""#,
+                    r#"WHERE "fruit"."id" < $1"#,
+                    r#"ORDER BY "fruit"."id" DESC LIMIT $2"#,
+                ]
+                .join(" ")
+                .as_str(),
+                vec![20_i32.into(), 3_u64.into()]
+            )]
+        );
+        Ok(())
+    }
+
+    #[smol_potat::test]
+    async fn composite_desc() -> Result<(), DbErr> {
+        let db = MockDatabase::new(DbBackend::MySql)
+            .append_query_results(vec![fruits(&[5])])
+            .into_connection();
+
+        let items = fruit::Entity::find()
+            .cursor_by((fruit::Column::CakeId, fruit::Column::Id))
+            .desc()
+            .after((1, 6))
+            .all(&db)
+            .await?;
+
+        assert_eq!(items, fruits(&[5]));
+
+        assert_eq!(
+            db.into_transaction_log(),
+            vec![Transaction::from_sql_and_values(
+                DbBackend::MySql,
+                [
+                    "SELECT `fruit`.`id`, `fruit`.`name`, `fruit`.`cake_id` FROM `fruit`",
+                    "WHERE `fruit`.`cake_id` < ? OR (`fruit`.`cake_id` = ? AND `fruit`.`id` < ?)",
+                    "ORDER BY `fruit`.`cake_id` DESC, `fruit`.`id` DESC",
+                ]
+                .join(" ")
+                .as_str(),
+                vec![1_i32.into(), 1_i32.into(), 6_i32.into()]
+            )]
+        );
+        Ok(())
+    }
+
+    #[smol_potat::test]
+    #[cfg(feature = "with-json")]
+    async fn json_and_custom_model() -> Result<(), DbErr> {
+        use crate::{QueryResult, QuerySelect};
+        use std::collections::BTreeMap;
+
+        #[derive(Debug, PartialEq)]
+        struct FruitName {
+            fruit_id: i32,
+            fruit_name: String,
+        }
+
+        impl FromQueryResult for FruitName {
+            fn from_query_result(res: &QueryResult, pre: &str) -> Result<Self, DbErr> {
+                Ok(Self {
+                    fruit_id: res.try_get(pre, "fruit_id")?,
+                    fruit_name: res.try_get(pre, "fruit_name")?,
+                })
+            }
+        }
+
+        let rows: Vec<BTreeMap<&str, Value>> = [(11, "Apple"), (12, "Banana")]
+            .iter()
+            .map(|(id, name)| {
+                BTreeMap::from([("fruit_id", (*id).into()), ("fruit_name", (*name).into())])
+            })
+            .collect();
+        let db = MockDatabase::new(DbBackend::Postgres)
+            .append_query_results(vec![rows.clone(), rows])
+            .into_connection();
+
+        let select = || {
+            fruit::Entity::find()
+                .select_only()
+                .column_as(fruit::Column::Id, "fruit_id")
+                .column_as(fruit::Column::Name, "fruit_name")
+        };
+
+        let page = select()
+            .into_model::<FruitName>()
+            .cursor_by("fruit_id")
+            .into_json()
+            .first(5)
+            .fetch_page(&db)
+            .await?;
+        assert_eq!(
+            page.items,
+            vec![
+                serde_json::json!({ "fruit_id": 11, "fruit_name": "Apple" }),
+                serde_json::json!({ "fruit_id": 12, "fruit_name": "Banana" }),
+            ]
+        );
+        assert_eq!(
+            decode_cursor(page.end_cursor.as_ref().unwrap())?,
+            vec![12.into()]
+        );
+
+        let page = select()
+            .into_model::<FruitName>()
+            .cursor_by("fruit_id")
+            .first(5)
+            .fetch_page(&db)
+            .await?;
+        assert_eq!(
+            page.items,
+            vec![
+                FruitName {
+                    fruit_id: 11,
+                    fruit_name: "Apple".to_owned(),
+                },
+                FruitName {
+                    fruit_id: 12,
+                    fruit_name: "Banana".to_owned(),
+                },
+            ]
+        );
+        assert_eq!(
+            decode_cursor(page.start_cursor.as_ref().unwrap())?,
+            vec![11.into()]
+        );
+        assert!(!page.has_next_page);
+        Ok(())
+    }
+
+    #[smol_potat::test]
+    async fn opaque_cursor() -> Result<(), DbErr> {
+        let values = vec![
+            Value::Int(Some(1)),
+            Value::String(Some(Box::new("a:b:c".to_owned()))),
+            Value::Bytes(Some(Box::new(vec![0, 58, 255]))),
+        ];
+        let cursor = encode_cursor(&values)?;
+        assert_eq!(decode_cursor(&cursor)?, values);
+        assert!(decode_cursor("not a cursor").is_err());
+
+        let db = MockDatabase::new(DbBackend::Sqlite)
+            .append_query_results(vec![fruits(&[2])])
+            .into_connection();
+
+        let cursor = encode_cursor(&[Value::Int(Some(1))])?;
+        fruit::Entity::find()
+            .cursor_by(fruit::Column::Id)
+            .after_cursor(&cursor)?
+            .first(1)
+            .all(&db)
+            .await?;
+
+        assert_eq!(
+            db.into_transaction_log(),
+            vec![Transaction::from_sql_and_values(
+                DbBackend::Sqlite,
+                [
+                    r#"SELECT "fruit"."id", "fruit"."name", "fruit"."cake_id" FROM "fruit""#,
+                    r#"WHERE "fruit"."id" > ?"#,
+                    r#"ORDER BY "fruit"."id" ASC LIMIT ?"#,
+                ]
+                .join(" ")
+                .as_str(),
+                vec![1_i32.into(), 1_u64.into()]
+            )]
+        );
+        Ok(())
+    }
+}
@@ -1,13 +1,15 @@
 use crate::{
-    error::*, ActiveModelTrait, ConnectionTrait, DeleteMany, DeleteOne, EntityTrait, Statement,
+    error::*, returning_all_columns, ActiveModelTrait, ConnectionTrait, DeleteMany, DeleteOne,
+    EntityTrait, SelectModel, SelectorRaw, Statement,
 };
 use sea_query::DeleteStatement;
-use std::future::Future;
+use std::{future::Future, time::Duration};
 
 /// Handles DELETE operations in a ActiveModel using [DeleteStatement]
 #[derive(Clone, Debug)]
 pub struct Deleter {
     query: DeleteStatement,
+    timeout: Option<Duration>,
 }
 
 /// The result of a DELETE operation
@@ -43,12 +45,37 @@ where
         // so that self is dropped before entering await
         exec_delete_only(self.query, db)
     }
+
+    /// Execute a DELETE operation and return the deleted models (use `RETURNING` syntax,
+    /// supported by Postgres and SQLite 3.35 or later)
+    pub fn exec_with_returning<C>(
+        self,
+        db: &'a C,
+    ) -> impl Future<Output = Result<Vec<E::Model>, DbErr>> + '_
+    where
+        C: ConnectionTrait,
+    {
+        // so that self is dropped before entering await
+        exec_delete_with_returning::<E, _>(self.query, db)
+    }
 }
 
 impl Deleter {
     /// Instantiate a new [Deleter] by passing it a [DeleteStatement]
     pub fn new(query: DeleteStatement) -> Self {
-        Self { query }
+        Self {
+            query,
+            timeout: None,
+        }
+    }
+
+    /// Set the maximum execution time of the delete statement, overriding the statement timeout of the connection
+    ///
+    /// On MySQL the timeout has no effect on the delete statement, because `MAX_EXECUTION_TIME`
+    /// only applies to read only `SELECT` statements
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
     }
 
     /// Execute a DELETE operation
@@ -57,7 +84,7 @@ impl Deleter {
         C: ConnectionTrait,
     {
         let builder = db.get_database_backend();
-        exec_delete(builder.build(&self.query), db)
+        exec_delete(builder.build(&self.query), self.timeout, db)
     }
 }
 
@@ -68,12 +95,88 @@ where
     Deleter::new(query).exec(db).await
 }
 
-async fn exec_delete<C>(statement: Statement, db: &C) -> Result<DeleteResult, DbErr>
+async fn exec_delete_with_returning<E, C>(
+    mut query: DeleteStatement,
+    db: &C,
+) -> Result<Vec<E::Model>, DbErr>
 where
+    E: EntityTrait,
     C: ConnectionTrait,
 {
-    let result = db.execute(statement).await?;
+    if !db.support_returning() {
+        return Err(DbErr::Exec(format!(
+            "`RETURNING` on DELETE is not supported by {:?}",
+            db.get_database_backend()
+        )));
+    }
+    let db_backend = db.get_database_backend();
+    query.returning(returning_all_columns::<E>());
+    SelectorRaw::<SelectModel<E::Model>>::from_statement(db_backend.build(&query))
+        .all(db)
+        .await
+}
+
+async fn exec_delete<C>(
+    statement: Statement,
+    timeout: Option<Duration>,
+    db: &C,
+) -> Result<DeleteResult, DbErr>
+where
+    C: ConnectionTrait,
+{
+    let result = db.execute_with_timeout(statement, timeout).await?;
     Ok(DeleteResult {
         rows_affected: result.rows_affected(),
     })
 }
+
+#[cfg(test)]
+#[cfg(feature = "mock")]
+mod tests {
+    use crate::{entity::prelude::*, tests_cfg::*, *};
+    use pretty_assertions::assert_eq;
+
+    #[smol_potat::test]
+    async fn delete_many_with_returning() -> Result<(), DbErr> {
+        let db = MockDatabase::new(DbBackend::Postgres)
+            .append_query_results(vec![vec![fruit::Model {
+                id: 3,
+                name: "Apple".to_owned(),
+                cake_id: None,
+            }]])
+            .into_connection();
+
+        let models = fruit::Entity::delete_many()
+            .filter(fruit::Column::CakeId.is_null())
+            .exec_with_returning(&db)
+            .await?;
+
+        assert_eq!(
+            models,
+            vec![fruit::Model {
+                id: 3,
+                name: "Apple".to_owned(),
+                cake_id: None,
+            }]
+        );
+        assert_eq!(
+            db.into_transaction_log(),
+            vec![Transaction::from_sql_and_values(
+                DbBackend::Postgres,
+                r#"DELETE FROM "fruit" WHERE "fruit"."cake_id" IS NULL RETURNING "id", "name", "cake_id""#,
+                vec![]
+            )]
+        );
+
+        let db = MockDatabase::new(DbBackend::Sqlite).into_connection();
+        assert_eq!(
+            fruit::Entity::delete_many().exec_with_returning(&db).await,
+            Err(DbErr::Exec(
+                "`RETURNING` on DELETE is not supported by Sqlite".to_owned()
+            ))
+        );
+        assert_eq!(db.into_transaction_log(), vec![]);
+
+        Ok(())
+    }
+}
@@ -1,11 +1,13 @@
 use crate::{
-    error::*, ActiveModelTrait, ColumnTrait, ConnectionTrait, EntityTrait, Insert, IntoActiveModel,
-    Iterable, PrimaryKeyTrait, SelectModel, SelectorRaw, Statement, TryFromU64,
+    build_insert, error::*, ActiveModelTrait, ColumnTrait, ConnectionTrait, DbBackend, EntityTrait,
+    Insert, IntoActiveModel, Iterable, ModelTrait, PrimaryKeyToColumn, PrimaryKeyTrait,
+    QueryFilter, QueryOrder, SelectModel, SelectorRaw, Statement, TransactionTrait, TryFromU64,
 };
 use sea_query::{
-    Alias, Expr, FromValueTuple, Iden, InsertStatement, IntoColumnRef, Query, ValueTuple,
+    Alias, Condition, Expr, FromValueTuple, Iden, InsertStatement, IntoColumnRef, Query,
+    SelectStatement, Value, ValueTuple,
 };
-use std::{future::Future, marker::PhantomData};
+use std::{future::Future, marker::PhantomData, time::Duration};
 
 /// Defines a structure to perform INSERT operations in an ActiveModel
 #[derive(Debug)]
@@ -15,6 +17,9 @@ where
 {
     primary_key: Option<ValueTuple>,
     query: InsertStatement,
+    timeout: Option<Duration>,
+    on_conflict_do_nothing: bool,
+    upserted_row: Option<Condition>,
     model: PhantomData<A>,
 }
 
@@ -28,19 +33,31 @@ where
     pub last_insert_id: <<<A as ActiveModelTrait>::Entity as EntityTrait>::PrimaryKey as PrimaryKeyTrait>::ValueType,
 }
 
+/// The result of a chunked INSERT operation
+#[derive(Debug)]
+pub struct InsertManyResult {
+    /// The number of rows inserted by all chunks
+    pub rows_affected: u64,
+}
+
 impl<A> Insert<A>
 where
     A: ActiveModelTrait,
 {
     /// Execute an insert operation
     #[allow(unused_mut)]
-    pub fn exec<'a, C>(self, db: &'a C) -> impl Future<Output = Result<InsertResult<A>, DbErr>> + '_
+    pub fn exec<'a, C>(
+        mut self,
+        db: &'a C,
+    ) -> impl Future<Output = Result<InsertResult<A>, DbErr>> + '_
     where
         C: ConnectionTrait,
         A: 'a,
     {
         // so that self is dropped before entering await
-        let mut query = self.query;
+        let primary_key = self.primary_key.take();
+        let on_conflict_do_nothing = self.on_conflict_do_nothing();
+        let mut query = self.into_query_for(db.get_database_backend());
         if db.support_returning() && <A::Entity as EntityTrait>::PrimaryKey::iter().count() > 0 {
             let mut returning = Query::select();
             returning.columns(
@@ -48,12 +65,16 @@ where
             );
             query.returning(returning);
         }
-        Inserter::<A>::new(self.primary_key, query).exec(db)
+        let mut inserter = Inserter::<A>::new(primary_key, query);
+        inserter.on_conflict_do_nothing = on_conflict_do_nothing;
+        inserter.exec(db)
     }
 
     /// Execute an insert operation and return the inserted model (use `RETURNING` syntax if database supported)
+    ///
+    /// On `ON CONFLICT DO UPDATE`, the updated model is returned.
     pub fn exec_with_returning<'a, C>(
-        self,
+        mut self,
         db: &'a C,
     ) -> impl Future<Output = Result<<A::Entity as EntityTrait>::Model, DbErr>> + '_
     where
@@ -61,7 +82,66 @@ where
         C: ConnectionTrait,
         A: 'a,
     {
-        Inserter::<A>::new(self.primary_key, self.query).exec_with_returning(db)
+        let primary_key = self.primary_key.take();
+        let on_conflict_do_nothing = self.on_conflict_do_nothing();
+        let upserted_row = self.upserted_row();
+        let mut inserter =
+            Inserter::<A>::new(primary_key, self.into_query_for(db.get_database_backend()));
+        inserter.on_conflict_do_nothing = on_conflict_do_nothing;
+        inserter.upserted_row = upserted_row;
+        inserter.exec_with_returning(db)
+    }
+
+    /// Execute an insert operation and return all inserted models, in the order they were added.
+    /// Use `RETURNING` syntax if database supported, otherwise the inserted models are selected
+    /// again by their primary keys.
+    ///
+    /// Without `RETURNING`, i.e. on MySQL and SQLite older than 3.35:
+    /// - an insert with `ON CONFLICT` is an error, as the affected rows cannot be told apart
+    /// - if every primary key is given, the models are selected by them
+    /// - otherwise, only MySQL selects the models by the range of ids starting from the last insert id,
+    ///   which requires `auto_increment_increment = 1` and an `innodb_autoinc_lock_mode` of 0 or 1
+    ///   so that the ids of the statement are consecutive
+    pub fn exec_with_returning_many<'a, C>(
+        self,
+        db: &'a C,
+    ) -> impl Future<Output = Result<Vec<<A::Entity as EntityTrait>::Model>, DbErr>> + '_
+    where
+        <A::Entity as EntityTrait>::Model: IntoActiveModel<A>,
+        C: ConnectionTrait,
+        A: 'a,
+    {
+        type PrimaryKey<A> = <<A as ActiveModelTrait>::Entity as EntityTrait>::PrimaryKey;
+        let primary_keys = self.column_values(
+            &PrimaryKey::<A>::iter()
+                .map(|key| key.into_column().to_string())
+                .collect::<Vec<_>>(),
+        );
+        let num_rows = self.rows.len() as u64;
+        let on_conflict = self.on_conflict.is_some();
+        exec_insert_many_with_returning::<A, _>(
+            primary_keys,
+            num_rows,
+            self.into_query_for(db.get_database_backend()),
+            on_conflict,
+            db,
+        )
+    }
+
+    /// Execute an insert operation split into as many statements as needed to stay within
+    /// the bind parameter limit of the database backend. All statements are executed in one
+    /// transaction.
+    pub fn exec_chunked<'a, C>(
+        self,
+        db: &'a C,
+    ) -> impl Future<Output = Result<InsertManyResult, DbErr>> + '_
+    where
+        C: ConnectionTrait + TransactionTrait,
+        A: 'a,
+    {
+        let db_backend = db.get_database_backend();
+        let statements = self.into_chunks(db_backend, db_backend.max_bind_params());
+        exec_insert_chunked(statements, db)
     }
 }
 
@@ -74,10 +154,22 @@ where
         Self {
             primary_key,
             query,
+            timeout: None,
+            on_conflict_do_nothing: false,
+            upserted_row: None,
             model: PhantomData,
         }
     }
 
+    /// Set the maximum execution time of the insert statement, overriding the statement timeout of the connection
+    ///
+    /// On MySQL the timeout has no effect on the insert statement, because `MAX_EXECUTION_TIME`
+    /// only applies to read only `SELECT` statements
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
     /// Execute an insert operation
     pub fn exec<'a, C>(self, db: &'a C) -> impl Future<Output = Result<InsertResult<A>, DbErr>> + '_
     where
@@ -85,7 +177,13 @@ where
         A: 'a,
     {
         let builder = db.get_database_backend();
-        exec_insert(self.primary_key, builder.build(&self.query), db)
+        exec_insert(
+            self.primary_key,
+            build_insert(builder, &self.query, self.on_conflict_do_nothing),
+            self.timeout,
+            self.on_conflict_do_nothing,
+            db,
+        )
     }
 
     /// Execute an insert operation and return the inserted model (use `RETURNING` syntax if database supported)
@@ -98,13 +196,22 @@ where
         C: ConnectionTrait,
         A: 'a,
     {
-        exec_insert_with_returning::<A, _>(self.primary_key, self.query, db)
+        exec_insert_with_returning::<A, _>(
+            self.primary_key,
+            self.query,
+            self.timeout,
+            self.on_conflict_do_nothing,
+            self.upserted_row,
+            db,
+        )
     }
 }
 
 async fn exec_insert<A, C>(
     primary_key: Option<ValueTuple>,
     statement: Statement,
+    timeout: Option<Duration>,
+    on_conflict_do_nothing: bool,
     db: &C,
 ) -> Result<InsertResult<A>, DbErr>
 where
@@ -118,12 +225,19 @@ where
             let cols = PrimaryKey::<A>::iter()
                 .map(|col| col.to_string())
                 .collect::<Vec<_>>();
-            let res = db.query_one(statement).await?.unwrap();
+            let res = match db.query_one_with_timeout(statement, timeout).await? {
+                Some(res) => res,
+                None => return Err(DbErr::RecordNotInserted),
+            };
             res.try_get_many("", cols.as_ref()).ok()
         }
         false => {
-            let last_insert_id = db.execute(statement).await?.last_insert_id();
-            ValueTypeOf::<A>::try_from_u64(last_insert_id).ok()
+            let res = db.execute_with_timeout(statement, timeout).await?;
+            // an upsert updating a row to the same values affects no row either
+            if on_conflict_do_nothing && res.rows_affected() == 0 {
+                return Err(DbErr::RecordNotInserted);
+            }
+            ValueTypeOf::<A>::try_from_u64(res.last_insert_id()).ok()
         }
     };
     let last_insert_id = match primary_key {
@@ -136,9 +250,31 @@ where
     Ok(InsertResult { last_insert_id })
 }
 
+async fn exec_insert_chunked<C>(
+    statements: Vec<Statement>,
+    db: &C,
+) -> Result<InsertManyResult, DbErr>
+where
+    C: ConnectionTrait + TransactionTrait,
+{
+    let mut rows_affected = 0;
+    if statements.is_empty() {
+        return Ok(InsertManyResult { rows_affected });
+    }
+    let txn = db.begin().await?;
+    for statement in statements {
+        rows_affected += txn.execute(statement).await?.rows_affected();
+    }
+    txn.commit().await?;
+    Ok(InsertManyResult { rows_affected })
+}
+
 async fn exec_insert_with_returning<A, C>(
     primary_key: Option<ValueTuple>,
     mut insert_statement: InsertStatement,
+    timeout: Option<Duration>,
+    on_conflict_do_nothing: bool,
+    upserted_row: Option<Condition>,
     db: &C,
 ) -> Result<<A::Entity as EntityTrait>::Model, DbErr>
 where
@@ -147,28 +283,33 @@ where
     A: ActiveModelTrait,
 {
     let db_backend = db.get_database_backend();
-    let found = match db.support_returning() {
-        true => {
-            let mut returning = Query::select();
-            returning.exprs(<A::Entity as EntityTrait>::Column::iter().map(|c| {
-                let col = Expr::col(c);
-                let col_def = ColumnTrait::def(&c);
-                let col_type = col_def.get_column_type();
-                match col_type.get_enum_name() {
-                    Some(_) => col.as_enum(Alias::new("text")),
-                    None => col.into(),
-                }
-            }));
-            insert_statement.returning(returning);
+    if db.support_returning() {
+        insert_statement.returning(returning_all_columns::<A::Entity>());
+        let mut selector =
             SelectorRaw::<SelectModel<<A::Entity as EntityTrait>::Model>>::from_statement(
                 db_backend.build(&insert_statement),
-            )
-            .one(db)
-            .await?
+            );
+        selector.timeout = timeout;
+        let found = selector.one(db).await?;
+        return found.ok_or(DbErr::RecordNotInserted);
+    }
+    let insert_res = exec_insert::<A, _>(
+        primary_key,
+        build_insert(db_backend, &insert_statement, on_conflict_do_nothing),
+        timeout,
+        on_conflict_do_nothing,
+        db,
+    )
+    .await?;
+    // the last insert id of an upsert updating a row is not the id of that row
+    let found = match upserted_row {
+        Some(condition) => {
+            <A::Entity as EntityTrait>::find()
+                .filter(condition)
+                .one(db)
+                .await?
         }
-        false => {
-            let insert_res =
-                exec_insert::<A, _>(primary_key, db_backend.build(&insert_statement), db).await?;
+        None => {
             <A::Entity as EntityTrait>::find_by_id(insert_res.last_insert_id)
                 .one(db)
                 .await?
@@ -179,3 +320,478 @@ where
         None => Err(DbErr::Exec("Failed to find inserted item".to_owned())),
     }
 }
+
+async fn exec_insert_many_with_returning<A, C>(
+    primary_keys: Option<Vec<Vec<Value>>>,
+    num_rows: u64,
+    mut insert_statement: InsertStatement,
+    on_conflict: bool,
+    db: &C,
+) -> Result<Vec<<A::Entity as EntityTrait>::Model>, DbErr>
+where
+    <A::Entity as EntityTrait>::Model: IntoActiveModel<A>,
+    C: ConnectionTrait,
+    A: ActiveModelTrait,
+{
+    type PrimaryKey<A> = <<A as ActiveModelTrait>::Entity as EntityTrait>::PrimaryKey;
+    if num_rows == 0 {
+        return Ok(Vec::new());
+    }
+    let db_backend = db.get_database_backend();
+    if db.support_returning() {
+        insert_statement.returning(returning_all_columns::<A::Entity>());
+        return SelectorRaw::<SelectModel<<A::Entity as EntityTrait>::Model>>::from_statement(
+            db_backend.build(&insert_statement),
+        )
+        .all(db)
+        .await;
+    }
+
+    // the rows skipped or updated on conflict cannot be told apart from the inserted ones
+    if on_conflict {
+        return Err(DbErr::Exec(
+            "Fail to select the models of an insert with ON CONFLICT without RETURNING".to_owned(),
+        ));
+    }
+    if primary_keys.is_none() && db_backend != DbBackend::MySql {
+        return Err(DbErr::Exec(
+            "Fail to select the models of an auto-increment insert without RETURNING".to_owned(),
+        ));
+    }
+    let res = db.execute(db_backend.build(&insert_statement)).await?;
+    let last_insert_id = res.last_insert_id();
+    let mut select = <A::Entity as EntityTrait>::find();
+    for col in PrimaryKey::<A>::iter() {
+        select = select.order_by_asc(col.into_column());
+    }
+    let models = match primary_keys {
+        // Every primary key was given, select the models back by them
+        Some(keys) => {
+            let mut condition = Condition::any();
+            for key in keys.iter() {
+                let mut key_condition = Condition::all();
+                for (col, val) in PrimaryKey::<A>::iter().zip(key.iter()) {
+                    key_condition = key_condition.add(col.into_column().eq(val.clone()));
+                }
+                condition = condition.add(key_condition);
+            }
+            let mut found = select.filter(condition).all(db).await?;
+            let mut models = Vec::with_capacity(keys.len());
+            for key in keys.iter() {
+                let pos = found.iter().position(|model| {
+                    PrimaryKey::<A>::iter()
+                        .zip(key.iter())
+                        .all(|(col, val)| &model.get(col.into_column()) == val)
+                });
+                if let Some(pos) = pos {
+                    models.push(found.swap_remove(pos));
+                }
+            }
+            models
+        }
+        // Auto-increment primary key on MySQL, the last insert id is the id of the first row,
+        // and the ids of a multi-row insert are consecutive in the traditional and consecutive
+        // lock modes, with an increment of 1
+        None => {
+            let mut cols = PrimaryKey::<A>::iter();
+            let col = match (cols.next(), cols.next()) {
+                (Some(col), None) => col.into_column(),
+                _ => return Err(DbErr::Exec("Fail to unpack last_insert_id".to_owned())),
+            };
+            select
+                .filter(col.between(last_insert_id, last_insert_id + num_rows - 1))
+                .all(db)
+                .await?
+        }
+    };
+    if models.len() as u64 != num_rows {
+        return Err(DbErr::Exec("Failed to find inserted item".to_owned()));
+    }
+    Ok(models)
+}
+
+/// Select all columns of an Entity in a `RETURNING` clause
+pub(crate) fn returning_all_columns<E>() -> SelectStatement
+where
+    E: EntityTrait,
+{
+    let mut returning = Query::select();
+    returning.exprs(E::Column::iter().map(|c| {
+        let col = Expr::col(c);
+        let col_def = ColumnTrait::def(&c);
+        let col_type = col_def.get_column_type();
+        match col_type.get_enum_name() {
+            Some(_) => col.as_enum(Alias::new("text")),
+            None => col.into(),
+        }
+    }));
+    returning
+}
+
+#[cfg(test)]
+#[cfg(feature = "mock")]
+mod tests {
+    use crate::tests_cfg::cake;
+    use crate::{
+        ActiveValue, DbBackend, DbErr, EntityTrait, Insert, MockDatabase, MockExecResult,
+        OnConflict, Statement, Transaction,
+    };
+
+    fn apple_and_orange() -> [cake::ActiveModel; 2] {
+        [
+            cake::ActiveModel {
+                id: ActiveValue::not_set(),
+                name: ActiveValue::set("Apple Pie".to_owned()),
+            },
+            cake::ActiveModel {
+                id: ActiveValue::not_set(),
+                name: ActiveValue::set("Orange Scone".to_owned()),
+            },
+        ]
+    }
+
+    fn cake_model(id: i32, name: &str) -> cake::Model {
+        cake::Model {
+            id,
+            name: name.to_owned(),
+        }
+    }
+
+    #[smol_potat::test]
+    async fn insert_many_with_returning_postgres() -> Result<(), DbErr> {
+        let db = MockDatabase::new(DbBackend::Postgres)
+            .append_query_results(vec![vec![
+                cake_model(1, "Apple Pie"),
+                cake_model(2, "Orange Scone"),
+            ]])
+            .into_connection();
+
+        let models = cake::Entity::insert_many(apple_and_orange())
+            .exec_with_returning_many(&db)
+            .await?;
+
+        assert_eq!(
+            models,
+            [cake_model(1, "Apple Pie"), cake_model(2, "Orange Scone")]
+        );
+        assert_eq!(
+            db.into_transaction_log(),
+            [Transaction::from_sql_and_values(
+                DbBackend::Postgres,
+                r#"INSERT INTO "cake" ("name") VALUES ($1), ($2) RETURNING "id", "name""#,
+                vec!["Apple Pie".into(), "Orange Scone".into()]
+            )]
+        );
+
+        Ok(())
+    }
+
+    #[smol_potat::test]
+    async fn insert_many_with_returning_auto_increment() -> Result<(), DbErr> {
+        let db = MockDatabase::new(DbBackend::MySql)
+            .append_exec_results(vec![MockExecResult {
+                last_insert_id: 5,
+                rows_affected: 2,
+            }])
+            .append_query_results(vec![vec![
+                cake_model(5, "Apple Pie"),
+                cake_model(6, "Orange Scone"),
+            ]])
+            .into_connection();
+
+        let models = cake::Entity::insert_many(apple_and_orange())
+            .exec_with_returning_many(&db)
+            .await?;
+
+        assert_eq!(
+            models,
+            [cake_model(5, "Apple Pie"), cake_model(6, "Orange Scone")]
+        );
+        assert_eq!(
+            db.into_transaction_log(),
+            [
+                Transaction::from_sql_and_values(
+                    DbBackend::MySql,
+                    r#"INSERT INTO `cake` (`name`) VALUES (?), (?)"#,
+                    vec!["Apple Pie".into(), "Orange Scone".into()]
+                ),
+                Transaction::from_sql_and_values(
+                    DbBackend::MySql,
+                    [
+                        "SELECT `cake`.`id`, `cake`.`name` FROM `cake`",
+                        "WHERE `cake`.`id` BETWEEN ? AND ? ORDER BY `cake`.`id` ASC",
+                    ]
+                    .join(" ")
+                    .as_str(),
+                    vec![5u64.into(), 6u64.into()]
+                ),
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[smol_potat::test]
+    async fn insert_many_with_returning_given_keys() -> Result<(), DbErr> {
+        let db = MockDatabase::new(DbBackend::MySql)
+            .append_exec_results(vec![MockExecResult {
+                last_insert_id: 0,
+                rows_affected: 2,
+            }])
+            .append_query_results(vec![vec![
+                cake_model(3, "Orange Scone"),
+                cake_model(8, "Apple Pie"),
+            ]])
+            .into_connection();
+
+        let models = Insert::<cake::ActiveModel>::many([
+            cake_model(8, "Apple Pie"),
+            cake_model(3, "Orange Scone"),
+        ])
+        .exec_with_returning_many(&db)
+        .await?;
+
+        assert_eq!(
+            models,
+            [cake_model(8, "Apple Pie"), cake_model(3, "Orange Scone")]
+        );
+        assert_eq!(
+            db.into_transaction_log()[1],
+            Transaction::from_sql_and_values(
+                DbBackend::MySql,
+                [
+                    "SELECT `cake`.`id`, `cake`.`name` FROM `cake`",
+                    "WHERE `cake`.`id` = ? OR `cake`.`id` = ? ORDER BY `cake`.`id` ASC",
+                ]
+                .join(" ")
+                .as_str(),
+                vec![8i32.into(), 3i32.into()]
+            )
+        );
+
+        Ok(())
+    }
+
+    #[smol_potat::test]
+    async fn insert_many_with_returning_unsupported() -> Result<(), DbErr> {
+        // no RETURNING on SQLite older than 3.35, and the ids may not be consecutive
+        let db = MockDatabase::new(DbBackend::Sqlite).into_connection();
+        let res = cake::Entity::insert_many(apple_and_orange())
+            .exec_with_returning_many(&db)
+            .await;
+        assert!(matches!(res, Err(DbErr::Exec(_))));
+
+        // the rows skipped or updated on conflict cannot be told apart
+        let db = MockDatabase::new(DbBackend::MySql).into_connection();
+        let res = Insert::<cake::ActiveModel>::many([
+            cake_model(1, "Apple Pie"),
+            cake_model(2, "Orange Scone"),
+        ])
+        .on_conflict(OnConflict::column(cake::Column::Id).do_nothing().to_owned())
+        .exec_with_returning_many(&db)
+        .await;
+        assert!(matches!(res, Err(DbErr::Exec(_))));
+        assert!(db.into_transaction_log().is_empty());
+
+        Ok(())
+    }
+
+    #[smol_potat::test]
+    async fn insert_many_with_returning_empty() -> Result<(), DbErr> {
+        let db = MockDatabase::new(DbBackend::MySql).into_connection();
+
+        let models = cake::Entity::insert_many(Vec::<cake::ActiveModel>::new())
+            .exec_with_returning_many(&db)
+            .await?;
+
+        assert!(models.is_empty());
+        assert!(db.into_transaction_log().is_empty());
+
+        Ok(())
+    }
+
+    #[smol_potat::test]
+    async fn insert_on_conflict_do_nothing() -> Result<(), DbErr> {
+        let db = MockDatabase::new(DbBackend::Postgres)
+            .append_query_results(vec![Vec::<cake::Model>::new()])
+            .into_connection();
+
+        let res = Insert::<cake::ActiveModel>::one(cake_model(1, "Apple Pie"))
+            .on_conflict(OnConflict::column(cake::Column::Id).do_nothing().to_owned())
+            .exec(&db)
+            .await;
+
+        assert_eq!(res.err(), Some(DbErr::RecordNotInserted));
+        assert_eq!(
+            db.into_transaction_log(),
+            [Transaction::from_sql_and_values(
+                DbBackend::Postgres,
+                [
+                    r#"INSERT INTO "cake" ("id", "name") VALUES ($1, $2)"#,
+                    r#"ON CONFLICT ("id") DO NOTHING RETURNING "id""#,
+                ]
+                .join(" ")
+                .as_str(),
+                vec![1i32.into(), "Apple Pie".into()]
+            )]
+        );
+
+        Ok(())
+    }
+
+    #[smol_potat::test]
+    async fn insert_on_conflict_do_nothing_mysql() -> Result<(), DbErr> {
+        let db = MockDatabase::new(DbBackend::MySql)
+            .append_exec_results(vec![MockExecResult {
+                last_insert_id: 0,
+                rows_affected: 0,
+            }])
+            .into_connection();
+
+        let res = Insert::<cake::ActiveModel>::one(cake_model(1, "Apple Pie"))
+            .on_conflict(OnConflict::column(cake::Column::Id).do_nothing().to_owned())
+            .exec_with_returning(&db)
+            .await;
+
+        assert_eq!(res.err(), Some(DbErr::RecordNotInserted));
+        assert_eq!(
+            db.into_transaction_log(),
+            [Transaction::from_sql_and_values(
+                DbBackend::MySql,
+                "INSERT IGNORE INTO `cake` (`id`, `name`) VALUES (?, ?)",
+                vec![1i32.into(), "Apple Pie".into()]
+            )]
+        );
+
+        Ok(())
+    }
+
+    #[smol_potat::test]
+    async fn insert_on_conflict_update_by_target() -> Result<(), DbErr> {
+        // the last insert id of an updated row is not its id
+        let db = MockDatabase::new(DbBackend::MySql)
+            .append_exec_results(vec![MockExecResult {
+                last_insert_id: 7,
+                rows_affected: 2,
+            }])
+            .append_query_results(vec![vec![cake_model(3, "Apple Pie")]])
+            .into_connection();
+
+        let model = Insert::one(cake::ActiveModel {
+            id: ActiveValue::not_set(),
+            name: ActiveValue::set("Apple Pie".to_owned()),
+        })
+        .on_conflict(
+            OnConflict::column(cake::Column::Name)
+                .update_column(cake::Column::Name)
+                .to_owned(),
+        )
+        .exec_with_returning(&db)
+        .await?;
+
+        assert_eq!(model, cake_model(3, "Apple Pie"));
+        assert_eq!(
+            db.into_transaction_log()[1],
+            Transaction::from_sql_and_values(
+                DbBackend::MySql,
+                "SELECT `cake`.`id`, `cake`.`name` FROM `cake` WHERE `cake`.`name` = ? LIMIT ?",
+                vec!["Apple Pie".into(), 1u64.into()]
+            )
+        );
+
+        Ok(())
+    }
+
+    #[smol_potat::test]
+    async fn insert_on_conflict_update_with_returning() -> Result<(), DbErr> {
+        // updating the row to the same values affects no row
+        let db = MockDatabase::new(DbBackend::MySql)
+            .append_exec_results(vec![MockExecResult {
+                last_insert_id: 1,
+                rows_affected: 0,
+            }])
+            .append_query_results(vec![vec![cake_model(1, "Apple Pie")]])
+            .into_connection();
+
+        let model = Insert::<cake::ActiveModel>::one(cake_model(1, "Apple Pie"))
+            .on_conflict(
+                OnConflict::column(cake::Column::Id)
+                    .update_column(cake::Column::Name)
+                    .to_owned(),
+            )
+            .exec_with_returning(&db)
+            .await?;
+
+        assert_eq!(model, cake_model(1, "Apple Pie"));
+        assert_eq!(
+            db.into_transaction_log(),
+            [
+                Transaction::from_sql_and_values(
+                    DbBackend::MySql,
+                    [
+                        "INSERT INTO `cake` (`id`, `name`) VALUES (?, ?)",
+                        "ON DUPLICATE KEY UPDATE `name` = VALUES(`name`)",
+                    ]
+                    .join(" ")
+                    .as_str(),
+                    vec![1i32.into(), "Apple Pie".into()]
+                ),
+                Transaction::from_sql_and_values(
+                    DbBackend::MySql,
+                    "SELECT `cake`.`id`, `cake`.`name` FROM `cake` WHERE `cake`.`id` = ? LIMIT ?",
+                    vec![1i32.into(), 1u64.into()]
+                ),
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[smol_potat::test]
+    async fn insert_chunked() -> Result<(), DbErr> {
+        let db = MockDatabase::new(DbBackend::Sqlite)
+            .append_exec_results(vec![
+                MockExecResult {
+                    last_insert_id: 32766,
+                    rows_affected: 32766,
+                },
+                MockExecResult {
+                    last_insert_id: 32767,
+                    rows_affected: 1,
+                },
+            ])
+            .into_connection();
+
+        let names: Vec<String> = (1..=32767).map(|i| format!("Cake {}", i)).collect();
+        let res = Insert::<cake::ActiveModel>::many(names.iter().map(|name| cake::ActiveModel {
+            id: ActiveValue::not_set(),
+            name: ActiveValue::set(name.clone()),
+        }))
+        .exec_chunked(&db)
+        .await?;
+
+        assert_eq!(res.rows_affected, 32767);
+
+        let insert = |names: &[String]| {
+            Statement::from_sql_and_values(
+                DbBackend::Sqlite,
+                &format!(
+                    r#"INSERT INTO "cake" ("name") VALUES {}"#,
+                    vec!["(?)"; names.len()].join(", ")
+                ),
+                names.iter().map(|name| name.as_str().into()),
+            )
+        };
+        assert_eq!(
+            db.into_transaction_log(),
+            [Transaction::many([
+                Statement::from_string(DbBackend::Postgres, "BEGIN".to_owned()),
+                insert(&names[..32766]),
+                insert(&names[32766..]),
+                Statement::from_string(DbBackend::Sqlite, "COMMIT".to_owned()),
+            ])]
+        );
+
+        Ok(())
+    }
+}
@@ -1,3 +1,5 @@
+mod aggregate;
+mod cursor;
 mod delete;
 mod execute;
 mod insert;
@@ -6,6 +8,7 @@ mod query;
 mod select;
 mod update;
 
+pub use cursor::*;
 pub use delete::*;
 pub use execute::*;
 pub use insert::*;
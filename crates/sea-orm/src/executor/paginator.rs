@@ -1,11 +1,11 @@
 use crate::{
     error::*, ConnectionTrait, DbBackend, EntityTrait, FromQueryResult, Select, SelectModel,
-    SelectTwo, SelectTwoModel, Selector, SelectorRaw, SelectorTrait,
+    SelectThree, SelectThreeModel, SelectTwo, SelectTwoModel, Selector, SelectorRaw, SelectorTrait,
 };
 use async_stream::stream;
 use futures::Stream;
 use sea_query::{Alias, Expr, SelectStatement};
-use std::{marker::PhantomData, pin::Pin};
+use std::{marker::PhantomData, pin::Pin, time::Duration};
 
 /// Pin a Model so that stream operations can be performed on the model
 pub type PinBoxStream<'db, Item> = Pin<Box<dyn Stream<Item = Item> + 'db>>;
@@ -21,6 +21,7 @@ where
     pub(crate) page: usize,
     pub(crate) page_size: usize,
     pub(crate) db: &'db C,
+    pub(crate) timeout: Option<Duration>,
     pub(crate) selector: PhantomData<S>,
 }
 
@@ -41,7 +42,7 @@ where
             .to_owned();
         let builder = self.db.get_database_backend();
         let stmt = builder.build(&query);
-        let rows = self.db.query_all(stmt).await?;
+        let rows = self.db.query_all_with_timeout(stmt, self.timeout).await?;
         let mut buffer = Vec::with_capacity(rows.len());
         for row in rows.into_iter() {
             // TODO: Error handling
@@ -55,6 +56,12 @@ where
         self.fetch_page(self.page).await
     }
 
+    /// Set the maximum execution time of each query, overriding the statement timeout of the connection
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
     /// Get the total number of items
     pub async fn num_items(&self) -> Result<usize, DbErr> {
         let builder = self.db.get_database_backend();
@@ -66,7 +73,7 @@ where
                     Alias::new("sub_query"),
                 ),
         );
-        let result = match self.db.query_one(stmt).await? {
+        let result = match self.db.query_one_with_timeout(stmt, self.timeout).await? {
             Some(res) => res,
             None => return Ok(0),
         };
@@ -214,6 +221,7 @@ where
             page: 0,
             page_size,
             db,
+            timeout: self.timeout,
             selector: PhantomData,
         }
     }
@@ -239,6 +247,7 @@ where
             page: 0,
             page_size,
             db,
+            timeout: self.timeout,
             selector: PhantomData,
         }
     }
@@ -272,6 +281,23 @@ where
     }
 }
 
+impl<'db, C, M, N, O, E, F, G> PaginatorTrait<'db, C> for SelectThree<E, F, G>
+where
+    C: ConnectionTrait,
+    E: EntityTrait<Model = M>,
+    F: EntityTrait<Model = N>,
+    G: EntityTrait<Model = O>,
+    M: FromQueryResult + Sized + Send + Sync + 'db,
+    N: FromQueryResult + Sized + Send + Sync + 'db,
+    O: FromQueryResult + Sized + Send + Sync + 'db,
+{
+    type Selector = SelectThreeModel<M, N, O>;
+
+    fn paginate(self, db: &'db C, page_size: usize) -> Paginator<'db, C, Self::Selector> {
+        self.into_model().paginate(db, page_size)
+    }
+}
+
 #[cfg(test)]
 #[cfg(feature = "mock")]
 mod tests {
@@ -361,6 +387,54 @@ mod tests {
         Ok(())
     }
 
+    #[smol_potat::test]
+    async fn fetch_page_with_timeout() -> Result<(), DbErr> {
+        let (db, pages) = setup();
+        let timeout = std::time::Duration::from_secs(3);
+
+        let paginator = fruit::Entity::find()
+            .into_model::<fruit::Model>()
+            .timeout(timeout)
+            .paginate(&db, 2);
+
+        assert_eq!(paginator.timeout, Some(timeout));
+        assert_eq!(paginator.fetch_page(0).await?, pages[0].clone());
+
+        let paginator = fruit::Entity::find()
+            .from_raw_sql(RAW_STMT.clone())
+            .paginate(&db, 2)
+            .timeout(timeout);
+
+        assert_eq!(paginator.timeout, Some(timeout));
+        assert_eq!(paginator.fetch_page(0).await?, pages[1].clone());
+
+        let select = SelectStatement::new()
+            .exprs(vec![
+                Expr::tbl(fruit::Entity, fruit::Column::Id),
+                Expr::tbl(fruit::Entity, fruit::Column::Name),
+                Expr::tbl(fruit::Entity, fruit::Column::CakeId),
+            ])
+            .from(fruit::Entity)
+            .offset(0)
+            .limit(2)
+            .to_owned();
+
+        let query_builder = db.get_database_backend();
+        let stmts = vec![
+            query_builder.build(&select),
+            query_builder.build(
+                SelectStatement::new()
+                    .expr(Expr::cust(
+                        r#""fruit"."id", "fruit"."name", "fruit"."cake_id" FROM "fruit""#,
+                    ))
+                    .offset(0)
+                    .limit(2),
+            ),
+        ];
+        assert_eq!(db.into_transaction_log(), Transaction::wrap(stmts));
+        Ok(())
+    }
+
     #[smol_potat::test]
     async fn fetch_page_raw() -> Result<(), DbErr> {
         let (db, pages) = setup();
@@ -135,6 +135,54 @@ macro_rules! try_getable_all {
     };
 }
 
+/// SQLite is dynamically typed, a REAL column can hold an INTEGER value. The declared type of
+/// the column makes the value decodable as a float, but the row of a `RETURNING` clause does
+/// not carry it, so an INTEGER value is decoded and converted as a fallback
+macro_rules! try_getable_float {
+    ( $type: ty ) => {
+        impl TryGetable for $type {
+            fn try_get(res: &QueryResult, pre: &str, col: &str) -> Result<Self, TryGetError> {
+                let column = format!("{}{}", pre, col);
+                match &res.row {
+                    #[cfg(feature = "sqlx-mysql")]
+                    QueryResultRow::SqlxMySql(row) => {
+                        use sqlx::Row;
+                        row.try_get::<Option<$type>, _>(column.as_str())
+                            .map_err(|e| TryGetError::DbErr(crate::sqlx_error_to_query_err(e)))
+                            .and_then(|opt| opt.ok_or(TryGetError::Null))
+                    }
+                    #[cfg(feature = "sqlx-postgres")]
+                    QueryResultRow::SqlxPostgres(row) => {
+                        use sqlx::Row;
+                        row.try_get::<Option<$type>, _>(column.as_str())
+                            .map_err(|e| TryGetError::DbErr(crate::sqlx_error_to_query_err(e)))
+                            .and_then(|opt| opt.ok_or(TryGetError::Null))
+                    }
+                    #[cfg(feature = "sqlx-sqlite")]
+                    QueryResultRow::SqlxSqlite(row) => {
+                        use sqlx::Row;
+                        row.try_get::<Option<$type>, _>(column.as_str())
+                            .or_else(|e| match row.try_get::<Option<i64>, _>(column.as_str()) {
+                                Ok(v) => Ok(v.map(|v| v as $type)),
+                                Err(_) => Err(e),
+                            })
+                            .map_err(|e| TryGetError::DbErr(crate::sqlx_error_to_query_err(e)))
+                            .and_then(|opt| opt.ok_or(TryGetError::Null))
+                    }
+                    #[cfg(feature = "mock")]
+                    #[allow(unused_variables)]
+                    QueryResultRow::Mock(row) => row.try_get(column.as_str()).map_err(|e| {
+                        debug_print!("{:#?}", e.to_string());
+                        TryGetError::Null
+                    }),
+                    #[allow(unreachable_patterns)]
+                    _ => unreachable!(),
+                }
+            }
+        }
+    };
+}
+
 macro_rules! try_getable_unsigned {
     ( $type: ty ) => {
         impl TryGetable for $type {
@@ -300,8 +348,8 @@ try_getable_unsigned!(u8);
 try_getable_unsigned!(u16);
 try_getable_all!(u32);
 try_getable_mysql!(u64);
-try_getable_all!(f32);
-try_getable_all!(f64);
+try_getable_float!(f32);
+try_getable_float!(f64);
 try_getable_all!(String);
 try_getable_all!(Vec<u8>);
 
@@ -361,20 +409,14 @@ impl TryGetable for Decimal {
                     .and_then(|opt| opt.ok_or(TryGetError::Null))
             }
             #[cfg(feature = "sqlx-sqlite")]
-            QueryResultRow::SqlxSqlite(row) => {
-                use sqlx::Row;
-                let val: Option<f64> = row
-                    .try_get(column.as_str())
-                    .map_err(|e| TryGetError::DbErr(crate::sqlx_error_to_query_err(e)))?;
+            QueryResultRow::SqlxSqlite(_) => {
                 use rust_decimal::prelude::FromPrimitive;
-                match val {
-                    Some(v) => Decimal::from_f64(v).ok_or_else(|| {
-                        TryGetError::DbErr(DbErr::Query(
-                            "Failed to convert f64 into Decimal".to_owned(),
-                        ))
-                    }),
-                    None => Err(TryGetError::Null),
-                }
+                let val = <f64 as TryGetable>::try_get(res, pre, col)?;
+                Decimal::from_f64(val).ok_or_else(|| {
+                    TryGetError::DbErr(DbErr::Query(
+                        "Failed to convert f64 into Decimal".to_owned(),
+                    ))
+                })
             }
             #[cfg(feature = "mock")]
             #[allow(unused_variables)]
@@ -1,12 +1,13 @@
 use crate::{
     error::*, ConnectionTrait, EntityTrait, FromQueryResult, IdenStatic, Iterable, ModelTrait,
-    PrimaryKeyToColumn, QueryResult, Select, SelectA, SelectB, SelectTwo, SelectTwoMany, Statement,
-    StreamTrait, TryGetableMany,
+    PartialModelTrait, PrimaryKeyToColumn, QueryResult, QuerySelect, Select, SelectA, SelectB,
+    SelectC, SelectThree, SelectTwo, SelectTwoMany, Statement, StreamTrait, TryGetableMany,
 };
 use futures::{Stream, TryStreamExt};
 use sea_query::SelectStatement;
 use std::marker::PhantomData;
 use std::pin::Pin;
+use std::time::Duration;
 
 #[cfg(feature = "with-json")]
 use crate::JsonValue;
@@ -18,6 +19,7 @@ where
     S: SelectorTrait,
 {
     pub(crate) query: SelectStatement,
+    pub(crate) timeout: Option<Duration>,
     selector: S,
 }
 
@@ -28,6 +30,7 @@ where
     S: SelectorTrait,
 {
     pub(crate) stmt: Statement,
+    pub(crate) timeout: Option<Duration>,
     #[allow(dead_code)]
     selector: S,
 }
@@ -71,6 +74,17 @@ where
     model: PhantomData<(M, N)>,
 }
 
+/// Defines a type to get three Models
+#[derive(Clone, Debug)]
+pub struct SelectThreeModel<M, N, O>
+where
+    M: FromQueryResult,
+    N: FromQueryResult,
+    O: FromQueryResult,
+{
+    model: PhantomData<(M, N, O)>,
+}
+
 impl<T, C> SelectorTrait for SelectGetableValue<T, C>
 where
     T: TryGetableMany,
@@ -110,6 +124,23 @@ where
     }
 }
 
+impl<M, N, O> SelectorTrait for SelectThreeModel<M, N, O>
+where
+    M: FromQueryResult + Sized,
+    N: FromQueryResult + Sized,
+    O: FromQueryResult + Sized,
+{
+    type Item = (M, Option<N>, Option<O>);
+
+    fn from_raw_query_result(res: QueryResult) -> Result<Self::Item, DbErr> {
+        Ok((
+            M::from_query_result(&res, SelectA.as_str())?,
+            N::from_query_result_optional(&res, SelectB.as_str())?,
+            O::from_query_result_optional(&res, SelectC.as_str())?,
+        ))
+    }
+}
+
 impl<E> Select<E>
 where
     E: EntityTrait,
@@ -119,6 +150,7 @@ where
     pub fn from_raw_sql(self, stmt: Statement) -> SelectorRaw<SelectModel<E::Model>> {
         SelectorRaw {
             stmt,
+            timeout: None,
             selector: SelectModel { model: PhantomData },
         }
     }
@@ -130,15 +162,95 @@ where
     {
         Selector {
             query: self.query,
+            timeout: None,
             selector: SelectModel { model: PhantomData },
         }
     }
 
+    /// Return a [Selector] from `Self` that selects only the columns needed by the partial Model `P`
+    ///
+    /// ```
+    /// # use sea_orm::{error::*, tests_cfg::*, *};
+    /// #
+    /// # #[smol_potat::main]
+    /// # #[cfg(all(feature = "mock", feature = "macros"))]
+    /// # pub async fn main() -> Result<(), DbErr> {
+    /// #
+    /// # let db = MockDatabase::new(DbBackend::Postgres)
+    /// #     .append_query_results(vec![vec![
+    /// #         maplit::btreemap! {
+    /// #             "name" => Into::<Value>::into("Chocolate Forest"),
+    /// #             "fruit_id" => Into::<Value>::into(2),
+    /// #             "fruit_name" => Into::<Value>::into("Cherry"),
+    /// #         },
+    /// #     ]])
+    /// #     .into_connection();
+    /// #
+    /// use sea_orm::{entity::*, query::*, tests_cfg::{cake, fruit}, DerivePartialModel};
+    ///
+    /// #[derive(Debug, PartialEq, DerivePartialModel)]
+    /// #[sea_orm(entity = "fruit::Entity")]
+    /// struct FruitItem {
+    ///     id: i32,
+    ///     name: String,
+    /// }
+    ///
+    /// #[derive(Debug, PartialEq, DerivePartialModel)]
+    /// #[sea_orm(entity = "cake::Entity")]
+    /// struct CakeWithFruit {
+    ///     name: String,
+    ///     #[sea_orm(nested)]
+    ///     fruit: FruitItem,
+    /// }
+    ///
+    /// let res: Vec<CakeWithFruit> = cake::Entity::find()
+    ///     .inner_join(fruit::Entity)
+    ///     .into_partial_model::<CakeWithFruit>()
+    ///     .all(&db)
+    ///     .await?;
+    ///
+    /// assert_eq!(
+    ///     res,
+    ///     vec![CakeWithFruit {
+    ///         name: "Chocolate Forest".to_owned(),
+    ///         fruit: FruitItem {
+    ///             id: 2,
+    ///             name: "Cherry".to_owned(),
+    ///         },
+    ///     }]
+    /// );
+    ///
+    /// assert_eq!(
+    ///     db.into_transaction_log(),
+    ///     vec![Transaction::from_sql_and_values(
+    ///         DbBackend::Postgres,
+    ///         [
+    ///             r#"SELECT "cake"."name" AS "name","#,
+    ///             r#""fruit"."id" AS "fruit_id", "fruit"."name" AS "fruit_name""#,
+    ///             r#"FROM "cake" INNER JOIN "fruit" ON "cake"."id" = "fruit"."cake_id""#,
+    ///         ]
+    ///         .join(" ")
+    ///         .as_str(),
+    ///         vec![]
+    ///     )]
+    /// );
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn into_partial_model<P>(self) -> Selector<SelectModel<P>>
+    where
+        P: PartialModelTrait,
+    {
+        P::select_cols(QuerySelect::select_only(self)).into_model()
+    }
+
     /// Get a selectable Model as a [JsonValue] for SQL JSON operations
     #[cfg(feature = "with-json")]
     pub fn into_json(self) -> Selector<SelectModel<JsonValue>> {
         Selector {
             query: self.query,
+            timeout: None,
             selector: SelectModel { model: PhantomData },
         }
     }
@@ -294,6 +406,7 @@ where
     {
         Selector {
             query: self.query,
+            timeout: None,
             selector: SelectTwoModel { model: PhantomData },
         }
     }
@@ -303,6 +416,7 @@ where
     pub fn into_json(self) -> Selector<SelectTwoModel<JsonValue, JsonValue>> {
         Selector {
             query: self.query,
+            timeout: None,
             selector: SelectTwoModel { model: PhantomData },
         }
     }
@@ -335,6 +449,73 @@ where
     }
 }
 
+impl<E, F, G> SelectThree<E, F, G>
+where
+    E: EntityTrait,
+    F: EntityTrait,
+    G: EntityTrait,
+{
+    /// Perform a conversion into a [SelectThreeModel]
+    pub fn into_model<M, N, O>(self) -> Selector<SelectThreeModel<M, N, O>>
+    where
+        M: FromQueryResult,
+        N: FromQueryResult,
+        O: FromQueryResult,
+    {
+        Selector {
+            query: self.query,
+            timeout: None,
+            selector: SelectThreeModel { model: PhantomData },
+        }
+    }
+
+    /// Convert the Models into JsonValue
+    #[cfg(feature = "with-json")]
+    pub fn into_json(self) -> Selector<SelectThreeModel<JsonValue, JsonValue, JsonValue>> {
+        Selector {
+            query: self.query,
+            timeout: None,
+            selector: SelectThreeModel { model: PhantomData },
+        }
+    }
+
+    /// Get one Model from the Select query
+    pub async fn one<C>(
+        self,
+        db: &C,
+    ) -> Result<Option<(E::Model, Option<F::Model>, Option<G::Model>)>, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        self.into_model().one(db).await
+    }
+
+    /// Get all Models from the Select query
+    pub async fn all<C>(
+        self,
+        db: &C,
+    ) -> Result<Vec<(E::Model, Option<F::Model>, Option<G::Model>)>, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        self.into_model().all(db).await
+    }
+
+    /// Stream the results of a Select operation on a Model
+    pub async fn stream<'a: 'b, 'b, C>(
+        self,
+        db: &'a C,
+    ) -> Result<
+        impl Stream<Item = Result<(E::Model, Option<F::Model>, Option<G::Model>), DbErr>> + 'b,
+        DbErr,
+    >
+    where
+        C: ConnectionTrait + StreamTrait<'a> + Send,
+    {
+        self.into_model().stream(db).await
+    }
+}
+
 impl<E, F> SelectTwoMany<E, F>
 where
     E: EntityTrait,
@@ -348,6 +529,7 @@ where
     {
         Selector {
             query: self.query,
+            timeout: None,
             selector: SelectTwoModel { model: PhantomData },
         }
     }
@@ -357,6 +539,7 @@ where
     pub fn into_json(self) -> Selector<SelectTwoModel<JsonValue, JsonValue>> {
         Selector {
             query: self.query,
+            timeout: None,
             selector: SelectTwoModel { model: PhantomData },
         }
     }
@@ -412,6 +595,7 @@ where
     {
         Selector {
             query,
+            timeout: None,
             selector: SelectGetableValue {
                 columns: PhantomData,
                 model: PhantomData,
@@ -427,10 +611,51 @@ where
         let stmt = builder.build(&self.query);
         SelectorRaw {
             stmt,
+            timeout: self.timeout,
             selector: self.selector,
         }
     }
 
+    /// Set the maximum execution time of the query, overriding the statement timeout of the connection.
+    /// It is not applied to [Selector::stream]
+    ///
+    /// ```
+    /// # use sea_orm::{error::*, tests_cfg::*, *};
+    /// #
+    /// # #[smol_potat::main]
+    /// # #[cfg(feature = "mock")]
+    /// # pub async fn main() -> Result<(), DbErr> {
+    /// #
+    /// # let db = MockDatabase::new(DbBackend::Postgres)
+    /// #     .append_query_results(vec![Vec::<cake::Model>::new()])
+    /// #     .into_connection();
+    /// #
+    /// use sea_orm::{entity::*, query::*, tests_cfg::cake};
+    /// use std::time::Duration;
+    ///
+    /// let _: Vec<cake::Model> = cake::Entity::find()
+    ///     .into_model()
+    ///     .timeout(Duration::from_secs(5))
+    ///     .all(&db)
+    ///     .await?;
+    ///
+    /// assert_eq!(
+    ///     db.into_transaction_log(),
+    ///     vec![Transaction::from_sql_and_values(
+    ///         DbBackend::Postgres,
+    ///         r#"SELECT "cake"."id", "cake"."name" FROM "cake""#,
+    ///         vec![]
+    ///     )]
+    /// );
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
     /// Get an item from the Select query
     pub async fn one<'a, C>(mut self, db: &C) -> Result<Option<S::Item>, DbErr>
     where
@@ -473,6 +698,7 @@ where
     {
         SelectorRaw {
             stmt,
+            timeout: None,
             selector: SelectModel { model: PhantomData },
         }
     }
@@ -486,6 +712,7 @@ where
     {
         SelectorRaw {
             stmt,
+            timeout: None,
             selector: SelectGetableValue {
                 columns: PhantomData,
                 model: PhantomData,
@@ -563,6 +790,7 @@ where
     {
         SelectorRaw {
             stmt: self.stmt,
+            timeout: self.timeout,
             selector: SelectModel { model: PhantomData },
         }
     }
@@ -627,10 +855,17 @@ where
     pub fn into_json(self) -> SelectorRaw<SelectModel<JsonValue>> {
         SelectorRaw {
             stmt: self.stmt,
+            timeout: self.timeout,
             selector: SelectModel { model: PhantomData },
         }
     }
 
+    /// Set the maximum execution time of the query, overriding the statement timeout of the connection
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
     /// Get an item from the Select query
     /// ```
     /// # use sea_orm::{error::*, tests_cfg::*, *};
@@ -675,7 +910,7 @@ where
     where
         C: ConnectionTrait,
     {
-        let row = db.query_one(self.stmt).await?;
+        let row = db.query_one_with_timeout(self.stmt, self.timeout).await?;
         match row {
             Some(row) => Ok(Some(S::from_raw_query_result(row)?)),
             None => Ok(None),
@@ -726,7 +961,7 @@ where
     where
         C: ConnectionTrait,
     {
-        let rows = db.query_all(self.stmt).await?;
+        let rows = db.query_all_with_timeout(self.stmt, self.timeout).await?;
         let mut models = Vec::new();
         for row in rows.into_iter() {
             models.push(S::from_raw_query_result(row)?);
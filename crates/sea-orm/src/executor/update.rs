@@ -1,15 +1,16 @@
 use crate::{
-    error::*, ActiveModelTrait, ColumnTrait, ConnectionTrait, EntityTrait, IntoActiveModel,
-    Iterable, SelectModel, SelectorRaw, Statement, UpdateMany, UpdateOne,
+    error::*, returning_all_columns, ActiveModelTrait, ConnectionTrait, EntityTrait,
+    IntoActiveModel, SelectModel, SelectorRaw, Statement, UpdateMany, UpdateOne,
 };
-use sea_query::{Alias, Expr, FromValueTuple, Query, UpdateStatement};
-use std::future::Future;
+use sea_query::{FromValueTuple, UpdateStatement};
+use std::{future::Future, time::Duration};
 
 /// Defines an update operation
 #[derive(Clone, Debug)]
 pub struct Updater {
     query: UpdateStatement,
     check_record_exists: bool,
+    timeout: Option<Duration>,
 }
 
 /// The result of an update operation on an ActiveModel
@@ -46,6 +47,19 @@ where
         // so that self is dropped before entering await
         exec_update_only(self.query, db)
     }
+
+    /// Execute an update operation and return the updated models (use `RETURNING` syntax,
+    /// supported by Postgres and SQLite 3.35 or later)
+    pub fn exec_with_returning<C>(
+        self,
+        db: &'a C,
+    ) -> impl Future<Output = Result<Vec<E::Model>, DbErr>> + '_
+    where
+        C: ConnectionTrait,
+    {
+        // so that self is dropped before entering await
+        exec_update_with_returning::<E, _>(self.query, db)
+    }
 }
 
 impl Updater {
@@ -54,6 +68,7 @@ impl Updater {
         Self {
             query,
             check_record_exists: false,
+            timeout: None,
         }
     }
 
@@ -63,13 +78,27 @@ impl Updater {
         self
     }
 
+    /// Set the maximum execution time of the update statement, overriding the statement timeout of the connection
+    ///
+    /// On MySQL the timeout has no effect on the update statement, because `MAX_EXECUTION_TIME`
+    /// only applies to read only `SELECT` statements
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
     /// Execute an update operation
     pub fn exec<'a, C>(self, db: &'a C) -> impl Future<Output = Result<UpdateResult, DbErr>> + '_
     where
         C: ConnectionTrait,
     {
         let builder = db.get_database_backend();
-        exec_update(builder.build(&self.query), db, self.check_record_exists)
+        exec_update(
+            builder.build(&self.query),
+            db,
+            self.check_record_exists,
+            self.timeout,
+        )
     }
 }
 
@@ -91,17 +120,7 @@ where
 {
     match db.support_returning() {
         true => {
-            let mut returning = Query::select();
-            returning.exprs(<A::Entity as EntityTrait>::Column::iter().map(|c| {
-                let col = Expr::col(c);
-                let col_def = c.def();
-                let col_type = col_def.get_column_type();
-                match col_type.get_enum_name() {
-                    Some(_) => col.as_enum(Alias::new("text")),
-                    None => col.into(),
-                }
-            }));
-            query.returning(returning);
+            query.returning(returning_all_columns::<A::Entity>());
             let db_backend = db.get_database_backend();
             let found: Option<<A::Entity as EntityTrait>::Model> =
                 SelectorRaw::<SelectModel<<A::Entity as EntityTrait>::Model>>::from_statement(
@@ -136,15 +155,37 @@ where
     }
 }
 
+async fn exec_update_with_returning<E, C>(
+    mut query: UpdateStatement,
+    db: &C,
+) -> Result<Vec<E::Model>, DbErr>
+where
+    E: EntityTrait,
+    C: ConnectionTrait,
+{
+    if !db.support_returning() {
+        return Err(DbErr::Exec(format!(
+            "`RETURNING` on UPDATE is not supported by {:?}",
+            db.get_database_backend()
+        )));
+    }
+    let db_backend = db.get_database_backend();
+    query.returning(returning_all_columns::<E>());
+    SelectorRaw::<SelectModel<E::Model>>::from_statement(db_backend.build(&query))
+        .all(db)
+        .await
+}
+
 async fn exec_update<C>(
     statement: Statement,
     db: &C,
     check_record_exists: bool,
+    timeout: Option<Duration>,
 ) -> Result<UpdateResult, DbErr>
 where
     C: ConnectionTrait,
 {
-    let result = db.execute(statement).await?;
+    let result = db.execute_with_timeout(statement, timeout).await?;
     if check_record_exists && result.rows_affected() == 0 {
         return Err(DbErr::RecordNotFound(
             "None of the database rows are affected".to_owned(),
@@ -280,4 +321,50 @@ mod tests {
 
         Ok(())
     }
+
+    #[smol_potat::test]
+    async fn update_many_with_returning() -> Result<(), DbErr> {
+        let db = MockDatabase::new(DbBackend::Postgres)
+            .append_query_results(vec![vec![
+                cake::Model {
+                    id: 1,
+                    name: "Cheese Cake".to_owned(),
+                },
+                cake::Model {
+                    id: 2,
+                    name: "Cheese Cake".to_owned(),
+                },
+            ]])
+            .into_connection();
+
+        let models = Update::many(cake::Entity)
+            .col_expr(cake::Column::Name, Expr::value("Cheese Cake".to_owned()))
+            .filter(cake::Column::Id.lte(2))
+            .exec_with_returning(&db)
+            .await?;
+
+        assert_eq!(models.len(), 2);
+        assert_eq!(
+            db.into_transaction_log(),
+            vec![Transaction::from_sql_and_values(
+                DbBackend::Postgres,
+                r#"UPDATE "cake" SET "name" = $1 WHERE "cake"."id" <= $2 RETURNING "id", "name""#,
+                vec!["Cheese Cake".into(), 2i32.into()]
+            )]
+        );
+
+        let db = MockDatabase::new(DbBackend::MySql).into_connection();
+        assert_eq!(
+            Update::many(cake::Entity)
+                .col_expr(cake::Column::Name, Expr::value("Cheese Cake".to_owned()))
+                .exec_with_returning(&db)
+                .await,
+            Err(DbErr::Exec(
+                "`RETURNING` on UPDATE is not supported by MySql".to_owned()
+            ))
+        );
+        assert_eq!(db.into_transaction_log(), vec![]);
+
+        Ok(())
+    }
 }
@@ -329,8 +329,8 @@ pub use schema::*;
 #[cfg(feature = "macros")]
 pub use sea_orm_macros::{
     DeriveActiveEnum, DeriveActiveModel, DeriveActiveModelBehavior, DeriveColumn,
-    DeriveCustomColumn, DeriveEntity, DeriveEntityModel, DeriveIntoActiveModel, DeriveModel,
-    DerivePrimaryKey, DeriveRelation, FromQueryResult,
+    DeriveCustomColumn, DeriveEntity, DeriveEntityModel, DeriveFactory, DeriveIntoActiveModel,
+    DeriveModel, DerivePartialModel, DerivePrimaryKey, DeriveRelation, FromQueryResult,
 };
 
 pub use sea_query;
@@ -1,3 +1,4 @@
+use crate::{DbBackend, DbErr, DriverErrKind, ExecResult, QueryResult, Statement};
 use std::{sync::Arc, time::Duration};
 
 pub(crate) type Callback = Arc<dyn Fn(&Info<'_>) + Send + Sync>;
@@ -14,41 +15,354 @@ pub struct Info<'a> {
     pub statement: &'a crate::Statement,
     /// Query execution failed
     pub failed: bool,
+    /// The number of rows affected by an execution
+    pub rows_affected: Option<u64>,
+    /// The number of rows returned by a query, unknown for a stream
+    pub rows_returned: Option<u64>,
+    /// The kind of the error reported by the driver, if the query failed and the error is classified
+    pub error_kind: Option<DriverErrKind>,
+    /// Whether the statement ran inside a transaction
+    pub in_transaction: bool,
+    /// The operation of the statement, i.e. its first keyword such as `SELECT`
+    pub operation: &'a str,
+    /// The table targeted by the statement, usually the table of the Entity, if it can be told
+    pub table: Option<&'a str>,
+}
+
+impl<'a> Info<'a> {
+    pub(crate) fn new<R>(
+        statement: &'a Statement,
+        elapsed: Duration,
+        in_transaction: bool,
+        res: &R,
+    ) -> Self
+    where
+        R: MetricResult,
+    {
+        let (operation, table) = parse_operation_and_table(&statement.sql);
+        Self {
+            elapsed,
+            statement,
+            failed: res.failed(),
+            rows_affected: res.rows_affected(),
+            rows_returned: res.rows_returned(),
+            error_kind: res.error_kind(),
+            in_transaction,
+            operation,
+            table,
+        }
+    }
+}
+
+/// The outcome of a statement, as reported in [Info]
+pub(crate) trait MetricResult {
+    fn failed(&self) -> bool {
+        false
+    }
+
+    fn rows_affected(&self) -> Option<u64> {
+        None
+    }
+
+    fn rows_returned(&self) -> Option<u64> {
+        None
+    }
+
+    fn error_kind(&self) -> Option<DriverErrKind> {
+        None
+    }
+}
+
+/// A stream of results, the number of rows is unknown when the stream is created
+#[cfg(feature = "sqlx-dep")]
+pub(crate) struct Streamed;
+
+#[cfg(feature = "sqlx-dep")]
+impl MetricResult for Streamed {}
+
+impl MetricResult for ExecResult {
+    fn rows_affected(&self) -> Option<u64> {
+        Some(ExecResult::rows_affected(self))
+    }
+}
+
+impl MetricResult for Option<QueryResult> {
+    fn rows_returned(&self) -> Option<u64> {
+        Some(self.is_some() as u64)
+    }
+}
+
+impl MetricResult for Vec<QueryResult> {
+    fn rows_returned(&self) -> Option<u64> {
+        Some(self.len() as u64)
+    }
+}
+
+/// An error that may be classified by a [DriverErrKind]
+pub(crate) trait MetricError {
+    fn error_kind(&self) -> Option<DriverErrKind>;
+}
+
+impl MetricError for DbErr {
+    fn error_kind(&self) -> Option<DriverErrKind> {
+        self.kind()
+    }
+}
+
+#[cfg(feature = "sqlx-dep")]
+impl MetricError for sqlx::Error {
+    fn error_kind(&self) -> Option<DriverErrKind> {
+        crate::driver::sqlx_error_kind(self)
+    }
+}
+
+impl<T, E> MetricResult for Result<T, E>
+where
+    T: MetricResult,
+    E: MetricError,
+{
+    fn failed(&self) -> bool {
+        self.is_err()
+    }
+
+    fn rows_affected(&self) -> Option<u64> {
+        self.as_ref().ok().and_then(T::rows_affected)
+    }
+
+    fn rows_returned(&self) -> Option<u64> {
+        self.as_ref().ok().and_then(T::rows_returned)
+    }
+
+    fn error_kind(&self) -> Option<DriverErrKind> {
+        self.as_ref().err().and_then(E::error_kind)
+    }
+}
+
+/// Report a statement to the metric callback and to its span, if any of them is enabled
+pub(crate) fn report<R>(
+    callback: Option<&Callback>,
+    span: &tracing::Span,
+    statement: &Statement,
+    elapsed: Duration,
+    in_transaction: bool,
+    res: &R,
+) where
+    R: MetricResult,
+{
+    if callback.is_none() && span.is_disabled() {
+        return;
+    }
+    let info = Info::new(statement, elapsed, in_transaction, res);
+    record_span(span, &info);
+    if let Some(callback) = callback {
+        callback(&info);
+    }
+}
+
+/// Open a span for a statement, with the attributes of the OpenTelemetry semantic conventions
+/// for database clients
+#[cfg(feature = "tracing-spans")]
+pub(crate) fn statement_span(statement: &Statement, in_transaction: bool) -> tracing::Span {
+    use tracing::field::Empty;
+
+    let (operation, table) = parse_operation_and_table(&statement.sql);
+    let name = match table {
+        Some(table) => format!("{} {}", operation, table),
+        None => operation.to_owned(),
+    };
+    tracing::info_span!(
+        "sea_orm.statement",
+        otel.name = name.as_str(),
+        otel.kind = "client",
+        otel.status_code = Empty,
+        db.system = db_system(statement.db_backend),
+        db.operation = operation,
+        db.sql.table = table,
+        db.statement = statement.sql.as_str(),
+        db.rows_affected = Empty,
+        db.response.returned_rows = Empty,
+        db.transaction = in_transaction,
+        error.type = Empty,
+    )
+}
+
+/// Spans are only opened with the `tracing-spans` feature
+#[cfg(not(feature = "tracing-spans"))]
+pub(crate) fn statement_span(_: &Statement, _: bool) -> tracing::Span {
+    tracing::Span::none()
+}
+
+fn record_span(span: &tracing::Span, info: &Info<'_>) {
+    if span.is_disabled() {
+        return;
+    }
+    if let Some(rows) = info.rows_affected {
+        span.record("db.rows_affected", rows);
+    }
+    if let Some(rows) = info.rows_returned {
+        span.record("db.response.returned_rows", rows);
+    }
+    if info.failed {
+        span.record("otel.status_code", "ERROR");
+        let error_type = match info.error_kind {
+            Some(kind) => format!("{:?}", kind),
+            None => "Other".to_owned(),
+        };
+        span.record("error.type", error_type.as_str());
+    }
+}
+
+/// The name of the backend, as the `db.system` attribute
+#[allow(dead_code)]
+fn db_system(db_backend: DbBackend) -> &'static str {
+    match db_backend {
+        DbBackend::MySql => "mysql",
+        DbBackend::Postgres => "postgresql",
+        DbBackend::Sqlite => "sqlite",
+    }
+}
+
+/// Get the operation of a statement, i.e. its first keyword, and the table it targets:
+/// the first table after `FROM` for `SELECT` and `DELETE`, after `INTO` for `INSERT` and `REPLACE`,
+/// or after `UPDATE`. A schema qualified table is reported without the schema.
+pub(crate) fn parse_operation_and_table(sql: &str) -> (&str, Option<&str>) {
+    let sql = sql.trim_start();
+    let end = sql
+        .find(|c: char| !c.is_ascii_alphabetic())
+        .unwrap_or(sql.len());
+    let (operation, rest) = sql.split_at(end);
+    let keyword = if operation.eq_ignore_ascii_case("SELECT")
+        || operation.eq_ignore_ascii_case("DELETE")
+    {
+        "FROM"
+    } else if operation.eq_ignore_ascii_case("INSERT") || operation.eq_ignore_ascii_case("REPLACE")
+    {
+        "INTO"
+    } else if operation.eq_ignore_ascii_case("UPDATE") {
+        ""
+    } else {
+        return (operation, None);
+    };
+    let rest = if keyword.is_empty() {
+        Some(rest)
+    } else {
+        find_keyword(rest, keyword).map(|i| &rest[i + keyword.len()..])
+    };
+    (operation, rest.and_then(parse_table))
+}
+
+/// Find a keyword surrounded by whitespace, ignoring the case
+fn find_keyword(sql: &str, keyword: &str) -> Option<usize> {
+    let bytes = sql.as_bytes();
+    let len = keyword.len();
+    (1..bytes.len().saturating_sub(len)).find(|&i| {
+        bytes[i - 1].is_ascii_whitespace()
+            && bytes[i + len].is_ascii_whitespace()
+            && bytes[i..i + len].eq_ignore_ascii_case(keyword.as_bytes())
+    })
+}
+
+/// Parse a possibly quoted and schema qualified table name at the start of the SQL
+fn parse_table(sql: &str) -> Option<&str> {
+    let (mut table, mut rest) = parse_ident(sql.trim_start())?;
+    while let Some(after) = rest.strip_prefix('.') {
+        let (ident, after) = parse_ident(after)?;
+        table = ident;
+        rest = after;
+    }
+    Some(table)
+}
+
+fn parse_ident(sql: &str) -> Option<(&str, &str)> {
+    let mut chars = sql.chars();
+    match chars.next()? {
+        quote @ ('"' | '`') => {
+            let end = sql[1..].find(quote)? + 1;
+            Some((&sql[1..end], &sql[end + 1..]))
+        }
+        c if c.is_alphanumeric() || c == '_' => {
+            let end = sql
+                .find(|c: char| !(c.is_alphanumeric() || c == '_' || c == '$'))
+                .unwrap_or(sql.len());
+            Some(sql.split_at(end))
+        }
+        _ => None,
+    }
 }
 
 mod inner {
     #[allow(unused_macros)]
     macro_rules! metric {
-        ($metric_callback:expr, $stmt:expr, $code:block) => {{
+        ($metric_callback:expr, $stmt:expr, $in_transaction:expr, $code:block) => {{
             let _start = std::time::SystemTime::now();
-            let res = $code;
-            if let Some(callback) = $metric_callback.as_deref() {
-                let info = crate::metric::Info {
-                    elapsed: _start.elapsed().unwrap_or_default(),
-                    statement: $stmt,
-                    failed: res.is_err(),
-                };
-                callback(&info);
-            }
+            let _span = crate::metric::statement_span($stmt, $in_transaction);
+            let res = tracing::Instrument::instrument(async { $code }, _span.clone()).await;
+            crate::metric::report(
+                $metric_callback.as_ref(),
+                &_span,
+                $stmt,
+                _start.elapsed().unwrap_or_default(),
+                $in_transaction,
+                &res,
+            );
             res
         }};
     }
     pub(crate) use metric;
     #[allow(unused_macros)]
     macro_rules! metric_ok {
-        ($metric_callback:expr, $stmt:expr, $code:block) => {{
+        ($metric_callback:expr, $stmt:expr, $in_transaction:expr, $code:block) => {{
             let _start = std::time::SystemTime::now();
-            let res = $code;
-            if let Some(callback) = $metric_callback.as_deref() {
-                let info = crate::metric::Info {
-                    elapsed: _start.elapsed().unwrap_or_default(),
-                    statement: $stmt,
-                    failed: false,
-                };
-                callback(&info);
-            }
+            let _span = crate::metric::statement_span($stmt, $in_transaction);
+            let res = _span.in_scope(|| $code);
+            crate::metric::report(
+                $metric_callback.as_ref(),
+                &_span,
+                $stmt,
+                _start.elapsed().unwrap_or_default(),
+                $in_transaction,
+                &crate::metric::Streamed,
+            );
             res
         }};
     }
     pub(crate) use metric_ok;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn operation_and_table() {
+        assert_eq!(
+            parse_operation_and_table(r#"SELECT "cake"."id", "cake"."name" FROM "cake""#),
+            ("SELECT", Some("cake"))
+        );
+        assert_eq!(
+            parse_operation_and_table("select `id` from `public`.`fruit` where `id` = ?"),
+            ("select", Some("fruit"))
+        );
+        assert_eq!(
+            parse_operation_and_table(r#"INSERT INTO "cake" ("name") VALUES ($1)"#),
+            ("INSERT", Some("cake"))
+        );
+        assert_eq!(
+            parse_operation_and_table(r#"UPDATE "cake" SET "name" = $1"#),
+            ("UPDATE", Some("cake"))
+        );
+        assert_eq!(
+            parse_operation_and_table("DELETE FROM cake_filling WHERE cake_id = 1"),
+            ("DELETE", Some("cake_filling"))
+        );
+        assert_eq!(
+            parse_operation_and_table(
+                r#"SELECT COUNT(*) AS num_items FROM (SELECT "id" FROM "cake") AS "sub_query""#
+            ),
+            ("SELECT", None)
+        );
+        assert_eq!(parse_operation_and_table("SELECT 1"), ("SELECT", None));
+        assert_eq!(parse_operation_and_table("BEGIN"), ("BEGIN", None));
+    }
+}
@@ -1,6 +1,6 @@
 use crate::{
-    ColumnTrait, EntityTrait, IdenStatic, IntoSimpleExpr, Iterable, QueryTrait, Select, SelectTwo,
-    SelectTwoMany,
+    ColumnTrait, EntityTrait, IdenStatic, IntoSimpleExpr, Iterable, QueryTrait, Select,
+    SelectThree, SelectTwo, SelectTwoMany,
 };
 use core::marker::PhantomData;
 pub use sea_query::JoinType;
@@ -30,6 +30,7 @@ macro_rules! select_def {
 
 select_def!(SelectA, "A_");
 select_def!(SelectB, "B_");
+select_def!(SelectC, "C_");
 
 impl<E> Select<E>
 where
@@ -111,9 +112,17 @@ where
     }
 
     fn prepare_select(mut self) -> Self {
-        prepare_select_two::<F, Self>(&mut self);
+        prepare_select_col::<F, Self>(&mut self, SelectB);
         self
     }
+
+    /// Selects a third Entity and returns it together with the Entities from `Self`
+    pub(crate) fn select_also<G>(self, _: G) -> SelectThree<E, F, G>
+    where
+        G: EntityTrait,
+    {
+        SelectThree::new(self.into_query())
+    }
 }
 
 impl<E, F> SelectTwoMany<E, F>
@@ -122,20 +131,24 @@ where
     F: EntityTrait,
 {
     pub(crate) fn new(query: SelectStatement) -> Self {
+        Self::new_without_prepare(query)
+            .prepare_select()
+            .prepare_order_by()
+    }
+
+    pub(crate) fn new_without_prepare(query: SelectStatement) -> Self {
         Self {
             query,
             entity: PhantomData,
         }
-        .prepare_select()
-        .prepare_order_by()
     }
 
     fn prepare_select(mut self) -> Self {
-        prepare_select_two::<F, Self>(&mut self);
+        prepare_select_col::<F, Self>(&mut self, SelectB);
         self
     }
 
-    fn prepare_order_by(mut self) -> Self {
+    pub(crate) fn prepare_order_by(mut self) -> Self {
         for col in <E::PrimaryKey as Iterable>::iter() {
             self.query.order_by((E::default(), col), Order::Asc);
         }
@@ -143,7 +156,27 @@ where
     }
 }
 
-fn prepare_select_two<F, S>(selector: &mut S)
+impl<E, F, G> SelectThree<E, F, G>
+where
+    E: EntityTrait,
+    F: EntityTrait,
+    G: EntityTrait,
+{
+    pub(crate) fn new(query: SelectStatement) -> Self {
+        Self {
+            query,
+            entity: PhantomData,
+        }
+        .prepare_select()
+    }
+
+    fn prepare_select(mut self) -> Self {
+        prepare_select_col::<G, Self>(&mut self, SelectC);
+        self
+    }
+}
+
+fn prepare_select_col<F, S>(selector: &mut S, alias: impl IdenStatic)
 where
     F: EntityTrait,
     S: QueryTrait<QueryStatement = SelectStatement>,
@@ -152,7 +185,7 @@ where
     for col in <F::Column as Iterable>::iter() {
         let col_def = col.def();
         let col_type = col_def.get_column_type();
-        let alias = format!("{}{}", SelectB.as_str(), col.as_str());
+        let alias = format!("{}{}", alias.as_str(), col.as_str());
         let expr = Expr::expr(col.into_simple_expr());
         let expr = match col_type.get_enum_name() {
             Some(_) => expr.as_enum(text_type.clone()),
@@ -1,9 +1,12 @@
 use crate::{
-    ActiveModelTrait, ColumnTrait, EntityName, EntityTrait, IntoActiveModel, Iterable,
-    PrimaryKeyTrait, QueryTrait,
+    ActiveModelTrait, ColumnTrait, DbBackend, EntityName, EntityTrait, IntoActiveModel, Iterable,
+    PrimaryKeyToColumn, PrimaryKeyTrait, QueryTrait, Statement,
 };
 use core::marker::PhantomData;
-use sea_query::{Alias, Expr, InsertStatement, ValueTuple};
+use once_cell::sync::OnceCell;
+use sea_query::{
+    Alias, Condition, DynIden, Expr, Iden, InsertStatement, IntoIden, SimpleExpr, Value, ValueTuple,
+};
 
 /// Performs INSERT operations on a ActiveModel
 #[derive(Debug)]
@@ -11,9 +14,14 @@ pub struct Insert<A>
 where
     A: ActiveModelTrait,
 {
+    /// The statement without the rows, which are only added when the statement is built
     pub(crate) query: InsertStatement,
     pub(crate) columns: Vec<bool>,
     pub(crate) primary_key: Option<ValueTuple>,
+    /// The values of the set columns of each row
+    pub(crate) rows: Vec<Vec<Value>>,
+    statement: OnceCell<InsertStatement>,
+    pub(crate) on_conflict: Option<OnConflict>,
     pub(crate) model: PhantomData<A>,
 }
 
@@ -38,6 +46,9 @@ where
                 .to_owned(),
             columns: Vec::new(),
             primary_key: None,
+            rows: Vec::new(),
+            statement: OnceCell::new(),
+            on_conflict: None,
             model: PhantomData,
         }
     }
@@ -134,18 +145,12 @@ where
             }
             if av_has_val {
                 columns.push(col);
-                let val = Expr::val(av.into_value().unwrap());
-                let col_def = col.def();
-                let col_type = col_def.get_column_type();
-                let expr = match col_type.get_enum_name() {
-                    Some(enum_name) => val.as_enum(Alias::new(enum_name)),
-                    None => val.into(),
-                };
-                values.push(expr);
+                values.push(av.into_value().unwrap());
             }
         }
         self.query.columns(columns);
-        self.query.exprs_panic(values);
+        self.rows.push(values);
+        self.statement.take();
         self
     }
 
@@ -160,6 +165,203 @@ where
         }
         self
     }
+
+    /// On conflict
+    ///
+    /// on conflict do nothing
+    /// ```
+    /// use sea_orm::{entity::*, query::*, tests_cfg::cake, DbBackend};
+    ///
+    /// let orange = cake::ActiveModel {
+    ///     id: ActiveValue::set(2),
+    ///     name: ActiveValue::set("Orange".to_owned()),
+    /// };
+    /// assert_eq!(
+    ///     cake::Entity::insert(orange)
+    ///         .on_conflict(
+    ///             OnConflict::column(cake::Column::Name)
+    ///                 .do_nothing()
+    ///                 .to_owned()
+    ///         )
+    ///         .build(DbBackend::Postgres)
+    ///         .to_string(),
+    ///     r#"INSERT INTO "cake" ("id", "name") VALUES (2, 'Orange') ON CONFLICT ("name") DO NOTHING"#,
+    /// );
+    /// ```
+    ///
+    /// on conflict do update
+    /// ```
+    /// use sea_orm::{entity::*, query::*, tests_cfg::cake, DbBackend};
+    ///
+    /// let orange = cake::ActiveModel {
+    ///     id: ActiveValue::set(2),
+    ///     name: ActiveValue::set("Orange".to_owned()),
+    /// };
+    /// assert_eq!(
+    ///     cake::Entity::insert(orange)
+    ///         .on_conflict(
+    ///             OnConflict::column(cake::Column::Id)
+    ///                 .update_column(cake::Column::Name)
+    ///                 .to_owned()
+    ///         )
+    ///         .build(DbBackend::Postgres)
+    ///         .to_string(),
+    ///     r#"INSERT INTO "cake" ("id", "name") VALUES (2, 'Orange') ON CONFLICT ("id") DO UPDATE SET "name" = "excluded"."name""#,
+    /// );
+    /// ```
+    ///
+    /// MySQL has no `DO NOTHING`, such a statement is executed as `INSERT IGNORE` instead,
+    /// which skips the rows conflicting on any unique key and also turns some other errors,
+    /// such as a value out of range, into warnings
+    /// ```
+    /// use sea_orm::{entity::*, query::*, tests_cfg::cake, DbBackend};
+    ///
+    /// let orange = cake::ActiveModel {
+    ///     id: ActiveValue::set(2),
+    ///     name: ActiveValue::set("Orange".to_owned()),
+    /// };
+    /// assert_eq!(
+    ///     cake::Entity::insert(orange)
+    ///         .on_conflict(
+    ///             OnConflict::column(cake::Column::Name)
+    ///                 .do_nothing()
+    ///                 .to_owned()
+    ///         )
+    ///         .build(DbBackend::MySql)
+    ///         .to_string(),
+    ///     r#"INSERT IGNORE INTO `cake` (`id`, `name`) VALUES (2, 'Orange')"#,
+    /// );
+    /// ```
+    pub fn on_conflict(mut self, on_conflict: OnConflict) -> Self {
+        self.on_conflict = Some(on_conflict);
+        self.statement.take();
+        self
+    }
+
+    /// Whether a conflicting row is skipped, so that inserting no row is not an error of the database
+    pub(crate) fn on_conflict_do_nothing(&self) -> bool {
+        matches!(&self.on_conflict, Some(on_conflict) if on_conflict.do_nothing)
+    }
+
+    /// The condition selecting the row inserted or updated by an upsert of one row, by the values
+    /// of its conflict target, or of its primary key if it has no target
+    pub(crate) fn upserted_row(&self) -> Option<Condition> {
+        let on_conflict = self.on_conflict.as_ref()?;
+        let cols: Vec<String> = if on_conflict.target.is_empty() {
+            <A::Entity as EntityTrait>::PrimaryKey::iter()
+                .map(|key| key.into_column().to_string())
+                .collect()
+        } else {
+            on_conflict
+                .target
+                .iter()
+                .map(|col| col.to_string())
+                .collect()
+        };
+        let row = self.column_values(&cols)?.pop()?;
+        let mut condition = Condition::all();
+        for (name, val) in cols.iter().zip(row) {
+            let col =
+                <A::Entity as EntityTrait>::Column::iter().find(|col| &col.to_string() == name)?;
+            condition = condition.add(col.eq(val));
+        }
+        Some(condition)
+    }
+
+    /// Whether the statement is executed as `INSERT IGNORE` on the given database backend,
+    /// leaving out its `ON CONFLICT DO NOTHING`
+    fn insert_ignore(&self, db_backend: DbBackend) -> bool {
+        db_backend == DbBackend::MySql && self.on_conflict_do_nothing()
+    }
+
+    /// Take ownership of the statement to execute on the given database backend,
+    /// to be built by [`build_insert`]
+    pub(crate) fn into_query_for(mut self, db_backend: DbBackend) -> InsertStatement {
+        if self.insert_ignore(db_backend) {
+            self.on_conflict = None;
+        }
+        self.into_query()
+    }
+
+    /// The statement inserting every row with the given `ON CONFLICT` clause
+    fn statement(&self, on_conflict: Option<&OnConflict>) -> InsertStatement {
+        let mut query = self.query.clone();
+        for row in self.rows.iter() {
+            query.exprs_panic(Self::row_exprs(&self.columns, row.clone()));
+        }
+        if let Some(on_conflict) = on_conflict {
+            query.on_conflict(on_conflict.on_conflict.clone());
+        }
+        query
+    }
+
+    /// The values of the given columns in each row, if every one of them is set
+    pub(crate) fn column_values(&self, cols: &[String]) -> Option<Vec<Vec<Value>>> {
+        let set_columns: Vec<String> = <A::Entity as EntityTrait>::Column::iter()
+            .zip(self.columns.iter())
+            .filter(|(_, &has_val)| has_val)
+            .map(|(col, _)| col.to_string())
+            .collect();
+        let positions = cols
+            .iter()
+            .map(|col| set_columns.iter().position(|set_col| set_col == col))
+            .collect::<Option<Vec<_>>>()?;
+        Some(
+            self.rows
+                .iter()
+                .map(|row| positions.iter().map(|&pos| row[pos].clone()).collect())
+                .collect(),
+        )
+    }
+
+    /// The expressions inserting a row, casting the values of enum columns
+    fn row_exprs(columns: &[bool], row: Vec<Value>) -> Vec<SimpleExpr> {
+        <A::Entity as EntityTrait>::Column::iter()
+            .zip(columns.iter())
+            .filter(|(_, &has_val)| has_val)
+            .zip(row)
+            .map(|((col, _), val)| {
+                let val = Expr::val(val);
+                let col_def = col.def();
+                let col_type = col_def.get_column_type();
+                match col_type.get_enum_name() {
+                    Some(enum_name) => val.as_enum(Alias::new(enum_name)),
+                    None => val.into(),
+                }
+            })
+            .collect()
+    }
+
+    /// Split the rows into statements binding at most `max_params` values each
+    pub(crate) fn into_chunks(self, db_backend: DbBackend, max_params: usize) -> Vec<Statement> {
+        let num_columns = self.columns.iter().filter(|&&has_val| has_val).count();
+        if self.rows.is_empty() {
+            return Vec::new();
+        }
+        let on_conflict_do_nothing = self.on_conflict_do_nothing();
+        if num_columns == 0 {
+            let query = self.into_query_for(db_backend);
+            return vec![build_insert(db_backend, &query, on_conflict_do_nothing)];
+        }
+        let rows_per_chunk = std::cmp::max(max_params / num_columns, 1);
+        let mut template = self.query.clone();
+        match &self.on_conflict {
+            Some(on_conflict) if !self.insert_ignore(db_backend) => {
+                template.on_conflict(on_conflict.on_conflict.clone());
+            }
+            _ => {}
+        }
+        let mut chunks = Vec::new();
+        let mut rows = self.rows.into_iter().peekable();
+        while rows.peek().is_some() {
+            let mut query = template.clone();
+            for row in rows.by_ref().take(rows_per_chunk) {
+                query.exprs_panic(Self::row_exprs(&self.columns, row));
+            }
+            chunks.push(build_insert(db_backend, &query, on_conflict_do_nothing));
+        }
+        chunks
+    }
 }
 
 impl<A> QueryTrait for Insert<A>
@@ -169,22 +371,161 @@ where
     type QueryStatement = InsertStatement;
 
     fn query(&mut self) -> &mut InsertStatement {
+        self.statement.take();
         &mut self.query
     }
 
     fn as_query(&self) -> &InsertStatement {
-        &self.query
+        self.statement
+            .get_or_init(|| self.statement(self.on_conflict.as_ref()))
     }
 
     fn into_query(self) -> InsertStatement {
-        self.query
+        let mut query = self.query;
+        for row in self.rows {
+            query.exprs_panic(Self::row_exprs(&self.columns, row));
+        }
+        if let Some(on_conflict) = self.on_conflict {
+            query.on_conflict(on_conflict.on_conflict);
+        }
+        query
+    }
+
+    fn build(&self, db_backend: DbBackend) -> Statement {
+        if self.insert_ignore(db_backend) {
+            build_insert(db_backend, &self.statement(None), true)
+        } else {
+            db_backend.build(self.as_query())
+        }
+    }
+}
+
+/// Build an insert statement, skipping the conflicting rows with `INSERT IGNORE` on MySQL
+/// if `on_conflict_do_nothing`, as the statement has no `ON CONFLICT` clause there
+pub(crate) fn build_insert(
+    db_backend: DbBackend,
+    query: &InsertStatement,
+    on_conflict_do_nothing: bool,
+) -> Statement {
+    let mut statement = db_backend.build(query);
+    if db_backend == DbBackend::MySql && on_conflict_do_nothing {
+        statement.sql = statement.sql.replacen("INSERT ", "INSERT IGNORE ", 1);
+    }
+    statement
+}
+
+/// An `ON CONFLICT` clause of an insert statement, recording its target and whether
+/// it does nothing, so that the statement can be adapted to each database backend
+#[derive(Debug, Clone, Default)]
+pub struct OnConflict {
+    on_conflict: sea_query::OnConflict,
+    target: Vec<DynIden>,
+    do_nothing: bool,
+}
+
+impl OnConflict {
+    /// Create a ON CONFLICT expression without target column
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Set ON CONFLICT target column
+    pub fn column<C>(column: C) -> Self
+    where
+        C: IntoIden,
+    {
+        Self::columns([column])
+    }
+
+    /// Set ON CONFLICT target columns
+    pub fn columns<I, C>(columns: I) -> Self
+    where
+        C: IntoIden,
+        I: IntoIterator<Item = C>,
+    {
+        let target: Vec<DynIden> = columns.into_iter().map(IntoIden::into_iden).collect();
+        Self {
+            on_conflict: sea_query::OnConflict::columns(target.clone()),
+            target,
+            do_nothing: false,
+        }
+    }
+
+    /// Set ON CONFLICT do nothing
+    pub fn do_nothing(&mut self) -> &mut Self {
+        self.on_conflict.do_nothing();
+        self.do_nothing = true;
+        self
+    }
+
+    /// Set ON CONFLICT update column
+    pub fn update_column<C>(&mut self, column: C) -> &mut Self
+    where
+        C: IntoIden,
+    {
+        self.on_conflict.update_column(column);
+        self.do_nothing = false;
+        self
+    }
+
+    /// Set ON CONFLICT update columns
+    pub fn update_columns<C, I>(&mut self, columns: I) -> &mut Self
+    where
+        C: IntoIden,
+        I: IntoIterator<Item = C>,
+    {
+        self.on_conflict.update_columns(columns);
+        self.do_nothing = false;
+        self
+    }
+
+    /// Set ON CONFLICT update value
+    pub fn update_value<C>(&mut self, column_value: (C, Value)) -> &mut Self
+    where
+        C: IntoIden,
+    {
+        self.on_conflict.update_value(column_value);
+        self.do_nothing = false;
+        self
+    }
+
+    /// Set ON CONFLICT update values
+    pub fn update_values<C, I>(&mut self, column_values: I) -> &mut Self
+    where
+        C: IntoIden,
+        I: IntoIterator<Item = (C, Value)>,
+    {
+        self.on_conflict.update_values(column_values);
+        self.do_nothing = false;
+        self
+    }
+
+    /// Set ON CONFLICT update expression
+    pub fn update_expr<C>(&mut self, column_expr: (C, SimpleExpr)) -> &mut Self
+    where
+        C: IntoIden,
+    {
+        self.on_conflict.update_expr(column_expr);
+        self.do_nothing = false;
+        self
+    }
+
+    /// Set ON CONFLICT update expressions
+    pub fn update_exprs<C, I>(&mut self, column_exprs: I) -> &mut Self
+    where
+        C: IntoIden,
+        I: IntoIterator<Item = (C, SimpleExpr)>,
+    {
+        self.on_conflict.update_exprs(column_exprs);
+        self.do_nothing = false;
+        self
     }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::tests_cfg::cake;
-    use crate::{ActiveValue, DbBackend, Insert, QueryTrait};
+    use crate::{ActiveValue, DbBackend, Insert, OnConflict, QueryTrait};
 
     #[test]
     fn insert_1() {
@@ -267,4 +608,98 @@ mod tests {
             r#"INSERT INTO "cake" ("id", "name") VALUES (NULL, 'Apple'), (2, 'Orange')"#,
         );
     }
+
+    #[test]
+    fn insert_modified_after_build() {
+        let mut insert = Insert::<cake::ActiveModel>::one(cake::Model {
+            id: 1,
+            name: "Apple Pie".to_owned(),
+        });
+        assert_eq!(
+            insert.build(DbBackend::Postgres).to_string(),
+            r#"INSERT INTO "cake" ("id", "name") VALUES (1, 'Apple Pie')"#,
+        );
+
+        insert.query().returning_col(cake::Column::Id);
+        assert_eq!(
+            insert
+                .add(cake::Model {
+                    id: 2,
+                    name: "Orange Scone".to_owned(),
+                })
+                .build(DbBackend::Postgres)
+                .to_string(),
+            r#"INSERT INTO "cake" ("id", "name") VALUES (1, 'Apple Pie'), (2, 'Orange Scone') RETURNING "id""#,
+        );
+    }
+
+    #[test]
+    fn insert_chunks() {
+        let chunks = Insert::<cake::ActiveModel>::new()
+            .add_many((1..=5).map(|id| cake::Model {
+                id,
+                name: format!("Cake {}", id),
+            }))
+            .on_conflict(OnConflict::column(cake::Column::Id).do_nothing().to_owned())
+            .into_chunks(DbBackend::Postgres, 5);
+
+        assert_eq!(
+            chunks
+                .iter()
+                .map(|statement| statement.to_string())
+                .collect::<Vec<_>>(),
+            [
+                r#"INSERT INTO "cake" ("id", "name") VALUES (1, 'Cake 1'), (2, 'Cake 2') ON CONFLICT ("id") DO NOTHING"#,
+                r#"INSERT INTO "cake" ("id", "name") VALUES (3, 'Cake 3'), (4, 'Cake 4') ON CONFLICT ("id") DO NOTHING"#,
+                r#"INSERT INTO "cake" ("id", "name") VALUES (5, 'Cake 5') ON CONFLICT ("id") DO NOTHING"#,
+            ]
+        );
+    }
+
+    #[test]
+    fn insert_chunks_mysql() {
+        let chunks = Insert::<cake::ActiveModel>::new()
+            .add_many((1..=3).map(|id| cake::Model {
+                id,
+                name: format!("Cake {}", id),
+            }))
+            .on_conflict(OnConflict::column(cake::Column::Id).do_nothing().to_owned())
+            .into_chunks(DbBackend::MySql, 4);
+
+        assert_eq!(
+            chunks
+                .iter()
+                .map(|statement| statement.to_string())
+                .collect::<Vec<_>>(),
+            [
+                "INSERT IGNORE INTO `cake` (`id`, `name`) VALUES (1, 'Cake 1'), (2, 'Cake 2')",
+                "INSERT IGNORE INTO `cake` (`id`, `name`) VALUES (3, 'Cake 3')",
+            ]
+        );
+    }
+
+    #[test]
+    fn insert_chunks_returning() {
+        let mut insert =
+            Insert::<cake::ActiveModel>::new().add_many((1..=3).map(|id| cake::Model {
+                id,
+                name: format!("Cake {}", id),
+            }));
+        insert.query().returning_col(cake::Column::Id);
+
+        assert_eq!(
+            insert
+                .into_chunks(DbBackend::Postgres, 4)
+                .iter()
+                .map(|statement| statement.to_string())
+                .collect::<Vec<_>>(),
+            [
+                r#"INSERT INTO "cake" ("id", "name") VALUES (1, 'Cake 1'), (2, 'Cake 2') RETURNING "id""#,
+                r#"INSERT INTO "cake" ("id", "name") VALUES (3, 'Cake 3') RETURNING "id""#,
+            ]
+        );
+        assert!(Insert::<cake::ActiveModel>::new()
+            .into_chunks(DbBackend::Postgres, 4)
+            .is_empty());
+    }
 }
@@ -1,9 +1,10 @@
 use crate::{
     join_tbl_on_condition, unpack_table_ref, ColumnTrait, EntityTrait, IdenStatic, Iterable,
-    Linked, QuerySelect, Related, Select, SelectA, SelectB, SelectTwo, SelectTwoMany,
+    Linked, QuerySelect, QueryTrait, Related, Select, SelectA, SelectB, SelectThree, SelectTwo,
+    SelectTwoMany,
 };
 pub use sea_query::JoinType;
-use sea_query::{Alias, DynIden, Expr, IntoIden, SeaRc, SelectExpr};
+use sea_query::{Alias, DynIden, Expr, IntoIden, SeaRc, SelectExpr, SelectStatement};
 
 impl<E> Select<E>
 where
@@ -68,7 +69,99 @@ where
         L: Linked<FromEntity = E, ToEntity = T>,
         T: EntityTrait,
     {
-        let mut slf = self;
+        let to_tbl = linked_to_tbl(&l);
+        let slf = self.join_linked(l).apply_alias(SelectA.as_str());
+        let mut select_two = SelectTwo::new_without_prepare(slf.query);
+        prepare_select_linked::<T, _>(&mut select_two, to_tbl);
+        select_two
+    }
+
+    /// Left Join with a Linked Entity and select the linked Entity as a `Vec`
+    ///
+    /// ```
+    /// # use sea_orm::{error::*, tests_cfg::*, *};
+    /// #
+    /// # #[smol_potat::main]
+    /// # #[cfg(feature = "mock")]
+    /// # pub async fn main() -> Result<(), DbErr> {
+    /// #
+    /// # let db = MockDatabase::new(DbBackend::Postgres)
+    /// #     .append_query_results(vec![vec![
+    /// #         maplit::btreemap! {
+    /// #             "A_id" => Into::<Value>::into(1),
+    /// #             "A_name" => Into::<Value>::into("Cheese Cake"),
+    /// #             "B_id" => Into::<Value>::into(1),
+    /// #             "B_name" => Into::<Value>::into("Dairy Farm"),
+    /// #         },
+    /// #         maplit::btreemap! {
+    /// #             "A_id" => Into::<Value>::into(1),
+    /// #             "A_name" => Into::<Value>::into("Cheese Cake"),
+    /// #             "B_id" => Into::<Value>::into(2),
+    /// #             "B_name" => Into::<Value>::into("Sugar Mill"),
+    /// #         },
+    /// #         maplit::btreemap! {
+    /// #             "A_id" => Into::<Value>::into(2),
+    /// #             "A_name" => Into::<Value>::into("Apple Pie"),
+    /// #             "B_id" => Into::<Value>::into(2),
+    /// #             "B_name" => Into::<Value>::into("Sugar Mill"),
+    /// #         },
+    /// #     ]])
+    /// #     .into_connection();
+    /// #
+    /// use sea_orm::{entity::*, query::*, tests_cfg::{cake, entity_linked, vendor}};
+    ///
+    /// let res: Vec<(cake::Model, Vec<vendor::Model>)> = cake::Entity::find()
+    ///     .find_with_linked(entity_linked::CakeToFillingVendor)
+    ///     .all(&db)
+    ///     .await?;
+    ///
+    /// let dairy_farm = vendor::Model {
+    ///     id: 1,
+    ///     name: "Dairy Farm".to_owned(),
+    /// };
+    /// let sugar_mill = vendor::Model {
+    ///     id: 2,
+    ///     name: "Sugar Mill".to_owned(),
+    /// };
+    /// assert_eq!(
+    ///     res,
+    ///     vec![
+    ///         (
+    ///             cake::Model {
+    ///                 id: 1,
+    ///                 name: "Cheese Cake".to_owned(),
+    ///             },
+    ///             vec![dairy_farm, sugar_mill.clone()],
+    ///         ),
+    ///         (
+    ///             cake::Model {
+    ///                 id: 2,
+    ///                 name: "Apple Pie".to_owned(),
+    ///             },
+    ///             vec![sugar_mill],
+    ///         ),
+    ///     ]
+    /// );
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn find_with_linked<L, T>(self, l: L) -> SelectTwoMany<E, T>
+    where
+        L: Linked<FromEntity = E, ToEntity = T>,
+        T: EntityTrait,
+    {
+        let to_tbl = linked_to_tbl(&l);
+        let slf = self.join_linked(l).apply_alias(SelectA.as_str());
+        let mut select_two_many = SelectTwoMany::new_without_prepare(slf.query);
+        prepare_select_linked::<T, _>(&mut select_two_many, to_tbl);
+        select_two_many.prepare_order_by()
+    }
+
+    fn join_linked<L>(mut self, l: L) -> Self
+    where
+        L: Linked<FromEntity = E>,
+    {
         for (i, rel) in l.link().into_iter().enumerate() {
             let to_tbl = Alias::new(&format!("r{}", i)).into_iden();
             let from_tbl = if i > 0 {
@@ -77,41 +170,160 @@ where
                 unpack_table_ref(&rel.from_tbl)
             };
 
-            slf.query().join_as(
+            self.query.join_as(
                 JoinType::LeftJoin,
                 rel.to_tbl,
                 SeaRc::clone(&to_tbl),
                 join_tbl_on_condition(from_tbl, to_tbl, rel.from_col, rel.to_col),
             );
         }
-        slf = slf.apply_alias(SelectA.as_str());
-        let text_type = SeaRc::new(Alias::new("text")) as DynIden;
-        let mut select_two = SelectTwo::new_without_prepare(slf.query);
-        for col in <T::Column as Iterable>::iter() {
-            let col_def = col.def();
-            let col_type = col_def.get_column_type();
-            let alias = format!("{}{}", SelectB.as_str(), col.as_str());
-            let expr = Expr::tbl(
-                Alias::new(&format!("r{}", l.link().len() - 1)).into_iden(),
-                col.into_iden(),
-            );
-            let expr = match col_type.get_enum_name() {
-                Some(_) => expr.as_enum(text_type.clone()),
-                None => expr.into(),
-            };
-            select_two.query().expr(SelectExpr {
-                expr,
-                alias: Some(SeaRc::new(Alias::new(&alias))),
-                window: None,
-            });
-        }
-        select_two
+        self
+    }
+}
+
+fn linked_to_tbl<L>(l: &L) -> DynIden
+where
+    L: Linked,
+{
+    Alias::new(&format!("r{}", l.link().len() - 1)).into_iden()
+}
+
+fn prepare_select_linked<T, S>(selector: &mut S, to_tbl: DynIden)
+where
+    T: EntityTrait,
+    S: QueryTrait<QueryStatement = SelectStatement>,
+{
+    let text_type = SeaRc::new(Alias::new("text")) as DynIden;
+    for col in <T::Column as Iterable>::iter() {
+        let col_def = col.def();
+        let col_type = col_def.get_column_type();
+        let alias = format!("{}{}", SelectB.as_str(), col.as_str());
+        let expr = Expr::tbl(SeaRc::clone(&to_tbl), col.into_iden());
+        let expr = match col_type.get_enum_name() {
+            Some(_) => expr.as_enum(text_type.clone()),
+            None => expr.into(),
+        };
+        selector.query().expr(SelectExpr {
+            expr,
+            alias: Some(SeaRc::new(Alias::new(&alias))),
+            window: None,
+        });
+    }
+}
+
+impl<E, F> SelectTwo<E, F>
+where
+    E: EntityTrait,
+    F: EntityTrait,
+{
+    /// Left Join with an Entity Related to the second Entity and select all three Entity.
+    ///
+    /// ```
+    /// # use sea_orm::{error::*, tests_cfg::*, *};
+    /// #
+    /// # #[smol_potat::main]
+    /// # #[cfg(feature = "mock")]
+    /// # pub async fn main() -> Result<(), DbErr> {
+    /// #
+    /// # let db = MockDatabase::new(DbBackend::Postgres)
+    /// #     .append_query_results(vec![vec![
+    /// #         maplit::btreemap! {
+    /// #             "A_id" => Into::<Value>::into(1),
+    /// #             "A_name" => Into::<Value>::into("Cheese Cake"),
+    /// #             "B_id" => Into::<Value>::into(2),
+    /// #             "B_name" => Into::<Value>::into("Cheese"),
+    /// #             "B_vendor_id" => Into::<Value>::into(3),
+    /// #             "C_id" => Into::<Value>::into(3),
+    /// #             "C_name" => Into::<Value>::into("Dairy Farm"),
+    /// #         },
+    /// #     ]])
+    /// #     .into_connection();
+    /// #
+    /// use sea_orm::{entity::*, query::*, tests_cfg::{cake, filling, vendor}};
+    ///
+    /// let res: Vec<(cake::Model, Option<filling::Model>, Option<vendor::Model>)> =
+    ///     cake::Entity::find()
+    ///         .find_also_related(filling::Entity)
+    ///         .find_also_related(vendor::Entity)
+    ///         .all(&db)
+    ///         .await?;
+    ///
+    /// assert_eq!(
+    ///     res,
+    ///     vec![(
+    ///         cake::Model {
+    ///             id: 1,
+    ///             name: "Cheese Cake".to_owned(),
+    ///         },
+    ///         Some(filling::Model {
+    ///             id: 2,
+    ///             name: "Cheese".to_owned(),
+    ///             vendor_id: Some(3),
+    ///             ignored_attr: 0,
+    ///         }),
+    ///         Some(vendor::Model {
+    ///             id: 3,
+    ///             name: "Dairy Farm".to_owned(),
+    ///         }),
+    ///     )]
+    /// );
+    ///
+    /// assert_eq!(
+    ///     db.into_transaction_log(),
+    ///     vec![Transaction::from_sql_and_values(
+    ///         DbBackend::Postgres,
+    ///         [
+    ///             r#"SELECT "cake"."id" AS "A_id", "cake"."name" AS "A_name","#,
+    ///             r#""filling"."id" AS "B_id", "filling"."name" AS "B_name", "filling"."vendor_id" AS "B_vendor_id","#,
+    ///             r#""vendor"."id" AS "C_id", "vendor"."name" AS "C_name""#,
+    ///             r#"FROM "cake""#,
+    ///             r#"LEFT JOIN "cake_filling" ON "cake"."id" = "cake_filling"."cake_id""#,
+    ///             r#"LEFT JOIN "filling" ON "cake_filling"."filling_id" = "filling"."id""#,
+    ///             r#"LEFT JOIN "vendor" ON "filling"."vendor_id" = "vendor"."id""#,
+    ///         ]
+    ///         .join(" ")
+    ///         .as_str(),
+    ///         vec![]
+    ///     ),]
+    /// );
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn find_also_related<R>(self, r: R) -> SelectThree<E, F, R>
+    where
+        R: EntityTrait,
+        F: Related<R>,
+    {
+        self.join_join(JoinType::LeftJoin, F::to(), F::via())
+            .select_also(r)
+    }
+
+    /// Left Join with an Entity Related to `T`, which must be one of the two Entities
+    /// already selected, and select all three Entity.
+    pub fn find_also<T, R, S>(self, _: T, r: R) -> SelectThree<E, F, R>
+    where
+        R: EntityTrait,
+        T: EntityTrait + Related<R> + SelectTwoEntity<E, F, S>,
+    {
+        self.join_join(JoinType::LeftJoin, T::to(), T::via())
+            .select_also(r)
     }
 }
 
+/// An Entity selected by a [SelectTwo], i.e. either `E` or `F`.
+/// `S` is [SelectA] or [SelectB] and only tells the two cases apart, it is always inferred.
+pub trait SelectTwoEntity<E, F, S> {}
+
+impl<E, F> SelectTwoEntity<E, F, SelectA> for E {}
+
+impl<E, F> SelectTwoEntity<E, F, SelectB> for F {}
+
 #[cfg(test)]
 mod tests {
-    use crate::tests_cfg::{cake, cake_filling, cake_filling_price, entity_linked, filling, fruit};
+    use crate::tests_cfg::{
+        cake, cake_filling, cake_filling_price, entity_linked, filling, fruit, vendor,
+    };
     use crate::{ColumnTrait, DbBackend, EntityTrait, ModelTrait, QueryFilter, QueryTrait};
     use pretty_assertions::assert_eq;
 
@@ -355,4 +567,66 @@ mod tests {
             .join(" ")
         );
     }
+
+    #[test]
+    fn join_14() {
+        assert_eq!(
+            cake::Entity::find()
+                .find_also_related(filling::Entity)
+                .find_also_related(vendor::Entity)
+                .build(DbBackend::MySql)
+                .to_string(),
+            [
+                r#"SELECT `cake`.`id` AS `A_id`, `cake`.`name` AS `A_name`,"#,
+                r#"`filling`.`id` AS `B_id`, `filling`.`name` AS `B_name`, `filling`.`vendor_id` AS `B_vendor_id`,"#,
+                r#"`vendor`.`id` AS `C_id`, `vendor`.`name` AS `C_name`"#,
+                r#"FROM `cake`"#,
+                r#"LEFT JOIN `cake_filling` ON `cake`.`id` = `cake_filling`.`cake_id`"#,
+                r#"LEFT JOIN `filling` ON `cake_filling`.`filling_id` = `filling`.`id`"#,
+                r#"LEFT JOIN `vendor` ON `filling`.`vendor_id` = `vendor`.`id`"#,
+            ]
+            .join(" ")
+        );
+    }
+
+    #[test]
+    fn join_15() {
+        assert_eq!(
+            cake::Entity::find()
+                .find_also_related(fruit::Entity)
+                .find_also(cake::Entity, filling::Entity)
+                .build(DbBackend::MySql)
+                .to_string(),
+            [
+                r#"SELECT `cake`.`id` AS `A_id`, `cake`.`name` AS `A_name`,"#,
+                r#"`fruit`.`id` AS `B_id`, `fruit`.`name` AS `B_name`, `fruit`.`cake_id` AS `B_cake_id`,"#,
+                r#"`filling`.`id` AS `C_id`, `filling`.`name` AS `C_name`, `filling`.`vendor_id` AS `C_vendor_id`"#,
+                r#"FROM `cake`"#,
+                r#"LEFT JOIN `fruit` ON `cake`.`id` = `fruit`.`cake_id`"#,
+                r#"LEFT JOIN `cake_filling` ON `cake`.`id` = `cake_filling`.`cake_id`"#,
+                r#"LEFT JOIN `filling` ON `cake_filling`.`filling_id` = `filling`.`id`"#,
+            ]
+            .join(" ")
+        );
+    }
+
+    #[test]
+    fn join_16() {
+        assert_eq!(
+            cake::Entity::find()
+                .find_with_linked(entity_linked::CakeToFillingVendor)
+                .build(DbBackend::MySql)
+                .to_string(),
+            [
+                r#"SELECT `cake`.`id` AS `A_id`, `cake`.`name` AS `A_name`,"#,
+                r#"`r2`.`id` AS `B_id`, `r2`.`name` AS `B_name`"#,
+                r#"FROM `cake`"#,
+                r#"LEFT JOIN `cake_filling` AS `r0` ON `cake`.`id` = `r0`.`cake_id`"#,
+                r#"LEFT JOIN `filling` AS `r1` ON `r0`.`filling_id` = `r1`.`id`"#,
+                r#"LEFT JOIN `vendor` AS `r2` ON `r1`.`vendor_id` = `r2`.`id`"#,
+                r#"ORDER BY `cake`.`id` ASC"#,
+            ]
+            .join(" ")
+        );
+    }
 }
@@ -0,0 +1,732 @@
+use crate::{
+    error::*, unpack_table_ref, value_tuple_to_vec, ConnectionTrait, DbBackend, EntityTrait,
+    FromQueryResult, IdenStatic, Identity, Iterable, ModelTrait, PrimaryKeyToColumn,
+    PrimaryKeyTrait, QueryFilter, QuerySelect, QueryTrait, Related, RelationDef, RelationType,
+    Select, TryGetableMany,
+};
+use async_trait::async_trait;
+use sea_query::{Condition, DynIden, Expr, IntoValueTuple, JoinType, SeaRc, Value};
+use std::{
+    collections::{HashMap, HashSet},
+    hash::{Hash, Hasher},
+};
+
+/// Prefix of the junction key columns selected when loading through a `via` relation
+const VIA_KEY_PREFIX: &str = "loader_key_";
+
+/// Either an Entity or a [Select] of it, to be used as the base query of a loader
+pub trait EntityOrSelect<E>: Send
+where
+    E: EntityTrait,
+{
+    /// Convert into a [Select]
+    fn select(self) -> Select<E>;
+}
+
+impl<E> EntityOrSelect<E> for E
+where
+    E: EntityTrait,
+{
+    fn select(self) -> Select<E> {
+        E::find()
+    }
+}
+
+impl<E> EntityOrSelect<E> for Select<E>
+where
+    E: EntityTrait,
+{
+    fn select(self) -> Select<E> {
+        self
+    }
+}
+
+/// Load related Models of a list of Models in batch, one query per relation
+#[async_trait]
+pub trait LoaderTrait {
+    /// The Model to load related Models for
+    type Model: ModelTrait;
+
+    /// Load the related Model of each Model of a `belongs_to` or `has_one` relation,
+    /// aligned to the order of `self`
+    async fn load_one<R, S, C>(&self, stmt: S, db: &C) -> Result<Vec<Option<R::Model>>, DbErr>
+    where
+        C: ConnectionTrait,
+        R: EntityTrait,
+        R::Model: Send + Sync,
+        S: EntityOrSelect<R>,
+        <Self::Model as ModelTrait>::Entity: Related<R>;
+
+    /// Load the related Models of each Model of a `has_many` or many-to-many relation,
+    /// aligned to the order of `self`
+    async fn load_many<R, S, C>(&self, stmt: S, db: &C) -> Result<Vec<Vec<R::Model>>, DbErr>
+    where
+        C: ConnectionTrait,
+        R: EntityTrait,
+        R::Model: Send + Sync,
+        S: EntityOrSelect<R>,
+        <Self::Model as ModelTrait>::Entity: Related<R>;
+}
+
+#[async_trait]
+impl<M> LoaderTrait for [M]
+where
+    M: ModelTrait + Sync,
+{
+    type Model = M;
+
+    /// ```
+    /// # use sea_orm::{error::*, tests_cfg::*, *};
+    /// #
+    /// # #[smol_potat::main]
+    /// # #[cfg(feature = "mock")]
+    /// # pub async fn main() -> Result<(), DbErr> {
+    /// #
+    /// # let db = MockDatabase::new(DbBackend::Postgres)
+    /// #     .append_query_results(vec![vec![cake::Model {
+    /// #         id: 1,
+    /// #         name: "New York Cheese".to_owned(),
+    /// #     }]])
+    /// #     .into_connection();
+    /// #
+    /// use sea_orm::{entity::*, query::*, tests_cfg::{cake, fruit}};
+    ///
+    /// let fruits = vec![
+    ///     fruit::Model {
+    ///         id: 1,
+    ///         name: "Apple".to_owned(),
+    ///         cake_id: Some(1),
+    ///     },
+    ///     fruit::Model {
+    ///         id: 2,
+    ///         name: "Orange".to_owned(),
+    ///         cake_id: None,
+    ///     },
+    /// ];
+    ///
+    /// let cakes: Vec<Option<cake::Model>> = fruits.load_one(cake::Entity, &db).await?;
+    ///
+    /// assert_eq!(
+    ///     cakes,
+    ///     vec![
+    ///         Some(cake::Model {
+    ///             id: 1,
+    ///             name: "New York Cheese".to_owned(),
+    ///         }),
+    ///         None,
+    ///     ]
+    /// );
+    ///
+    /// assert_eq!(
+    ///     db.into_transaction_log(),
+    ///     vec![Transaction::from_sql_and_values(
+    ///         DbBackend::Postgres,
+    ///         r#"SELECT "cake"."id", "cake"."name" FROM "cake" WHERE "cake"."id" IN ($1)"#,
+    ///         vec![1i32.into()]
+    ///     )]
+    /// );
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    async fn load_one<R, S, C>(&self, stmt: S, db: &C) -> Result<Vec<Option<R::Model>>, DbErr>
+    where
+        C: ConnectionTrait,
+        R: EntityTrait,
+        R::Model: Send + Sync,
+        S: EntityOrSelect<R>,
+        <Self::Model as ModelTrait>::Entity: Related<R>,
+    {
+        let rel_def = <<Self::Model as ModelTrait>::Entity as Related<R>>::to();
+        if <<Self::Model as ModelTrait>::Entity as Related<R>>::via().is_some() {
+            return Err(DbErr::Custom(
+                "Relation is many-to-many, use `load_many` instead".to_owned(),
+            ));
+        }
+        if let RelationType::HasMany = rel_def.rel_type {
+            return Err(DbErr::Custom(
+                "Relation is has_many, use `load_many` instead".to_owned(),
+            ));
+        }
+
+        let groups = load_direct(self, stmt, rel_def, db).await?;
+        Ok(groups
+            .into_iter()
+            .map(|models| models.into_iter().next())
+            .collect())
+    }
+
+    /// ```
+    /// # use sea_orm::{error::*, tests_cfg::*, *};
+    /// #
+    /// # #[smol_potat::main]
+    /// # #[cfg(feature = "mock")]
+    /// # pub async fn main() -> Result<(), DbErr> {
+    /// #
+    /// # let db = MockDatabase::new(DbBackend::Postgres)
+    /// #     .append_query_results(vec![vec![
+    /// #         fruit::Model {
+    /// #             id: 1,
+    /// #             name: "Apple".to_owned(),
+    /// #             cake_id: Some(2),
+    /// #         },
+    /// #         fruit::Model {
+    /// #             id: 2,
+    /// #             name: "Orange".to_owned(),
+    /// #             cake_id: Some(2),
+    /// #         },
+    /// #     ]])
+    /// #     .into_connection();
+    /// #
+    /// use sea_orm::{entity::*, query::*, tests_cfg::{cake, fruit}};
+    ///
+    /// let cakes = vec![
+    ///     cake::Model {
+    ///         id: 1,
+    ///         name: "New York Cheese".to_owned(),
+    ///     },
+    ///     cake::Model {
+    ///         id: 2,
+    ///         name: "Chocolate Forest".to_owned(),
+    ///     },
+    /// ];
+    ///
+    /// let fruits: Vec<Vec<fruit::Model>> = cakes.load_many(fruit::Entity, &db).await?;
+    ///
+    /// assert_eq!(fruits[0], vec![]);
+    /// assert_eq!(fruits[1].len(), 2);
+    ///
+    /// assert_eq!(
+    ///     db.into_transaction_log(),
+    ///     vec![Transaction::from_sql_and_values(
+    ///         DbBackend::Postgres,
+    ///         [
+    ///             r#"SELECT "fruit"."id", "fruit"."name", "fruit"."cake_id" FROM "fruit""#,
+    ///             r#"WHERE "fruit"."cake_id" IN ($1, $2)"#,
+    ///         ]
+    ///         .join(" ")
+    ///         .as_str(),
+    ///         vec![1i32.into(), 2i32.into()]
+    ///     )]
+    /// );
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    async fn load_many<R, S, C>(&self, stmt: S, db: &C) -> Result<Vec<Vec<R::Model>>, DbErr>
+    where
+        C: ConnectionTrait,
+        R: EntityTrait,
+        R::Model: Send + Sync,
+        S: EntityOrSelect<R>,
+        <Self::Model as ModelTrait>::Entity: Related<R>,
+    {
+        let rel_def = <<Self::Model as ModelTrait>::Entity as Related<R>>::to();
+        match <<Self::Model as ModelTrait>::Entity as Related<R>>::via() {
+            Some(via_def) => load_via(self, stmt, rel_def, via_def, db).await,
+            None => load_direct(self, stmt, rel_def, db).await,
+        }
+    }
+}
+
+/// Load through a relation where `from_col` is on `M` and `to_col` is on `R`
+async fn load_direct<M, R, S, C>(
+    models: &[M],
+    stmt: S,
+    rel_def: RelationDef,
+    db: &C,
+) -> Result<Vec<Vec<R::Model>>, DbErr>
+where
+    M: ModelTrait,
+    R: EntityTrait,
+    S: EntityOrSelect<R>,
+    C: ConnectionTrait,
+{
+    let keys = models
+        .iter()
+        .map(|model| extract_key(&rel_def.from_col, model))
+        .collect::<Result<Vec<_>, _>>()?;
+    if models.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let unique_keys = unique_keys(&keys);
+    if unique_keys.is_empty() {
+        return Ok(keys.iter().map(|_| Vec::new()).collect());
+    }
+
+    let to_tbl = unpack_table_ref(&rel_def.to_tbl);
+    let select = stmt.select();
+    let chunk_size = chunk_size(db.get_database_backend(), &select, &rel_def.to_col);
+    let mut groups = KeyGroups::default();
+    for chunk in unique_keys.chunks(chunk_size) {
+        let condition = prepare_condition(SeaRc::clone(&to_tbl), &rel_def.to_col, chunk);
+        for model in select.clone().filter(condition).all(db).await? {
+            let key = extract_key(&rel_def.to_col, &model)?;
+            groups.push(key, model);
+        }
+    }
+    Ok(groups.align(&keys))
+}
+
+/// Load through a junction, where `via_def` goes from `M` to the junction
+/// and `rel_def` goes from the junction to `R`
+async fn load_via<M, R, S, C>(
+    models: &[M],
+    stmt: S,
+    rel_def: RelationDef,
+    via_def: RelationDef,
+    db: &C,
+) -> Result<Vec<Vec<R::Model>>, DbErr>
+where
+    M: ModelTrait,
+    R: EntityTrait,
+    S: EntityOrSelect<R>,
+    C: ConnectionTrait,
+{
+    // The junction key is read back with the primary key type of `M`
+    let pk_cols: Vec<String> = <<M::Entity as EntityTrait>::PrimaryKey as Iterable>::iter()
+        .map(|pk| pk.into_column().as_str().to_owned())
+        .collect();
+    let via_cols: Vec<String> = via_def
+        .from_col
+        .columns()
+        .iter()
+        .map(|col| col.to_string())
+        .collect();
+    if via_cols != pk_cols {
+        return Err(DbErr::Custom(
+            "Many-to-many relation must reference the primary key".to_owned(),
+        ));
+    }
+
+    let keys = models
+        .iter()
+        .map(|model| extract_key(&via_def.from_col, model))
+        .collect::<Result<Vec<_>, _>>()?;
+    if models.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let unique_keys = unique_keys(&keys);
+    if unique_keys.is_empty() {
+        return Ok(keys.iter().map(|_| Vec::new()).collect());
+    }
+
+    let junction_tbl = unpack_table_ref(&via_def.to_tbl);
+    let key_cols: Vec<String> = (0..pk_cols.len())
+        .map(|i| format!("{}{}", VIA_KEY_PREFIX, i))
+        .collect();
+    let mut select = stmt.select().join_rev(JoinType::InnerJoin, rel_def);
+    for (col, key_col) in via_def.to_col.columns().into_iter().zip(key_cols.iter()) {
+        select = select.column_as(
+            Expr::tbl(SeaRc::clone(&junction_tbl), col).into_simple_expr(),
+            key_col.as_str(),
+        );
+    }
+
+    let backend = db.get_database_backend();
+    let chunk_size = chunk_size(backend, &select, &via_def.to_col);
+    let mut groups = KeyGroups::default();
+    for chunk in unique_keys.chunks(chunk_size) {
+        let condition = prepare_condition(SeaRc::clone(&junction_tbl), &via_def.to_col, chunk);
+        let stmt = select.clone().filter(condition).build(backend);
+        for row in db.query_all(stmt).await? {
+            let model = R::Model::from_query_result(&row, "")?;
+            let key = <<M::Entity as EntityTrait>::PrimaryKey as PrimaryKeyTrait>::ValueType::try_get_many(
+                &row, "", &key_cols,
+            )?;
+            let key = value_tuple_to_vec(key.into_value_tuple());
+            groups.push(key, model);
+        }
+    }
+    Ok(groups.align(&keys))
+}
+
+/// A normalised key, usable as the key of a map
+///
+/// [Value] is neither `Eq` nor `Hash`: equality falls back to `PartialEq`, and only
+/// the variants keys are usually made of contribute their payload to the hash
+#[derive(Debug, PartialEq)]
+struct GroupKey(Vec<Value>);
+
+impl Eq for GroupKey {}
+
+impl Hash for GroupKey {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        for value in self.0.iter() {
+            std::mem::discriminant(value).hash(state);
+            match value {
+                Value::Bool(v) => v.hash(state),
+                Value::BigInt(v) => v.hash(state),
+                Value::BigUnsigned(v) => v.hash(state),
+                Value::String(v) => v.hash(state),
+                Value::Bytes(v) => v.hash(state),
+                #[cfg(feature = "with-uuid")]
+                Value::Uuid(v) => v.hash(state),
+                _ => {}
+            }
+        }
+    }
+}
+
+impl GroupKey {
+    /// Integer keys are compared regardless of their width and signedness, so that
+    /// e.g. an `i32` foreign key matches an `i64` primary key
+    fn new(key: Vec<Value>) -> Self {
+        Self(
+            key.into_iter()
+                .map(|value| match value {
+                    Value::TinyInt(Some(v)) => Value::BigInt(Some(v.into())),
+                    Value::SmallInt(Some(v)) => Value::BigInt(Some(v.into())),
+                    Value::Int(Some(v)) => Value::BigInt(Some(v.into())),
+                    Value::TinyUnsigned(Some(v)) => Value::BigInt(Some(v.into())),
+                    Value::SmallUnsigned(Some(v)) => Value::BigInt(Some(v.into())),
+                    Value::Unsigned(Some(v)) => Value::BigInt(Some(v.into())),
+                    Value::BigUnsigned(Some(v)) => match i64::try_from(v) {
+                        Ok(v) => Value::BigInt(Some(v)),
+                        Err(_) => Value::BigUnsigned(Some(v)),
+                    },
+                    value => value,
+                })
+                .collect(),
+        )
+    }
+}
+
+/// Related rows grouped by the normalised value of their key
+struct KeyGroups<T> {
+    groups: HashMap<GroupKey, Vec<T>>,
+}
+
+impl<T> Default for KeyGroups<T> {
+    fn default() -> Self {
+        Self {
+            groups: HashMap::new(),
+        }
+    }
+}
+
+impl<T> KeyGroups<T>
+where
+    T: Clone,
+{
+    fn push(&mut self, key: Vec<Value>, item: T) {
+        self.groups
+            .entry(GroupKey::new(key))
+            .or_default()
+            .push(item);
+    }
+
+    /// Distribute the groups in the order of `keys`, a key without rows gets an empty group.
+    /// A group is moved out to the last of its keys and only cloned for the ones before.
+    fn align(mut self, keys: &[Vec<Value>]) -> Vec<Vec<T>> {
+        let keys: Vec<GroupKey> = keys.iter().map(|key| GroupKey::new(key.clone())).collect();
+        let mut remaining: HashMap<&GroupKey, usize> = HashMap::new();
+        for key in keys.iter() {
+            *remaining.entry(key).or_default() += 1;
+        }
+        keys.iter()
+            .map(|key| {
+                let count = remaining.get_mut(key).expect("counted above");
+                *count -= 1;
+                if *count == 0 {
+                    self.groups.remove(key).unwrap_or_default()
+                } else {
+                    self.groups.get(key).cloned().unwrap_or_default()
+                }
+            })
+            .collect()
+    }
+}
+
+/// The distinct keys that can match a row; a null key cannot match any row
+fn unique_keys(keys: &[Vec<Value>]) -> Vec<&Vec<Value>> {
+    let mut seen = HashSet::new();
+    keys.iter()
+        .filter(|key| !key.iter().any(is_null) && seen.insert(GroupKey::new(key.to_vec())))
+        .collect()
+}
+
+/// The number of keys that fit in one query, next to the values already bound by `select`
+fn chunk_size<E>(backend: DbBackend, select: &Select<E>, col: &Identity) -> usize
+where
+    E: EntityTrait,
+{
+    let bound = select
+        .build(backend)
+        .values
+        .map(|values| values.0.len())
+        .unwrap_or_default();
+    let key_len = col.columns().len().max(1);
+    (backend.max_bind_params().saturating_sub(bound) / key_len).max(1)
+}
+
+fn prepare_condition(table: DynIden, col: &Identity, unique_keys: &[&Vec<Value>]) -> Condition {
+    let columns = col.columns();
+    if let [column] = columns.as_slice() {
+        return Condition::all().add(
+            Expr::tbl(SeaRc::clone(&table), SeaRc::clone(column))
+                .is_in(unique_keys.iter().map(|key| key[0].clone())),
+        );
+    }
+    unique_keys.iter().fold(Condition::any(), |cond, key| {
+        let all = columns
+            .iter()
+            .zip(key.iter())
+            .fold(Condition::all(), |all, (column, value)| {
+                all.add(Expr::tbl(SeaRc::clone(&table), SeaRc::clone(column)).eq(value.clone()))
+            });
+        cond.add(all)
+    })
+}
+
+fn extract_key<M>(col: &Identity, model: &M) -> Result<Vec<Value>, DbErr>
+where
+    M: ModelTrait,
+{
+    col.columns()
+        .iter()
+        .map(|col| {
+            let name = col.to_string();
+            <<M::Entity as EntityTrait>::Column as Iterable>::iter()
+                .find(|c| c.as_str() == name)
+                .map(|c| model.get(c))
+                .ok_or_else(|| DbErr::Custom(format!("Column `{}` not found in Model", name)))
+        })
+        .collect()
+}
+
+fn is_null(value: &Value) -> bool {
+    match value {
+        Value::Bool(None)
+        | Value::TinyInt(None)
+        | Value::SmallInt(None)
+        | Value::Int(None)
+        | Value::BigInt(None)
+        | Value::TinyUnsigned(None)
+        | Value::SmallUnsigned(None)
+        | Value::Unsigned(None)
+        | Value::BigUnsigned(None)
+        | Value::Float(None)
+        | Value::Double(None)
+        | Value::String(None)
+        | Value::Bytes(None) => true,
+        #[cfg(feature = "with-json")]
+        Value::Json(None) => true,
+        #[cfg(feature = "with-chrono")]
+        Value::ChronoDate(None)
+        | Value::ChronoTime(None)
+        | Value::ChronoDateTime(None)
+        | Value::ChronoDateTimeUtc(None)
+        | Value::ChronoDateTimeLocal(None)
+        | Value::ChronoDateTimeWithTimeZone(None) => true,
+        #[cfg(feature = "with-time")]
+        Value::TimeDate(None)
+        | Value::TimeTime(None)
+        | Value::TimeDateTime(None)
+        | Value::TimeDateTimeWithTimeZone(None) => true,
+        #[cfg(feature = "with-uuid")]
+        Value::Uuid(None) => true,
+        #[cfg(feature = "with-rust_decimal")]
+        Value::Decimal(None) => true,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "mock")]
+mod tests {
+    use crate::{
+        entity::prelude::*, tests_cfg::*, DbBackend, IntoMockRow, LoaderTrait, MockDatabase,
+        Transaction,
+    };
+    use pretty_assertions::assert_eq;
+
+    fn cake_model(id: i32) -> cake::Model {
+        cake::Model {
+            id,
+            name: format!("Cake {}", id),
+        }
+    }
+
+    fn filling_row(id: i32, cake_id: i32) -> crate::MockRow {
+        maplit::btreemap! {
+            "id" => Into::<Value>::into(id),
+            "name" => Into::<Value>::into(format!("Filling {}", id)),
+            "vendor_id" => Into::<Value>::into(Option::<i32>::None),
+            "loader_key_0" => Into::<Value>::into(cake_id),
+        }
+        .into_mock_row()
+    }
+
+    fn filling_model(id: i32) -> filling::Model {
+        filling::Model {
+            id,
+            name: format!("Filling {}", id),
+            vendor_id: None,
+            ignored_attr: 0,
+        }
+    }
+
+    #[smol_potat::test]
+    async fn load_many_via() -> Result<(), DbErr> {
+        let db = MockDatabase::new(DbBackend::Postgres)
+            .append_query_results(vec![vec![
+                filling_row(1, 1),
+                filling_row(2, 1),
+                filling_row(1, 3),
+            ]])
+            .into_connection();
+
+        let cakes = [cake_model(1), cake_model(2), cake_model(3), cake_model(1)];
+        let fillings = cakes.load_many(filling::Entity, &db).await?;
+
+        assert_eq!(
+            fillings,
+            vec![
+                vec![filling_model(1), filling_model(2)],
+                vec![],
+                vec![filling_model(1)],
+                vec![filling_model(1), filling_model(2)],
+            ]
+        );
+
+        assert_eq!(
+            db.into_transaction_log(),
+            vec![Transaction::from_sql_and_values(
+                DbBackend::Postgres,
+                [
+                    r#"SELECT "filling"."id", "filling"."name", "filling"."vendor_id","#,
+                    r#""cake_filling"."cake_id" AS "loader_key_0" FROM "filling""#,
+                    r#"INNER JOIN "cake_filling" ON "cake_filling"."filling_id" = "filling"."id""#,
+                    r#"WHERE "cake_filling"."cake_id" IN ($1, $2, $3)"#,
+                ]
+                .join(" ")
+                .as_str(),
+                vec![1i32.into(), 2i32.into(), 3i32.into()]
+            )]
+        );
+        Ok(())
+    }
+
+    #[smol_potat::test]
+    async fn load_many_chunked() -> Result<(), DbErr> {
+        let max = DbBackend::Sqlite.max_bind_params() as i32;
+        let db = MockDatabase::new(DbBackend::Sqlite)
+            .append_query_results(vec![
+                vec![fruit::Model {
+                    id: 1,
+                    name: "Apple".to_owned(),
+                    cake_id: Some(1),
+                }],
+                vec![fruit::Model {
+                    id: 2,
+                    name: "Banana".to_owned(),
+                    cake_id: Some(max + 1),
+                }],
+            ])
+            .into_connection();
+
+        let cakes: Vec<_> = (1..=max + 1).map(cake_model).collect();
+        let fruits = cakes.load_many(fruit::Entity, &db).await?;
+
+        assert_eq!(fruits.len(), cakes.len());
+        assert_eq!(fruits[0].len(), 1);
+        assert_eq!(fruits[max as usize].len(), 1);
+        assert_eq!(fruits.iter().map(Vec::len).sum::<usize>(), 2);
+
+        let log = db.into_transaction_log();
+        assert_eq!(log.len(), 2);
+        assert_eq!(
+            log[1],
+            Transaction::from_sql_and_values(
+                DbBackend::Sqlite,
+                [
+                    r#"SELECT "fruit"."id", "fruit"."name", "fruit"."cake_id" FROM "fruit""#,
+                    r#"WHERE "fruit"."cake_id" IN (?)"#,
+                ]
+                .join(" ")
+                .as_str(),
+                vec![(max + 1).into()]
+            )
+        );
+        Ok(())
+    }
+
+    #[smol_potat::test]
+    async fn load_one_composite() -> Result<(), DbErr> {
+        let db = MockDatabase::new(DbBackend::Postgres)
+            .append_query_results(vec![vec![cake_filling::Model {
+                cake_id: 1,
+                filling_id: 2,
+            }]])
+            .into_connection();
+
+        let prices = [
+            cake_filling_price::Model {
+                cake_id: 1,
+                filling_id: 2,
+                ignored_attr: 0,
+            },
+            cake_filling_price::Model {
+                cake_id: 2,
+                filling_id: 1,
+                ignored_attr: 0,
+            },
+        ];
+        let cake_fillings = prices.load_one(cake_filling::Entity, &db).await?;
+
+        assert_eq!(
+            cake_fillings,
+            vec![
+                Some(cake_filling::Model {
+                    cake_id: 1,
+                    filling_id: 2,
+                }),
+                None,
+            ]
+        );
+
+        assert_eq!(
+            db.into_transaction_log(),
+            vec![Transaction::from_sql_and_values(
+                DbBackend::Postgres,
+                [
+                    r#"SELECT "cake_filling"."cake_id", "cake_filling"."filling_id" FROM "cake_filling""#,
+                    r#"WHERE ("cake_filling"."cake_id" = $1 AND "cake_filling"."filling_id" = $2)"#,
+                    r#"OR ("cake_filling"."cake_id" = $3 AND "cake_filling"."filling_id" = $4)"#,
+                ]
+                .join(" ")
+                .as_str(),
+                vec![1i32.into(), 2i32.into(), 2i32.into(), 1i32.into()]
+            )]
+        );
+        Ok(())
+    }
+
+    #[smol_potat::test]
+    async fn load_one_null_keys() -> Result<(), DbErr> {
+        let db = MockDatabase::new(DbBackend::Postgres).into_connection();
+
+        let fruits: Vec<_> = (1..=2)
+            .map(|id| fruit::Model {
+                id,
+                name: format!("Fruit {}", id),
+                cake_id: None,
+            })
+            .collect();
+        let cakes = fruits.load_one(cake::Entity, &db).await?;
+
+        assert_eq!(cakes, vec![None, None]);
+        assert!(db.into_transaction_log().is_empty());
+        Ok(())
+    }
+
+    #[smol_potat::test]
+    async fn load_one_has_many() {
+        let db = MockDatabase::new(DbBackend::Postgres).into_connection();
+
+        assert!([cake_model(1)].load_one(fruit::Entity, &db).await.is_err());
+    }
+}
@@ -5,18 +5,20 @@ mod insert;
 mod join;
 #[cfg(feature = "with-json")]
 mod json;
+mod loader;
 mod select;
 mod traits;
 mod update;
 mod util;
 
-pub use combine::{SelectA, SelectB};
+pub use combine::{SelectA, SelectB, SelectC};
 pub use delete::*;
 pub use helper::*;
 pub use insert::*;
 pub use join::*;
 #[cfg(feature = "with-json")]
 pub use json::*;
+pub use loader::*;
 pub use select::*;
 pub use traits::*;
 pub use update::*;
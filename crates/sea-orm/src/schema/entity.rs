@@ -164,6 +164,8 @@ where
         stmt.primary_key(idx_pk.name(&format!("pk-{}", entity.to_string())).primary());
     }
 
+    // the relations to entities of the same table, such as an expanded entity, share a foreign key
+    let mut foreign_keys: Vec<(String, String)> = Vec::new();
     for relation in E::Relation::iter() {
         let relation = relation.def();
         if relation.is_owner {
@@ -209,6 +211,11 @@ where
         } else {
             format!("fk-{}-{}", from_tbl.to_string(), from_cols.join("-"))
         };
+        let foreign_key = (to_tbl.to_string(), name.clone());
+        if foreign_keys.contains(&foreign_key) {
+            continue;
+        }
+        foreign_keys.push(foreign_key);
         stmt.foreign_key(
             foreign_key_stmt
                 .name(&name)
@@ -4,7 +4,7 @@ use crate::entity::prelude::*;
 #[cfg(feature = "with-json")]
 use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, DeriveFactory)]
 #[cfg_attr(feature = "with-json", derive(Serialize, Deserialize))]
 #[sea_orm(table_name = "cake")]
 pub struct Model {
@@ -10,7 +10,7 @@ impl EntityName for Entity {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, DeriveModel, DeriveActiveModel)]
+#[derive(Clone, Debug, PartialEq, DeriveModel, DeriveActiveModel, DeriveFactory)]
 pub struct Model {
     pub cake_id: i32,
     pub filling_id: i32,
@@ -85,4 +85,10 @@ impl Related<super::cake::Entity> for Entity {
     }
 }
 
+impl Related<super::vendor::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Vendor.def()
+    }
+}
+
 impl ActiveModelBehavior for ActiveModel {}
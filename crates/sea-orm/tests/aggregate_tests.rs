@@ -0,0 +1,97 @@
+pub mod common;
+
+pub use common::{bakery_chain::*, setup::*, TestContext};
+use pretty_assertions::assert_eq;
+use rust_decimal_macros::dec;
+use sea_orm::{entity::prelude::*, entity::*, query::*};
+use uuid::Uuid;
+
+// Run the test locally:
+// DATABASE_URL="sqlite::memory:" cargo test --features sqlx-sqlite,runtime-async-std-native-tls --test aggregate_tests
+#[sea_orm_macros::test]
+#[cfg(any(
+    feature = "sqlx-mysql",
+    feature = "sqlx-sqlite",
+    feature = "sqlx-postgres"
+))]
+async fn main() -> Result<(), DbErr> {
+    let ctx = TestContext::new("aggregate_tests").await;
+    create_tables(&ctx.db).await?;
+    seed_data(&ctx.db).await?;
+    aggregate(&ctx.db).await?;
+    group_count(&ctx.db).await?;
+    ctx.delete().await;
+
+    Ok(())
+}
+
+pub async fn seed_data(db: &DatabaseConnection) -> Result<(), DbErr> {
+    let bakery = bakery::ActiveModel {
+        name: Set("SeaSide Bakery".to_owned()),
+        profit_margin: Set(10.4),
+        ..Default::default()
+    }
+    .insert(db)
+    .await?;
+
+    for (name, price, bakery_id) in [
+        ("Apple Pie", dec!(10.25), Some(bakery.id)),
+        ("Orange Scone", dec!(20.5), Some(bakery.id)),
+        ("Lemon Tart", dec!(4), None),
+    ] {
+        cake::ActiveModel {
+            name: Set(name.to_owned()),
+            price: Set(price),
+            bakery_id: Set(bakery_id),
+            gluten_free: Set(false),
+            serial: Set(Uuid::new_v4()),
+            ..Default::default()
+        }
+        .insert(db)
+        .await?;
+    }
+
+    Ok(())
+}
+
+pub async fn aggregate(db: &DatabaseConnection) -> Result<(), DbErr> {
+    // the SQL types of these aggregates differ across database backends
+    let sum: Option<i64> = cake::Entity::find().sum(cake::Column::Id, db).await?;
+    assert_eq!(sum, Some(6));
+    let sum: Option<i32> = cake::Entity::find().sum(cake::Column::Id, db).await?;
+    assert_eq!(sum, Some(6));
+    let avg: Option<f64> = cake::Entity::find().avg(cake::Column::Id, db).await?;
+    assert_eq!(avg, Some(2.0));
+    let sum: Option<Decimal> = cake::Entity::find().sum(cake::Column::Price, db).await?;
+    assert_eq!(sum, Some(dec!(34.75)));
+
+    // the order, limit and offset are ignored
+    let max: Option<Decimal> = cake::Entity::find()
+        .order_by_asc(cake::Column::Price)
+        .limit(1)
+        .offset(1)
+        .max(cake::Column::Price, db)
+        .await?;
+    assert_eq!(max, Some(dec!(20.5)));
+
+    let min: Option<String> = cake::Entity::find()
+        .filter(cake::Column::Id.gt(100))
+        .min(cake::Column::Name, db)
+        .await?;
+    assert_eq!(min, None);
+
+    Ok(())
+}
+
+pub async fn group_count(db: &DatabaseConnection) -> Result<(), DbErr> {
+    let mut counts: Vec<(Option<i32>, i64)> = cake::Entity::find()
+        .order_by_desc(cake::Column::Name)
+        .limit(1)
+        .group_count(cake::Column::BakeryId, db)
+        .await?;
+    // NULL comes last on Postgres
+    counts.sort();
+    assert_eq!(counts, vec![(None, 1), (Some(1), 2)]);
+
+    Ok(())
+}
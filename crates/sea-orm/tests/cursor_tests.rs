@@ -0,0 +1,129 @@
+pub mod common;
+
+pub use common::{features::*, setup::*, TestContext};
+use pretty_assertions::assert_eq;
+use sea_orm::{entity::prelude::*, entity::*, query::*, FromQueryResult};
+use serde_json::json;
+
+#[sea_orm_macros::test]
+#[cfg(any(
+    feature = "sqlx-mysql",
+    feature = "sqlx-sqlite",
+    feature = "sqlx-postgres"
+))]
+async fn main() -> Result<(), DbErr> {
+    let ctx = TestContext::new("cursor_tests").await;
+    create_tables(&ctx.db).await?;
+    seed_data(&ctx.db).await?;
+    cursor_model(&ctx.db).await?;
+    cursor_json(&ctx.db).await?;
+    cursor_custom_model(&ctx.db).await?;
+    ctx.delete().await;
+
+    Ok(())
+}
+
+pub async fn seed_data(db: &DatabaseConnection) -> Result<(), DbErr> {
+    for (id, action) in [(1, "b"), (2, "a"), (3, "b"), (4, "c")] {
+        applog::ActiveModel {
+            id: Set(id),
+            action: Set(action.to_owned()),
+            json: Set(json!({ "id": id })),
+            created_at: Set("2022-05-01T00:00:00+00:00".parse().unwrap()),
+        }
+        .insert(db)
+        .await?;
+    }
+    Ok(())
+}
+
+pub async fn cursor_model(db: &DatabaseConnection) -> Result<(), DbErr> {
+    let page = applog::Entity::find()
+        .cursor_by((applog::Column::Action, applog::Column::Id))
+        .first(2)
+        .fetch_page(db)
+        .await?;
+    assert_eq!(
+        page.items.iter().map(|m| m.id).collect::<Vec<_>>(),
+        vec![2, 1]
+    );
+    assert!(page.has_next_page);
+
+    let page = applog::Entity::find()
+        .cursor_by((applog::Column::Action, applog::Column::Id))
+        .after_cursor(page.end_cursor.as_ref().unwrap())?
+        .first(2)
+        .fetch_page(db)
+        .await?;
+    assert_eq!(
+        page.items.iter().map(|m| m.id).collect::<Vec<_>>(),
+        vec![3, 4]
+    );
+    assert!(!page.has_next_page);
+
+    Ok(())
+}
+
+pub async fn cursor_json(db: &DatabaseConnection) -> Result<(), DbErr> {
+    let select = || {
+        applog::Entity::find()
+            .select_only()
+            .column(applog::Column::Id)
+            .column(applog::Column::Action)
+            .into_json()
+            .cursor_by((applog::Column::Action, applog::Column::Id))
+    };
+
+    let page = select().last(3).fetch_page(db).await?;
+    assert_eq!(
+        page.items,
+        vec![
+            json!({ "id": 1, "action": "b" }),
+            json!({ "id": 3, "action": "b" }),
+            json!({ "id": 4, "action": "c" }),
+        ]
+    );
+    assert!(page.has_previous_page);
+
+    let items = select()
+        .before_cursor(page.start_cursor.as_ref().unwrap())?
+        .all(db)
+        .await?;
+    assert_eq!(items, vec![json!({ "id": 2, "action": "a" })]);
+
+    Ok(())
+}
+
+pub async fn cursor_custom_model(db: &DatabaseConnection) -> Result<(), DbErr> {
+    #[derive(Debug, PartialEq, FromQueryResult)]
+    struct LogId {
+        log_id: i32,
+    }
+
+    let select = || {
+        applog::Entity::find()
+            .select_only()
+            .column_as(applog::Column::Id, "log_id")
+            .into_model::<LogId>()
+            .cursor_by("log_id")
+    };
+
+    let page = select().desc().first(3).fetch_page(db).await?;
+    assert_eq!(
+        page.items,
+        vec![
+            LogId { log_id: 4 },
+            LogId { log_id: 3 },
+            LogId { log_id: 2 }
+        ]
+    );
+
+    let items = select()
+        .desc()
+        .after_cursor(page.end_cursor.as_ref().unwrap())?
+        .all(db)
+        .await?;
+    assert_eq!(items, vec![LogId { log_id: 1 }]);
+
+    Ok(())
+}
@@ -2,7 +2,7 @@ pub mod common;
 
 pub use common::{bakery_chain::*, setup::*, TestContext};
 pub use sea_orm::{entity::prelude::*, *};
-pub use sea_query::Query;
+pub use sea_query::{Expr, Query};
 
 #[sea_orm_macros::test]
 #[cfg(any(
@@ -63,7 +63,35 @@ async fn main() -> Result<(), DbErr> {
         assert!(update_res.rows_affected() > 0);
     }
 
+    update_and_delete_many_with_returning(db).await?;
+
     ctx.delete().await;
 
     Ok(())
 }
+
+pub async fn update_and_delete_many_with_returning(db: &DatabaseConnection) -> Result<(), DbErr> {
+    use bakery::*;
+
+    let updated = Entity::update_many()
+        .col_expr(Column::Name, Expr::value("Top Bakery"))
+        .filter(Column::Name.eq("Bakery Shop"))
+        .exec_with_returning(db)
+        .await;
+    let deleted = Entity::delete_many()
+        .filter(Column::Name.eq("Top Bakery"))
+        .exec_with_returning(db)
+        .await;
+
+    if db.support_returning() {
+        let updated = updated?;
+        assert_eq!(updated.len(), 1);
+        assert_eq!(updated[0].name, "Top Bakery");
+        assert_eq!(deleted?, updated);
+    } else {
+        assert!(updated.is_err());
+        assert!(deleted.is_err());
+    }
+
+    Ok(())
+}
@@ -0,0 +1,85 @@
+pub mod common;
+
+pub use sea_orm::{
+    ConnectOptions, ConnectionTrait, Database, DbBackend, DbConn, DbErr, SqliteJournalMode,
+    SqliteSynchronous, Statement,
+};
+pub use std::time::Duration;
+
+// cargo test --features sqlx-sqlite,runtime-async-std-native-tls --test sqlite_options_tests
+#[sea_orm_macros::test]
+#[cfg(feature = "sqlx-sqlite")]
+async fn main() -> Result<(), DbErr> {
+    let path = std::env::temp_dir().join(format!("sqlite_options_{}.db", std::process::id()));
+    let url = format!("sqlite://{}", path.display());
+
+    connect_with_sqlite_options(&url).await?;
+    split_writer_requires_wal(&url).await;
+
+    for suffix in ["", "-wal", "-shm"] {
+        let _ = std::fs::remove_file(format!("{}{}", path.display(), suffix));
+    }
+    Ok(())
+}
+
+#[cfg(feature = "sqlx-sqlite")]
+async fn pragma(db: &DbConn, name: &str) -> Result<String, DbErr> {
+    let stmt = Statement::from_string(DbBackend::Sqlite, format!("PRAGMA {}", name));
+    let row = db.query_one(stmt).await?.unwrap();
+    // `PRAGMA busy_timeout` names its column `timeout`
+    let column = if name == "busy_timeout" { "timeout" } else { name };
+    Ok(match row.try_get::<String>("", column) {
+        Ok(value) => value,
+        Err(_) => row.try_get::<i64>("", column)?.to_string(),
+    })
+}
+
+#[cfg(feature = "sqlx-sqlite")]
+async fn connect_with_sqlite_options(url: &str) -> Result<(), DbErr> {
+    let mut opt = ConnectOptions::new(url.to_owned());
+    opt.max_connections(2)
+        .sqlite_journal_mode(SqliteJournalMode::Wal)
+        .sqlite_synchronous(SqliteSynchronous::Normal)
+        .sqlite_busy_timeout(Duration::from_millis(1500))
+        .sqlite_foreign_keys(false)
+        .sqlite_create_if_missing(true)
+        .sqlite_cache_size(-4000)
+        .sqlite_split_writer(true);
+    let db = Database::connect(opt).await?;
+
+    assert_eq!(pragma(&db, "journal_mode").await?, "wal");
+    // NORMAL
+    assert_eq!(pragma(&db, "synchronous").await?, "1");
+    assert_eq!(pragma(&db, "busy_timeout").await?, "1500");
+    assert_eq!(pragma(&db, "foreign_keys").await?, "0");
+    assert_eq!(pragma(&db, "cache_size").await?, "-4000");
+
+    // the statement creating the table runs on the writer, the select on a reader
+    db.execute(Statement::from_string(
+        DbBackend::Sqlite,
+        "CREATE TABLE IF NOT EXISTS cake (id INTEGER PRIMARY KEY)".to_owned(),
+    ))
+    .await?;
+    assert!(db
+        .query_all(Statement::from_string(
+            DbBackend::Sqlite,
+            "SELECT * FROM cake".to_owned(),
+        ))
+        .await?
+        .is_empty());
+    db.close().await;
+
+    Ok(())
+}
+
+#[cfg(feature = "sqlx-sqlite")]
+async fn split_writer_requires_wal(url: &str) {
+    let mut opt = ConnectOptions::new(url.to_owned());
+    opt.sqlite_split_writer(true);
+    assert!(matches!(Database::connect(opt).await, Err(DbErr::Conn(_))));
+
+    let mut opt = ConnectOptions::new("sqlite::memory:".to_owned());
+    opt.sqlite_journal_mode(SqliteJournalMode::Wal)
+        .sqlite_split_writer(true);
+    assert!(matches!(Database::connect(opt).await, Err(DbErr::Conn(_))));
+}
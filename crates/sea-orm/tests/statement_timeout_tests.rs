@@ -0,0 +1,64 @@
+pub mod common;
+
+pub use sea_orm::{
+    ConnectOptions, ConnectionTrait, Database, DbBackend, DbConn, DbErr, DriverErrKind, Statement,
+    TransactionTrait,
+};
+pub use std::time::Duration;
+
+// cargo test --features sqlx-sqlite,runtime-async-std-native-tls --test statement_timeout_tests
+#[sea_orm_macros::test]
+#[cfg(feature = "sqlx-sqlite")]
+async fn main() -> Result<(), DbErr> {
+    let db = Database::connect("sqlite::memory:").await?;
+    query_with_timeout(&db).await?;
+
+    let mut opt = ConnectOptions::new("sqlite::memory:".to_owned());
+    opt.statement_timeout(Duration::from_millis(100));
+    let db = Database::connect(opt).await?;
+    transaction_with_statement_timeout(&db).await?;
+
+    Ok(())
+}
+
+/// A query counting forever
+#[cfg(feature = "sqlx-sqlite")]
+fn endless_query() -> Statement {
+    Statement::from_string(
+        DbBackend::Sqlite,
+        "WITH RECURSIVE c(x) AS (SELECT 1 UNION ALL SELECT x + 1 FROM c) SELECT max(x) FROM c"
+            .to_owned(),
+    )
+}
+
+#[cfg(feature = "sqlx-sqlite")]
+fn assert_timeout<T>(res: Result<T, DbErr>) {
+    match res {
+        Err(DbErr::Driver(err)) => assert_eq!(err.kind, Some(DriverErrKind::Timeout)),
+        Err(err) => panic!("unexpected error: {}", err),
+        Ok(_) => panic!("the query did not time out"),
+    }
+}
+
+#[cfg(feature = "sqlx-sqlite")]
+async fn query_with_timeout(db: &DbConn) -> Result<(), DbErr> {
+    assert_timeout(
+        db.query_one_with_timeout(endless_query(), Some(Duration::from_millis(100)))
+            .await,
+    );
+
+    // the statement has been interrupted, the connection can be used again
+    let select_one = Statement::from_string(DbBackend::Sqlite, "SELECT 1".to_owned());
+    assert!(db.query_one(select_one).await?.is_some());
+
+    Ok(())
+}
+
+#[cfg(feature = "sqlx-sqlite")]
+async fn transaction_with_statement_timeout(db: &DbConn) -> Result<(), DbErr> {
+    let txn = db.begin().await?;
+    assert_timeout(txn.query_all(endless_query()).await);
+    txn.commit().await?;
+
+    Ok(())
+}
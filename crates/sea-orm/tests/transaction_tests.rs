@@ -686,3 +686,50 @@ pub async fn transaction_nested() {
 
     ctx.delete().await;
 }
+
+#[sea_orm_macros::test]
+#[cfg(any(
+    feature = "sqlx-mysql",
+    feature = "sqlx-sqlite",
+    feature = "sqlx-postgres"
+))]
+pub async fn transaction_with_config() -> Result<(), DbErr> {
+    let ctx = TestContext::new("transaction_with_config_test").await;
+    create_tables(&ctx.db).await?;
+
+    // a locking transaction on SQLite
+    let txn = ctx
+        .db
+        .begin_with_config(
+            Some(IsolationLevel::Serializable),
+            Some(AccessMode::ReadWrite),
+        )
+        .await?;
+    bakery::ActiveModel {
+        name: Set("SeaSide Bakery".to_owned()),
+        profit_margin: Set(10.4),
+        ..Default::default()
+    }
+    .save(&txn)
+    .await?;
+    txn.commit().await?;
+
+    let txn = ctx
+        .db
+        .begin_with_config(Some(IsolationLevel::ReadCommitted), None)
+        .await?;
+    bakery::ActiveModel {
+        name: Set("Top Bakery".to_owned()),
+        profit_margin: Set(15.0),
+        ..Default::default()
+    }
+    .save(&txn)
+    .await?;
+    assert_eq!(bakery::Entity::find().all(&txn).await?.len(), 2);
+    txn.rollback().await?;
+
+    assert_eq!(bakery::Entity::find().all(&ctx.db).await?.len(), 1);
+
+    ctx.delete().await;
+    Ok(())
+}